@@ -0,0 +1,566 @@
+//! Reading of [Inno Setup] installer headers.
+//!
+//! [Inno Setup]: https://jrsoftware.org/isinfo.php
+
+use alloc::{
+    collections::BTreeSet,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+use std::io::{Read, Seek};
+
+use ::inno::{
+    error::InnoError,
+    header::{Architecture as InnoArchitecture, PrivilegeLevel},
+};
+
+use crate::{
+    Version,
+    installer::{
+        AppsAndFeaturesEntry, Architecture, InstallationMetadata, Installer, InstallerSwitches,
+        InstallerType, Scope, switches::CustomSwitch,
+    },
+    utils::RelativeDir,
+};
+
+/// Inno Setup appends `_is1` to the `AppId` to create the Uninstall registry key.
+const PRODUCT_CODE_SUFFIX: &str = "_is1";
+
+/// The directory that Inno Setup uses for `{autopf}` in non-administrative install mode.
+const USER_PROGRAMS: &str = r"%LocalAppData%\Programs";
+
+/// The directory that Inno Setup uses for `{autocf}` in non-administrative install mode.
+const USER_COMMON_FILES: &str = r"%LocalAppData%\Programs\Common";
+
+/// The value of the [`PrivilegesRequired`] directive.
+///
+/// [`PrivilegesRequired`]: https://jrsoftware.org/ishelp/topic_setup_privilegesrequired.htm
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum PrivilegesRequired {
+    /// Setup runs with the privileges of the user that started it.
+    None,
+
+    /// Setup requests power user privileges. Only used by installers made with Inno Setup 5 and
+    /// earlier.
+    PowerUser,
+
+    /// Setup always runs with administrative privileges and in administrative install mode.
+    #[default]
+    Admin,
+
+    /// Setup never requests administrative privileges and always runs in non-administrative
+    /// install mode.
+    Lowest,
+}
+
+impl PrivilegesRequired {
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::PowerUser => "poweruser",
+            Self::Admin => "admin",
+            Self::Lowest => "lowest",
+        }
+    }
+}
+
+impl fmt::Display for PrivilegesRequired {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl From<PrivilegeLevel> for PrivilegesRequired {
+    fn from(level: PrivilegeLevel) -> Self {
+        match level {
+            PrivilegeLevel::None => Self::None,
+            PrivilegeLevel::PowerUser => Self::PowerUser,
+            PrivilegeLevel::Admin => Self::Admin,
+            PrivilegeLevel::Lowest => Self::Lowest,
+        }
+    }
+}
+
+/// The installer-relevant values of an Inno Setup header.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Inno {
+    /// The `AppId` directive.
+    pub app_id: Option<String>,
+
+    /// The `AppName` directive.
+    pub app_name: Option<String>,
+
+    /// The `AppVerName` directive.
+    pub app_versioned_name: Option<String>,
+
+    /// The `AppVersion` directive.
+    pub app_version: Option<Version>,
+
+    /// The `AppPublisher` directive.
+    pub app_publisher: Option<String>,
+
+    /// The `PrivilegesRequired` directive.
+    pub privileges_required: PrivilegesRequired,
+
+    /// Whether the `PrivilegesRequiredOverridesAllowed` directive allows the install mode to be
+    /// chosen with the `/ALLUSERS` and `/CURRENTUSER` command line parameters.
+    pub privileges_required_overridable: bool,
+
+    /// The architectures from the `ArchitecturesAllowed` directive.
+    ///
+    /// This is empty if the installer is allowed to install on any architecture.
+    pub architectures_allowed: BTreeSet<Architecture>,
+
+    /// Whether the `ArchitecturesInstallIn64BitMode` directive enables 64-bit install mode on any
+    /// architecture.
+    pub install_in_64_bit_mode: bool,
+
+    /// The `DefaultDirName` directive.
+    pub default_dir_name: Option<String>,
+}
+
+impl Inno {
+    /// Reads the setup header of an Inno Setup installer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the reader is not an Inno Setup installer or if its header could not be
+    /// read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    ///
+    /// use winget_types::analysis::inno::Inno;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let inno = Inno::new(File::open("npp.8.7.Installer.x64.exe")?)?;
+    ///
+    ///     println!("Product code: {:?}", inno.product_code());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn new<R: Read + Seek>(reader: R) -> Result<Self, InnoError> {
+        let inno = ::inno::Inno::new(reader)?;
+        let header = inno.header();
+
+        Ok(Self {
+            app_id: header.app_id().map(str::to_owned),
+            app_name: header.app_name().map(str::to_owned),
+            app_versioned_name: header.app_versioned_name().map(str::to_owned),
+            app_version: header.app_version().map(Version::new),
+            app_publisher: header.app_publisher().map(str::to_owned),
+            privileges_required: header.privileges_required().into(),
+            privileges_required_overridable: !header
+                .privileges_required_overrides_allowed()
+                .is_empty(),
+            architectures_allowed: architectures(header.architectures_allowed()),
+            install_in_64_bit_mode: !header.architectures_install_in_64_bit_mode().is_empty(),
+            default_dir_name: header.default_dir_name().map(str::to_owned),
+        })
+    }
+
+    /// Returns the product code of the Apps and Features entry that the installer creates, which is
+    /// the `AppId` followed by `_is1`.
+    ///
+    /// Inno Setup uses the `AppName` when no `AppId` is specified.
+    #[must_use]
+    pub fn product_code(&self) -> Option<String> {
+        let app_id = self.app_id.as_deref().or(self.app_name.as_deref())?;
+
+        // A leading `{` is escaped as `{{` so that it is not read as an Inno Setup constant
+        let app_id = app_id
+            .strip_prefix('{')
+            .filter(|id| id.starts_with('{'))
+            .unwrap_or(app_id);
+
+        Some(format!("{app_id}{PRODUCT_CODE_SUFFIX}"))
+    }
+
+    /// Returns the display name of the Apps and Features entry.
+    ///
+    /// This is the `AppVerName` if specified. Otherwise, Inno Setup combines the `AppName` and the
+    /// `AppVersion` using the installer's language, so the English form is returned.
+    #[must_use]
+    pub fn display_name(&self) -> Option<String> {
+        if let Some(app_versioned_name) = &self.app_versioned_name {
+            return Some(app_versioned_name.clone());
+        }
+
+        let app_name = self.app_name.as_deref()?;
+        Some(self.app_version.as_ref().map_or_else(
+            || app_name.to_string(),
+            |version| format!("{app_name} version {version}"),
+        ))
+    }
+
+    /// Returns the scope that the installer installs to.
+    ///
+    /// Installers that allow the install mode to be overridden can install to either scope, so no
+    /// scope is returned. When `PrivilegesRequired` is `none`, the install mode depends on the
+    /// user running the installer, so the scope is taken from the default directory instead.
+    #[must_use]
+    pub fn scope(&self) -> Option<Scope> {
+        if self.privileges_required_overridable {
+            return None;
+        }
+
+        match self.privileges_required {
+            PrivilegesRequired::Admin | PrivilegesRequired::PowerUser => Some(Scope::Machine),
+            PrivilegesRequired::Lowest => Some(Scope::User),
+            PrivilegesRequired::None => self
+                .default_dir_name
+                .as_deref()
+                .and_then(|default_dir_name| expand_constants(default_dir_name, None, false))
+                .and_then(Scope::from_install_directory),
+        }
+    }
+
+    /// Returns the architectures that the installer can install on.
+    ///
+    /// Installers that do not restrict the architecture run in 32-bit install mode, so they are
+    /// considered to be [`x86`].
+    ///
+    /// [`x86`]: Architecture::X86
+    #[must_use]
+    pub fn architectures(&self) -> BTreeSet<Architecture> {
+        if self.architectures_allowed.is_empty() {
+            BTreeSet::from([Architecture::X86])
+        } else {
+            self.architectures_allowed.clone()
+        }
+    }
+
+    /// Returns the default install location for the given scope, with Inno Setup directory
+    /// constants replaced by their [`RelativeDir`] equivalents.
+    ///
+    /// Returns `None` if the default directory contains a constant that cannot be expressed as a
+    /// relative directory, such as `{code:...}` or `{reg:...}`.
+    #[must_use]
+    pub fn default_install_location(&self, scope: Scope) -> Option<String> {
+        expand_constants(
+            self.default_dir_name.as_deref()?,
+            Some(scope),
+            self.install_in_64_bit_mode,
+        )
+    }
+
+    /// Returns the Apps and Features entry that the installer creates.
+    #[must_use]
+    pub fn apps_and_features_entry(&self) -> AppsAndFeaturesEntry {
+        AppsAndFeaturesEntry::builder()
+            .maybe_display_name(self.display_name())
+            .maybe_publisher(self.app_publisher.as_deref())
+            .maybe_display_version(self.app_version.clone())
+            .maybe_product_code(self.product_code())
+            .build()
+    }
+
+    /// Creates an [`Installer`] for each architecture and scope that the installer supports.
+    ///
+    /// Installers that allow the install mode to be overridden produce an installer for each scope,
+    /// with the `/CURRENTUSER` or `/ALLUSERS` custom switch to select it.
+    #[must_use]
+    pub fn installers(&self) -> Vec<Installer> {
+        let scopes = if self.privileges_required_overridable {
+            vec![Some(Scope::User), Some(Scope::Machine)]
+        } else {
+            vec![self.scope()]
+        };
+
+        let product_code = self.product_code();
+        let apps_and_features_entry = self.apps_and_features_entry();
+
+        self.architectures()
+            .into_iter()
+            .flat_map(|architecture| scopes.iter().map(move |&scope| (architecture, scope)))
+            .map(|(architecture, scope)| Installer {
+                architecture,
+                r#type: Some(InstallerType::Inno),
+                scope,
+                switches: InstallerSwitches::builder()
+                    .maybe_custom(self.privileges_required_overridable.then(|| match scope {
+                        Some(Scope::User) => CustomSwitch::current_user(),
+                        _ => CustomSwitch::all_users(),
+                    }))
+                    .build(),
+                product_code: product_code.clone(),
+                apps_and_features_entries: apps_and_features_entry.clone().into(),
+                installation_metadata: InstallationMetadata {
+                    default_install_location: scope
+                        .and_then(|scope| self.default_install_location(scope))
+                        .map(Into::into),
+                    ..InstallationMetadata::default()
+                },
+                ..Installer::default()
+            })
+            .collect()
+    }
+}
+
+fn architectures(allowed: InnoArchitecture) -> BTreeSet<Architecture> {
+    allowed
+        .iter()
+        .filter_map(|architecture| match architecture {
+            InnoArchitecture::X64_COMPATIBLE
+            | InnoArchitecture::X64_OS
+            | InnoArchitecture::WIN64 => Some(Architecture::X64),
+            InnoArchitecture::ARM64 => Some(Architecture::Arm64),
+            InnoArchitecture::X86_COMPATIBLE | InnoArchitecture::X86_OS => Some(Architecture::X86),
+            InnoArchitecture::ARM32_COMPATIBLE => Some(Architecture::Arm),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Replaces the Inno Setup [directory constants] in a path with their [`RelativeDir`]
+/// equivalents.
+///
+/// Constants whose value depends on the install mode are only expanded when a scope is given.
+///
+/// [directory constants]: https://jrsoftware.org/ishelp/topic_consts.htm
+fn expand_constants(path: &str, scope: Option<Scope>, is_64_bit: bool) -> Option<String> {
+    let program_files = if is_64_bit {
+        RelativeDir::PROGRAM_FILES_64
+    } else {
+        RelativeDir::PROGRAM_FILES_32
+    };
+    let common_files = if is_64_bit {
+        RelativeDir::COMMON_FILES_64
+    } else {
+        RelativeDir::COMMON_FILES_32
+    };
+    let by_scope = |user: &'static str, machine: &'static str| match scope? {
+        Scope::User => Some(user),
+        Scope::Machine => Some(machine),
+    };
+
+    let mut expanded = String::with_capacity(path.len());
+    let mut rest = path;
+
+    while let Some(start) = rest.find('{') {
+        expanded.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+
+        if let Some(after_escape) = rest.strip_prefix('{') {
+            expanded.push('{');
+            rest = after_escape;
+            continue;
+        }
+
+        let end = rest.find('}')?;
+        let directory = match rest[..end].to_ascii_lowercase().as_str() {
+            "pf" | "commonpf" => Some(program_files),
+            "pf32" | "commonpf32" => Some(RelativeDir::PROGRAM_FILES_32),
+            "pf64" | "commonpf64" => Some(RelativeDir::PROGRAM_FILES_64),
+            "autopf" => by_scope(USER_PROGRAMS, program_files),
+            "autopf32" => by_scope(USER_PROGRAMS, RelativeDir::PROGRAM_FILES_32),
+            "autopf64" => by_scope(USER_PROGRAMS, RelativeDir::PROGRAM_FILES_64),
+            "cf" | "commoncf" => Some(common_files),
+            "cf32" | "commoncf32" => Some(RelativeDir::COMMON_FILES_32),
+            "cf64" | "commoncf64" => Some(RelativeDir::COMMON_FILES_64),
+            "autocf" => by_scope(USER_COMMON_FILES, common_files),
+            "autocf32" => by_scope(USER_COMMON_FILES, RelativeDir::COMMON_FILES_32),
+            "autocf64" => by_scope(USER_COMMON_FILES, RelativeDir::COMMON_FILES_64),
+            "userpf" => Some(USER_PROGRAMS),
+            "usercf" => Some(USER_COMMON_FILES),
+            "localappdata" => Some(RelativeDir::LOCAL_APP_DATA),
+            "userappdata" => Some(RelativeDir::APP_DATA),
+            "commonappdata" => Some(RelativeDir::PROGRAM_DATA),
+            "autoappdata" => by_scope(RelativeDir::APP_DATA, RelativeDir::PROGRAM_DATA),
+            "win" => Some(RelativeDir::WINDOWS_DIR),
+            "sd" => Some(RelativeDir::SYSTEM_DRIVE),
+            "tmp" => Some(RelativeDir::TEMP_FOLDER),
+            _ => None,
+        }?;
+
+        expanded.push_str(directory);
+        rest = &rest[end + 1..];
+    }
+
+    expanded.push_str(rest);
+    Some(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeSet;
+
+    use rstest::rstest;
+
+    use super::{Inno, PrivilegesRequired, expand_constants};
+    use crate::{
+        Version,
+        installer::{Architecture, Scope},
+    };
+
+    fn notepad_plus_plus() -> Inno {
+        Inno {
+            app_id: Some("{{1D8E4D24-5B7A-4C1E-9B0A-3C8A6C1F2E9D}".into()),
+            app_name: Some("Notepad++".into()),
+            app_version: Some(Version::new("8.7")),
+            app_publisher: Some("Notepad++ Team".into()),
+            architectures_allowed: BTreeSet::from([Architecture::X64]),
+            install_in_64_bit_mode: true,
+            default_dir_name: Some(r"{autopf}\Notepad++".into()),
+            ..Inno::default()
+        }
+    }
+
+    #[test]
+    fn product_code() {
+        assert_eq!(
+            notepad_plus_plus().product_code().as_deref(),
+            Some("{1D8E4D24-5B7A-4C1E-9B0A-3C8A6C1F2E9D}_is1")
+        );
+
+        let inno = Inno {
+            app_name: Some("Example".into()),
+            ..Inno::default()
+        };
+        assert_eq!(inno.product_code().as_deref(), Some("Example_is1"));
+    }
+
+    #[test]
+    fn display_name() {
+        assert_eq!(
+            notepad_plus_plus().display_name().as_deref(),
+            Some("Notepad++ version 8.7")
+        );
+    }
+
+    #[rstest]
+    #[case(PrivilegesRequired::Admin, false, Some(Scope::Machine))]
+    #[case(PrivilegesRequired::PowerUser, false, Some(Scope::Machine))]
+    #[case(PrivilegesRequired::Lowest, false, Some(Scope::User))]
+    #[case(PrivilegesRequired::Lowest, true, None)]
+    #[case(PrivilegesRequired::None, false, None)]
+    fn scope(
+        #[case] privileges_required: PrivilegesRequired,
+        #[case] privileges_required_overridable: bool,
+        #[case] expected: Option<Scope>,
+    ) {
+        let inno = Inno {
+            privileges_required,
+            privileges_required_overridable,
+            ..notepad_plus_plus()
+        };
+
+        assert_eq!(inno.scope(), expected);
+    }
+
+    #[test]
+    fn scope_from_default_dir() {
+        let inno = Inno {
+            privileges_required: PrivilegesRequired::None,
+            default_dir_name: Some(r"{localappdata}\Example".into()),
+            ..Inno::default()
+        };
+
+        assert_eq!(inno.scope(), Some(Scope::User));
+    }
+
+    #[test]
+    fn overridable_installers() {
+        let inno = Inno {
+            privileges_required: PrivilegesRequired::Lowest,
+            privileges_required_overridable: true,
+            architectures_allowed: BTreeSet::from([Architecture::X64, Architecture::Arm64]),
+            ..notepad_plus_plus()
+        };
+
+        let installers = inno
+            .installers()
+            .into_iter()
+            .map(|installer| {
+                (
+                    installer.architecture,
+                    installer.scope,
+                    installer.switches.custom().map(ToString::to_string),
+                    installer
+                        .installation_metadata
+                        .default_install_location
+                        .map(|location| location.to_string()),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            installers,
+            [
+                (
+                    Architecture::X64,
+                    Some(Scope::User),
+                    Some("/CURRENTUSER".into()),
+                    Some(r"%LocalAppData%\Programs\Notepad++".into())
+                ),
+                (
+                    Architecture::X64,
+                    Some(Scope::Machine),
+                    Some("/ALLUSERS".into()),
+                    Some(r"%ProgramFiles%\Notepad++".into())
+                ),
+                (
+                    Architecture::Arm64,
+                    Some(Scope::User),
+                    Some("/CURRENTUSER".into()),
+                    Some(r"%LocalAppData%\Programs\Notepad++".into())
+                ),
+                (
+                    Architecture::Arm64,
+                    Some(Scope::Machine),
+                    Some("/ALLUSERS".into()),
+                    Some(r"%ProgramFiles%\Notepad++".into())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn unrestricted_architecture() {
+        let installers = Inno::default().installers();
+
+        assert_eq!(installers.len(), 1);
+        assert_eq!(installers[0].architecture, Architecture::X86);
+        assert_eq!(installers[0].scope, Some(Scope::Machine));
+        assert!(installers[0].switches.custom().is_none());
+    }
+
+    #[rstest]
+    #[case(
+        r"{autopf}\App",
+        Some(Scope::Machine),
+        false,
+        Some(r"%ProgramFiles(x86)%\App")
+    )]
+    #[case(r"{pf64}\App", None, false, Some(r"%ProgramFiles%\App"))]
+    #[case(r"{autoappdata}\App", Some(Scope::User), true, Some(r"%AppData%\App"))]
+    #[case(r"{userpf}\App", None, true, Some(r"%LocalAppData%\Programs\App"))]
+    #[case(
+        r"{usercf}\App",
+        None,
+        true,
+        Some(r"%LocalAppData%\Programs\Common\App")
+    )]
+    #[case(r"{localappdata}\App", None, true, Some(r"%LocalAppData%\App"))]
+    #[case(r"{sd}\{{App}", None, true, Some(r"%SystemDrive%\{App}"))]
+    #[case(r"C:\App", None, true, Some(r"C:\App"))]
+    #[case(r"{autopf}\App", None, true, None)]
+    #[case(r"{code:GetDir}\App", Some(Scope::User), true, None)]
+    #[case(r"{pf\App", None, true, None)]
+    fn expand(
+        #[case] path: &str,
+        #[case] scope: Option<Scope>,
+        #[case] is_64_bit: bool,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(
+            expand_constants(path, scope, is_64_bit).as_deref(),
+            expected
+        );
+    }
+}
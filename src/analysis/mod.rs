@@ -0,0 +1,14 @@
+//! Extraction of manifest values from installer files.
+
+#[cfg(feature = "inno")]
+pub mod inno;
+#[cfg(feature = "msi")]
+pub mod msi;
+#[cfg(feature = "msix")]
+pub mod msix;
+#[cfg(feature = "pe")]
+pub mod pe;
+#[cfg(feature = "registry")]
+pub mod registry;
+#[cfg(feature = "zip")]
+pub mod zip;
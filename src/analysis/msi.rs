@@ -0,0 +1,479 @@
+//! Reading of Windows Installer (`.msi`) databases.
+
+use alloc::{collections::BTreeSet, format, string::String};
+use std::{
+    collections::BTreeMap,
+    io::{self, Read, Seek},
+};
+
+use msi::{Package, Select};
+
+use crate::{
+    Version,
+    installer::{
+        AppsAndFeaturesEntry, Architecture, FileExtension, Installer, InstallerType, Protocol,
+        Scope,
+    },
+};
+
+const PROPERTY: &str = "Property";
+const EXTENSION: &str = "Extension";
+const REGISTRY: &str = "Registry";
+
+const PRODUCT_CODE: &str = "ProductCode";
+const UPGRADE_CODE: &str = "UpgradeCode";
+const PRODUCT_VERSION: &str = "ProductVersion";
+const MANUFACTURER: &str = "Manufacturer";
+const PRODUCT_NAME: &str = "ProductName";
+const ALL_USERS: &str = "ALLUSERS";
+
+// The registry roots used by the `Root` column of the `Registry` table. The default root is
+// `HKEY_CURRENT_USER` or `HKEY_LOCAL_MACHINE` depending on whether the installation is per-user or
+// per-machine.
+const REGISTRY_ROOT_DEFAULT: i32 = -1;
+const HKEY_CLASSES_ROOT: i32 = 0;
+const HKEY_CURRENT_USER: i32 = 1;
+const HKEY_LOCAL_MACHINE: i32 = 2;
+const CLASSES_KEY: &str = r"Software\Classes\";
+
+/// The installer-relevant values of an MSI database.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Msi {
+    /// The `ProductCode` property.
+    pub product_code: Option<String>,
+
+    /// The `UpgradeCode` property.
+    pub upgrade_code: Option<String>,
+
+    /// The `ProductVersion` property.
+    pub product_version: Option<Version>,
+
+    /// The `Manufacturer` property.
+    pub manufacturer: Option<String>,
+
+    /// The `ProductName` property.
+    pub product_name: Option<String>,
+
+    /// The `ALLUSERS` property.
+    pub all_users: Option<String>,
+
+    /// The architecture from the template summary property.
+    pub architecture: Option<Architecture>,
+
+    /// Whether the database was created by the [WiX Toolset].
+    ///
+    /// [WiX Toolset]: https://wixtoolset.org/
+    pub is_wix: bool,
+
+    /// The file extensions from the `Extension` table and from file association keys in the
+    /// `Registry` table.
+    pub file_extensions: BTreeSet<FileExtension>,
+
+    /// The URI schemes registered with a `URL Protocol` value in the `Registry` table.
+    pub protocols: BTreeSet<Protocol>,
+}
+
+impl Msi {
+    /// Reads the `Property`, `Extension` and `Registry` tables of an MSI database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the reader is not a valid MSI database, if a table could not be read, or
+    /// if the `Property`, `Extension` or `Registry` table lacks a standard column.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::{fs::File, io};
+    ///
+    /// use winget_types::analysis::msi::Msi;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let msi = Msi::new(File::open("7z2409-x64.msi")?)?;
+    ///
+    ///     println!("Product code: {:?}", msi.product_code);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn new<R: Read + Seek>(reader: R) -> io::Result<Self> {
+        let mut package = Package::open(reader)?;
+
+        check_columns(&package, PROPERTY, &[PROPERTY, "Value"])?;
+        let mut properties = package
+            .select_rows(Select::table(PROPERTY))?
+            .filter_map(|row| {
+                Some((
+                    row[PROPERTY].as_str()?.to_owned(),
+                    row["Value"].as_str()?.to_owned(),
+                ))
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let summary_info = package.summary_info();
+        let architecture = summary_info.arch().and_then(architecture);
+        let is_wix = summary_info
+            .creating_application()
+            .is_some_and(|application| {
+                application.contains("WiX") || application.contains("Windows Installer XML")
+            })
+            || properties
+                .keys()
+                .any(|property| property.starts_with("Wix"));
+
+        let mut msi = Self {
+            product_code: properties.remove(PRODUCT_CODE),
+            upgrade_code: properties.remove(UPGRADE_CODE),
+            product_version: properties.remove(PRODUCT_VERSION).map(Version::new),
+            manufacturer: properties.remove(MANUFACTURER),
+            product_name: properties.remove(PRODUCT_NAME),
+            all_users: properties.remove(ALL_USERS),
+            architecture,
+            is_wix,
+            ..Self::default()
+        };
+
+        if package.has_table(EXTENSION) {
+            check_columns(&package, EXTENSION, &[EXTENSION])?;
+            msi.file_extensions.extend(
+                package
+                    .select_rows(Select::table(EXTENSION))?
+                    .filter_map(|row| FileExtension::new(row[EXTENSION].as_str()?).ok()),
+            );
+        }
+
+        if package.has_table(REGISTRY) {
+            check_columns(&package, REGISTRY, &["Root", "Key", "Name"])?;
+            for row in package.select_rows(Select::table(REGISTRY))? {
+                let Some(key) = row["Key"]
+                    .as_str()
+                    .and_then(|key| match row["Root"].as_int()? {
+                        HKEY_CLASSES_ROOT => Some(key),
+                        REGISTRY_ROOT_DEFAULT | HKEY_CURRENT_USER | HKEY_LOCAL_MACHINE => key
+                            .get(..CLASSES_KEY.len())
+                            .filter(|prefix| prefix.eq_ignore_ascii_case(CLASSES_KEY))
+                            .map(|_| &key[CLASSES_KEY.len()..]),
+                        _ => None,
+                    })
+                else {
+                    continue;
+                };
+
+                // The first key component is either a file extension or a URI scheme
+                let class = key.split('\\').next().unwrap_or(key);
+                if class.starts_with('.') {
+                    if let Ok(extension) = FileExtension::new(class) {
+                        msi.file_extensions.insert(extension);
+                    }
+                } else if class == key && row["Name"].as_str() == Some("URL Protocol") {
+                    if let Ok(protocol) = Protocol::new(class) {
+                        msi.protocols.insert(protocol);
+                    }
+                }
+            }
+        }
+
+        Ok(msi)
+    }
+
+    /// Returns the installer type, which is [`wix`] if the database was created by the WiX
+    /// Toolset and [`msi`] otherwise.
+    ///
+    /// [`wix`]: InstallerType::Wix
+    /// [`msi`]: InstallerType::Msi
+    #[must_use]
+    pub const fn installer_type(&self) -> InstallerType {
+        if self.is_wix {
+            InstallerType::Wix
+        } else {
+            InstallerType::Msi
+        }
+    }
+
+    /// Returns the scope from the `ALLUSERS` property.
+    ///
+    /// An `ALLUSERS` value of `1` is a per-machine installation and an absent or empty value is a
+    /// per-user installation. A value of `2` allows either scope, so no scope is returned.
+    #[must_use]
+    pub fn scope(&self) -> Option<Scope> {
+        match self.all_users.as_deref().map(str::trim) {
+            Some("1") => Some(Scope::Machine),
+            None | Some("") => Some(Scope::User),
+            Some(_) => None,
+        }
+    }
+
+    /// Returns the Apps and Features entry that installing this database creates.
+    #[must_use]
+    pub fn apps_and_features_entry(&self) -> AppsAndFeaturesEntry {
+        AppsAndFeaturesEntry::builder()
+            .maybe_display_name(self.product_name.as_deref())
+            .maybe_publisher(self.manufacturer.as_deref())
+            .maybe_display_version(self.product_version.clone())
+            .maybe_product_code(self.product_code.clone())
+            .maybe_upgrade_code(self.upgrade_code.clone())
+            .installer_type(self.installer_type())
+            .build()
+    }
+
+    /// Creates an [`Installer`] populated with the values from this database.
+    #[must_use]
+    pub fn to_installer(&self) -> Installer {
+        Installer {
+            architecture: self.architecture.unwrap_or_default(),
+            r#type: Some(self.installer_type()),
+            scope: self.scope(),
+            protocols: self.protocols.clone(),
+            file_extensions: self.file_extensions.clone(),
+            product_code: self.product_code.clone(),
+            apps_and_features_entries: self.apps_and_features_entry().into(),
+            ..Installer::default()
+        }
+    }
+}
+
+/// Maps the platform of the template summary property to an architecture.
+///
+/// `Intel64` is Itanium, which is not a supported architecture.
+/// Returns an error if `table` is missing or lacks any of `columns`, as indexing a row by a
+/// missing column panics.
+fn check_columns<F>(package: &Package<F>, table: &str, columns: &[&str]) -> io::Result<()> {
+    let Some(table) = package.get_table(table) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("The MSI database has no {table} table"),
+        ));
+    };
+
+    match columns.iter().find(|column| !table.has_column(column)) {
+        Some(column) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("The {} table has no {column} column", table.name()),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn architecture(platform: &str) -> Option<Architecture> {
+    match platform {
+        "x64" | "AMD64" => Some(Architecture::X64),
+        "Intel" => Some(Architecture::X86),
+        "Arm64" => Some(Architecture::Arm64),
+        "Arm" => Some(Architecture::Arm),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use msi::{Column, Insert, Package, PackageType, Value};
+    use rstest::rstest;
+
+    use super::{Msi, architecture};
+    use crate::installer::{Architecture, InstallerType, Scope};
+
+    const PRODUCT_CODE: &str = "{23170F69-40C1-2702-2409-000001000000}";
+
+    fn create_msi(properties: &[(&str, &str)]) -> Cursor<Vec<u8>> {
+        let mut package = Package::create(PackageType::Installer, Cursor::new(Vec::new())).unwrap();
+        package.summary_info_mut().set_arch("x64");
+
+        package
+            .create_table(
+                "Property",
+                vec![
+                    Column::build("Property").primary_key().id_string(72),
+                    Column::build("Value").text_string(0),
+                ],
+            )
+            .unwrap();
+        package
+            .insert_rows(
+                Insert::into("Property").rows(
+                    properties
+                        .iter()
+                        .map(|(property, value)| vec![Value::from(*property), Value::from(*value)])
+                        .collect(),
+                ),
+            )
+            .unwrap();
+
+        package
+            .create_table(
+                "Extension",
+                vec![
+                    Column::build("Extension").primary_key().string(255),
+                    Column::build("Component_").primary_key().id_string(72),
+                ],
+            )
+            .unwrap();
+        package
+            .insert_rows(
+                Insert::into("Extension").row(vec![Value::from("7z"), Value::from("Program")]),
+            )
+            .unwrap();
+
+        package
+            .create_table(
+                "Registry",
+                vec![
+                    Column::build("Registry").primary_key().id_string(72),
+                    Column::build("Root").int16(),
+                    Column::build("Key").string(255),
+                    Column::build("Name").nullable().formatted_string(255),
+                    Column::build("Value").nullable().formatted_string(0),
+                    Column::build("Component_").id_string(72),
+                ],
+            )
+            .unwrap();
+        package
+            .insert_rows(
+                Insert::into("Registry")
+                    .row(vec![
+                        Value::from("Zip"),
+                        Value::Int(2),
+                        Value::from(r"Software\Classes\.zip"),
+                        Value::Null,
+                        Value::from("7-Zip.zip"),
+                        Value::from("Program"),
+                    ])
+                    .row(vec![
+                        Value::from("Tar"),
+                        Value::Int(-1),
+                        Value::from(r"Software\Classes\.tar"),
+                        Value::Null,
+                        Value::from("7-Zip.tar"),
+                        Value::from("Program"),
+                    ])
+                    .row(vec![
+                        Value::from("Protocol"),
+                        Value::Int(0),
+                        Value::from("sevenzip"),
+                        Value::from("URL Protocol"),
+                        Value::from(""),
+                        Value::from("Program"),
+                    ])
+                    .row(vec![
+                        Value::from("Settings"),
+                        Value::Int(1),
+                        Value::from(r"Software\7-Zip"),
+                        Value::from("Path"),
+                        Value::from("[INSTALLDIR]"),
+                        Value::from("Program"),
+                    ]),
+            )
+            .unwrap();
+
+        let mut cursor = package.into_inner().unwrap();
+        cursor.set_position(0);
+        cursor
+    }
+
+    #[test]
+    fn read_msi() {
+        let msi = Msi::new(create_msi(&[
+            ("ProductCode", PRODUCT_CODE),
+            ("UpgradeCode", "{23170F69-40C1-2702-0000-000004000000}"),
+            ("ProductVersion", "24.09.00.0"),
+            ("Manufacturer", "Igor Pavlov"),
+            ("ProductName", "7-Zip 24.09 (x64 edition)"),
+            ("ALLUSERS", "1"),
+        ]))
+        .unwrap();
+
+        assert_eq!(msi.product_code.as_deref(), Some(PRODUCT_CODE));
+        assert_eq!(msi.architecture, Some(Architecture::X64));
+        assert_eq!(msi.scope(), Some(Scope::Machine));
+        assert_eq!(msi.installer_type(), InstallerType::Msi);
+        assert_eq!(
+            msi.file_extensions
+                .iter()
+                .map(|extension| extension.as_str())
+                .collect::<Vec<_>>(),
+            ["7z", "tar", "zip"]
+        );
+        assert_eq!(
+            msi.protocols
+                .iter()
+                .map(|protocol| protocol.as_str())
+                .collect::<Vec<_>>(),
+            ["sevenzip"]
+        );
+
+        let installer = msi.to_installer();
+        let entry = installer.apps_and_features_entries.iter().next().unwrap();
+        assert_eq!(installer.product_code.as_deref(), Some(PRODUCT_CODE));
+        assert_eq!(entry.display_name(), Some("7-Zip 24.09 (x64 edition)"));
+        assert_eq!(entry.publisher(), Some("Igor Pavlov"));
+        assert_eq!(
+            entry.display_version().map(|version| version.as_str()),
+            Some("24.09.00.0")
+        );
+    }
+
+    #[test]
+    fn all_users_scope() {
+        let scope = |all_users: &[(&str, &str)]| Msi::new(create_msi(all_users)).unwrap().scope();
+
+        assert_eq!(scope(&[]), Some(Scope::User));
+        assert_eq!(scope(&[("ALLUSERS", "1")]), Some(Scope::Machine));
+        assert_eq!(scope(&[("ALLUSERS", "2")]), None);
+    }
+
+    #[rstest]
+    #[case("x64", Some(Architecture::X64))]
+    #[case("AMD64", Some(Architecture::X64))]
+    #[case("Intel", Some(Architecture::X86))]
+    #[case("Arm64", Some(Architecture::Arm64))]
+    #[case("Arm", Some(Architecture::Arm))]
+    #[case("Intel64", None)]
+    fn template_architecture(#[case] platform: &str, #[case] expected: Option<Architecture>) {
+        assert_eq!(architecture(platform), expected);
+    }
+
+    #[rstest]
+    #[case("Property", &["Property"])]
+    #[case("Registry", &["Registry", "Root", "Key"])]
+    fn missing_column(#[case] table: &str, #[case] columns: &[&str]) {
+        let mut package = Package::create(PackageType::Installer, Cursor::new(Vec::new())).unwrap();
+        if table != "Property" {
+            package
+                .create_table(
+                    "Property",
+                    vec![
+                        Column::build("Property").primary_key().id_string(72),
+                        Column::build("Value").text_string(0),
+                    ],
+                )
+                .unwrap();
+        }
+        package
+            .create_table(
+                table,
+                columns
+                    .iter()
+                    .enumerate()
+                    .map(|(index, name)| match (index, *name) {
+                        (0, _) => Column::build(*name).primary_key().id_string(72),
+                        (_, "Root") => Column::build(*name).int16(),
+                        _ => Column::build(*name).string(255),
+                    })
+                    .collect(),
+            )
+            .unwrap();
+        let mut cursor = package.into_inner().unwrap();
+        cursor.set_position(0);
+
+        let err = Msi::new(cursor).unwrap_err();
+
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn wix_property() {
+        let msi = Msi::new(create_msi(&[("WixUIRMOption", "UseRM")])).unwrap();
+
+        assert_eq!(msi.installer_type(), InstallerType::Wix);
+    }
+}
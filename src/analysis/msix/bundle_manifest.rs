@@ -0,0 +1,92 @@
+use alloc::{string::String, vec::Vec};
+use core::str::FromStr;
+
+use roxmltree::Document;
+
+use super::{MsixError, manifest::required_attribute};
+use crate::installer::Architecture;
+
+/// The packages listed in an `AppxBundleManifest.xml` file.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AppxBundleManifest {
+    /// The application packages contained in the bundle.
+    ///
+    /// Resource packages are not included as they do not contain an application.
+    pub packages: Vec<BundlePackage>,
+}
+
+/// An application package contained in a bundle.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BundlePackage {
+    /// The name of the package file within the bundle.
+    pub file_name: String,
+
+    /// The processor architecture of the package.
+    pub architecture: Architecture,
+}
+
+impl FromStr for AppxBundleManifest {
+    type Err = MsixError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let document = Document::parse(s)?;
+
+        let packages = document
+            .root_element()
+            .descendants()
+            .filter(|node| node.has_tag_name("Package"))
+            .filter(|node| {
+                node.attribute("Type")
+                    .is_none_or(|r#type| r#type == "application")
+            })
+            .map(|node| {
+                Ok(BundlePackage {
+                    file_name: required_attribute(node, "FileName")?.into(),
+                    architecture: node
+                        .attribute("Architecture")
+                        .and_then(|architecture| architecture.parse().ok())
+                        .unwrap_or_default(),
+                })
+            })
+            .collect::<Result<_, MsixError>>()?;
+
+        Ok(Self { packages })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::AppxBundleManifest;
+    use crate::installer::Architecture;
+
+    #[test]
+    fn parse_bundle_manifest() {
+        let manifest = indoc! {r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <Bundle xmlns="http://schemas.microsoft.com/appx/2013/bundle" SchemaVersion="5.0">
+              <Identity Name="Example.App" Publisher="CN=Example" Version="1.0.0.0" />
+              <Packages>
+                <Package Type="application" Version="1.0.0.0" Architecture="x64" FileName="App_x64.msix" Offset="60" Size="1024" />
+                <Package Type="application" Version="1.0.0.0" Architecture="arm64" FileName="App_arm64.msix" Offset="1084" Size="1024" />
+                <Package Type="resource" Version="1.0.0.0" ResourceId="split.scale-200" FileName="App_scale-200.msix" Offset="2108" Size="512" />
+              </Packages>
+            </Bundle>
+        "#}
+        .parse::<AppxBundleManifest>()
+        .unwrap();
+
+        assert_eq!(
+            manifest
+                .packages
+                .iter()
+                .map(|package| (package.file_name.as_str(), package.architecture))
+                .collect::<Vec<_>>(),
+            [
+                ("App_x64.msix", Architecture::X64),
+                ("App_arm64.msix", Architecture::Arm64)
+            ]
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use alloc::{collections::BTreeSet, string::String};
+use core::str::FromStr;
+
+use package_family_name::PackageFamilyName;
+use roxmltree::{Document, Node};
+
+use super::MsixError;
+use crate::{
+    Version,
+    installer::{
+        Architecture, Capability, FileExtension, Installer, InstallerType, MinimumOSVersion,
+        Platform, Protocol, RestrictedCapability,
+    },
+};
+
+const WINDOWS_DESKTOP: &str = "Windows.Desktop";
+const WINDOWS_UNIVERSAL: &str = "Windows.Universal";
+
+/// The installer-relevant values of an `AppxManifest.xml` file.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AppxManifest {
+    /// The `Name` attribute of the package `Identity`.
+    pub identity_name: String,
+
+    /// The `Publisher` attribute of the package `Identity`.
+    pub identity_publisher: String,
+
+    /// The `Version` attribute of the package `Identity`.
+    pub version: Version,
+
+    /// The `ProcessorArchitecture` attribute of the package `Identity`.
+    pub architecture: Architecture,
+
+    /// The platforms from each supported `TargetDeviceFamily`.
+    pub platform: Platform,
+
+    /// The lowest `MinVersion` of each supported `TargetDeviceFamily`.
+    pub minimum_os_version: Option<MinimumOSVersion>,
+
+    /// The declared capabilities and device capabilities.
+    pub capabilities: BTreeSet<Capability>,
+
+    /// The declared restricted capabilities.
+    pub restricted_capabilities: BTreeSet<RestrictedCapability>,
+
+    /// The URI schemes from each `windows.protocol` extension.
+    pub protocols: BTreeSet<Protocol>,
+
+    /// The file types from each `windows.fileTypeAssociation` extension.
+    pub file_extensions: BTreeSet<FileExtension>,
+}
+
+impl AppxManifest {
+    /// Returns the [package family name] calculated from the identity name and publisher.
+    ///
+    /// [package family name]: https://learn.microsoft.com/windows/apps/desktop/modernize/package-identity-overview#package-family-name
+    #[must_use]
+    pub fn package_family_name(&self) -> PackageFamilyName<'static> {
+        PackageFamilyName::new(self.identity_name.clone(), &self.identity_publisher)
+    }
+
+    /// Creates an [`Installer`] populated with the values from this manifest.
+    ///
+    /// The installer type is set to [`msix`]. Packages with an `.appx` extension should have
+    /// their installer type replaced with [`appx`].
+    ///
+    /// [`msix`]: InstallerType::Msix
+    /// [`appx`]: InstallerType::Appx
+    #[must_use]
+    pub fn to_installer(&self) -> Installer {
+        Installer {
+            platform: self.platform,
+            minimum_os_version: self.minimum_os_version,
+            architecture: self.architecture,
+            r#type: Some(InstallerType::Msix),
+            protocols: self.protocols.clone(),
+            file_extensions: self.file_extensions.clone(),
+            package_family_name: Some(self.package_family_name()),
+            capabilities: self.capabilities.clone(),
+            restricted_capabilities: self.restricted_capabilities.clone(),
+            ..Installer::default()
+        }
+    }
+}
+
+impl FromStr for AppxManifest {
+    type Err = MsixError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let document = Document::parse(s)?;
+        let package = document.root_element();
+
+        let identity = package
+            .children()
+            .find(|node| node.has_tag_name("Identity"))
+            .ok_or(MsixError::MissingElement("Identity"))?;
+
+        let mut manifest = Self {
+            identity_name: required_attribute(identity, "Name")?.into(),
+            identity_publisher: required_attribute(identity, "Publisher")?.into(),
+            version: Version::new(required_attribute(identity, "Version")?),
+            architecture: identity
+                .attribute("ProcessorArchitecture")
+                .and_then(|architecture| architecture.parse().ok())
+                .unwrap_or_default(),
+            ..Self::default()
+        };
+
+        for family in package
+            .descendants()
+            .filter(|node| node.has_tag_name("TargetDeviceFamily"))
+        {
+            let platform = match family.attribute("Name") {
+                Some(WINDOWS_DESKTOP) => Platform::WINDOWS_DESKTOP,
+                Some(WINDOWS_UNIVERSAL) => Platform::WINDOWS_UNIVERSAL,
+                _ => continue,
+            };
+            manifest.platform |= platform;
+
+            if let Some(min_version) = family
+                .attribute("MinVersion")
+                .and_then(|min_version| min_version.parse::<MinimumOSVersion>().ok())
+            {
+                manifest.minimum_os_version = Some(
+                    manifest
+                        .minimum_os_version
+                        .map_or(min_version, |current| current.min(min_version)),
+                );
+            }
+        }
+
+        if let Some(capabilities) = package
+            .children()
+            .find(|node| node.has_tag_name("Capabilities"))
+        {
+            for name in capabilities
+                .children()
+                .filter(|node| {
+                    node.has_tag_name("Capability") || node.has_tag_name("DeviceCapability")
+                })
+                .filter_map(|node| node.attribute("Name"))
+            {
+                if let Ok(capability) = name.parse::<Capability>() {
+                    manifest.capabilities.insert(capability);
+                } else if let Ok(restricted) = name.parse::<RestrictedCapability>() {
+                    manifest.restricted_capabilities.insert(restricted);
+                }
+            }
+        }
+
+        manifest.protocols = package
+            .descendants()
+            .filter(|node| node.has_tag_name("Protocol"))
+            .filter_map(|node| node.attribute("Name"))
+            .filter_map(|name| Protocol::new(name).ok())
+            .collect();
+
+        manifest.file_extensions = package
+            .descendants()
+            .filter(|node| node.has_tag_name("FileType"))
+            .filter_map(|node| node.text())
+            .filter_map(|file_type| FileExtension::new(file_type.trim()).ok())
+            .collect();
+
+        Ok(manifest)
+    }
+}
+
+pub(super) fn required_attribute<'doc>(
+    node: Node<'doc, '_>,
+    name: &'static str,
+) -> Result<&'doc str, MsixError> {
+    node.attribute(name).ok_or(MsixError::MissingAttribute(
+        node.tag_name().name().into(),
+        name,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::AppxManifest;
+    use crate::{
+        Version,
+        installer::{Architecture, Capability, MinimumOSVersion, Platform, RestrictedCapability},
+    };
+
+    const MANIFEST: &str = indoc! {r#"
+        <?xml version="1.0" encoding="utf-8"?>
+        <Package
+            xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10"
+            xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10"
+            xmlns:rescap="http://schemas.microsoft.com/appx/manifest/foundation/windows10/restrictedcapabilities">
+          <Identity
+            Name="Microsoft.PowerShell"
+            Publisher="CN=Microsoft Corporation, O=Microsoft Corporation, L=Redmond, S=Washington, C=US"
+            Version="7.5.0.0"
+            ProcessorArchitecture="x64" />
+          <Dependencies>
+            <TargetDeviceFamily Name="Windows.Desktop" MinVersion="10.0.18362.0" MaxVersionTested="10.0.22621.0" />
+            <TargetDeviceFamily Name="Windows.Universal" MinVersion="10.0.17763.0" MaxVersionTested="10.0.22621.0" />
+            <TargetDeviceFamily Name="Windows.Holographic" MinVersion="10.0.10240.0" MaxVersionTested="10.0.22621.0" />
+          </Dependencies>
+          <Capabilities>
+            <Capability Name="internetClient" />
+            <rescap:Capability Name="runFullTrust" />
+            <DeviceCapability Name="webcam" />
+          </Capabilities>
+          <Applications>
+            <Application Id="App">
+              <Extensions>
+                <uap:Extension Category="windows.protocol">
+                  <uap:Protocol Name="pwsh" />
+                </uap:Extension>
+                <uap:Extension Category="windows.fileTypeAssociation">
+                  <uap:FileTypeAssociation Name="powershell">
+                    <uap:SupportedFileTypes>
+                      <uap:FileType>.ps1</uap:FileType>
+                      <uap:FileType>.psm1</uap:FileType>
+                    </uap:SupportedFileTypes>
+                  </uap:FileTypeAssociation>
+                </uap:Extension>
+              </Extensions>
+            </Application>
+          </Applications>
+        </Package>
+    "#};
+
+    #[test]
+    fn parse_appx_manifest() {
+        let manifest = MANIFEST.parse::<AppxManifest>().unwrap();
+
+        assert_eq!(manifest.identity_name, "Microsoft.PowerShell");
+        assert_eq!(manifest.version, Version::new("7.5"));
+        assert_eq!(manifest.architecture, Architecture::X64);
+        assert_eq!(manifest.platform, Platform::all());
+        assert_eq!(
+            manifest.minimum_os_version,
+            Some(MinimumOSVersion::new(10, 0, 17763, 0))
+        );
+        assert!(manifest.capabilities.contains(&Capability::InternetClient));
+        assert!(manifest.capabilities.contains(&Capability::Webcam));
+        assert!(
+            manifest
+                .restricted_capabilities
+                .contains(&RestrictedCapability::RunFullTrust)
+        );
+        assert_eq!(
+            manifest
+                .protocols
+                .iter()
+                .map(|protocol| protocol.as_str())
+                .collect::<Vec<_>>(),
+            ["pwsh"]
+        );
+        assert_eq!(
+            manifest
+                .file_extensions
+                .iter()
+                .map(|extension| extension.as_str())
+                .collect::<Vec<_>>(),
+            ["ps1", "psm1"]
+        );
+    }
+
+    #[test]
+    fn appx_manifest_to_installer() {
+        let installer = MANIFEST.parse::<AppxManifest>().unwrap().to_installer();
+
+        assert_eq!(
+            installer
+                .package_family_name
+                .map(|package_family_name| package_family_name.to_string())
+                .as_deref(),
+            Some("Microsoft.PowerShell_8wekyb3d8bbwe")
+        );
+        assert_eq!(installer.architecture, Architecture::X64);
+    }
+
+    #[test]
+    fn missing_identity() {
+        assert!(
+            r#"<Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10" />"#
+                .parse::<AppxManifest>()
+                .is_err()
+        );
+    }
+}
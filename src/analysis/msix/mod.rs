@@ -0,0 +1,385 @@
+//! Reading of `.msix`, `.appx`, `.msixbundle` and `.appxbundle` packages.
+
+mod bundle_manifest;
+mod manifest;
+
+use alloc::{string::String, vec::Vec};
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+
+pub use bundle_manifest::{AppxBundleManifest, BundlePackage};
+pub use manifest::AppxManifest;
+use thiserror::Error;
+use zip::{ZipArchive, result::ZipError};
+
+use crate::{Sha256Hasher, Sha256String, installer::Installer};
+
+const APPX_MANIFEST: &str = "AppxManifest.xml";
+const APPX_BUNDLE_MANIFEST: &str = "AppxMetadata/AppxBundleManifest.xml";
+const APPX_SIGNATURE: &str = "AppxSignature.p7x";
+
+#[derive(Debug, Error)]
+pub enum MsixError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Zip(#[from] ZipError),
+    #[error(transparent)]
+    Xml(#[from] roxmltree::Error),
+    #[error("Package does not contain an `{APPX_MANIFEST}` or `{APPX_BUNDLE_MANIFEST}`")]
+    MissingManifest,
+    #[error("Package does not contain an `{APPX_SIGNATURE}`")]
+    MissingSignature,
+    #[error("Manifest does not contain a `{_0}` element")]
+    MissingElement(&'static str),
+    #[error("`{_0}` element does not have a `{_1}` attribute")]
+    MissingAttribute(String, &'static str),
+}
+
+/// An MSIX or APPX package, or a bundle of them.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Msix {
+    manifests: Vec<AppxManifest>,
+    is_bundle: bool,
+}
+
+impl Msix {
+    /// Reads the manifest of a package, or the manifest of each application package in a bundle.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the reader is not a valid zip archive, does not contain an
+    /// `AppxManifest.xml` or `AppxBundleManifest.xml`, or if a manifest is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    ///
+    /// use winget_types::analysis::msix::{Msix, MsixError};
+    ///
+    /// fn main() -> Result<(), MsixError> {
+    ///     let msix = Msix::new(File::open("PowerShell-7.5.0-win.msixbundle")?)?;
+    ///
+    ///     for installer in msix.installers() {
+    ///         println!("{}: {:?}", installer.architecture, installer.package_family_name);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn new<R: Read + Seek>(reader: R) -> Result<Self, MsixError> {
+        let mut archive = ZipArchive::new(reader)?;
+
+        if archive.index_for_name(APPX_MANIFEST).is_some() {
+            return Ok(Self {
+                manifests: vec![read_manifest(&mut archive)?],
+                is_bundle: false,
+            });
+        }
+
+        if archive.index_for_name(APPX_BUNDLE_MANIFEST).is_none() {
+            return Err(MsixError::MissingManifest);
+        }
+
+        let bundle_manifest =
+            read_to_string(&mut archive, APPX_BUNDLE_MANIFEST)?.parse::<AppxBundleManifest>()?;
+
+        let mut manifests = Vec::with_capacity(bundle_manifest.packages.len());
+        for package in &bundle_manifest.packages {
+            let mut manifest = match archive.by_name_seek(&package.file_name) {
+                // Packages inside a bundle are stored uncompressed, so they can be read in place
+                Ok(package_file) => read_manifest(&mut ZipArchive::new(package_file)?)?,
+                Err(ZipError::UnsupportedArchive(_)) => {
+                    let mut buffer = Vec::new();
+                    archive
+                        .by_name(&package.file_name)?
+                        .read_to_end(&mut buffer)?;
+                    read_manifest(&mut ZipArchive::new(Cursor::new(buffer))?)?
+                }
+                Err(err) => return Err(err.into()),
+            };
+
+            if manifest.architecture.is_neutral() {
+                manifest.architecture = package.architecture;
+            }
+
+            manifests.push(manifest);
+        }
+
+        Ok(Self {
+            manifests,
+            is_bundle: true,
+        })
+    }
+
+    /// Returns the manifest of the package, or the manifest of each application package if this is
+    /// a bundle.
+    #[must_use]
+    #[inline]
+    pub fn manifests(&self) -> &[AppxManifest] {
+        &self.manifests
+    }
+
+    /// Returns `true` if this is a bundle of packages.
+    #[must_use]
+    #[inline]
+    pub const fn is_bundle(&self) -> bool {
+        self.is_bundle
+    }
+
+    /// Returns an [`Installer`] for each architecture in the package.
+    ///
+    /// A bundle containing more than one package for the same architecture produces a single
+    /// installer for that architecture.
+    #[must_use]
+    pub fn installers(&self) -> Vec<Installer> {
+        let mut installers = Vec::<Installer>::with_capacity(self.manifests.len());
+
+        for manifest in &self.manifests {
+            let installer = manifest.to_installer();
+            if let Some(existing) = installers
+                .iter_mut()
+                .find(|existing| existing.architecture == installer.architecture)
+            {
+                existing.capabilities.extend(installer.capabilities);
+                existing
+                    .restricted_capabilities
+                    .extend(installer.restricted_capabilities);
+                existing.protocols.extend(installer.protocols);
+                existing.file_extensions.extend(installer.file_extensions);
+            } else {
+                installers.push(installer);
+            }
+        }
+
+        installers
+    }
+}
+
+/// The hashes of an MSIX or APPX package, or a bundle of them, that are declared in an installer
+/// manifest.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct MsixHashes {
+    /// The Sha256 hash of the whole package, for [`Installer::sha_256`].
+    pub installer_sha_256: Sha256String,
+
+    /// The Sha256 hash of the package's `AppxSignature.p7x`, for
+    /// [`Installer::signature_sha_256`].
+    pub signature_sha_256: Sha256String,
+}
+
+impl MsixHashes {
+    /// Computes the hash of a package and the hash of its signature.
+    ///
+    /// The package is read in full only once. Only the zip central directory and the signature,
+    /// which are small, are read separately.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the reader is not a valid zip archive or does not contain an
+    /// `AppxSignature.p7x`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    ///
+    /// use winget_types::analysis::msix::{MsixError, MsixHashes};
+    ///
+    /// fn main() -> Result<(), MsixError> {
+    ///     let hashes = MsixHashes::new(File::open("PowerShell-7.5.0-win.msixbundle")?)?;
+    ///
+    ///     println!("InstallerSha256: {}", hashes.installer_sha_256);
+    ///     println!("SignatureSha256: {}", hashes.signature_sha_256);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn new<R: Read + Seek>(reader: R) -> Result<Self, MsixError> {
+        Self::with_progress(reader, |_| {})
+    }
+
+    /// Computes the hash of a package and the hash of its signature, like [`MsixHashes::new`],
+    /// calling `progress` with the number of bytes of the package hashed so far.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the reader is not a valid zip archive or does not contain an
+    /// `AppxSignature.p7x`.
+    pub fn with_progress<R, F>(reader: R, progress: F) -> Result<Self, MsixError>
+    where
+        R: Read + Seek,
+        F: FnMut(u64),
+    {
+        let mut archive = ZipArchive::new(reader)?;
+
+        let signature_sha_256 = match archive.by_name(APPX_SIGNATURE) {
+            Ok(signature) => Sha256String::hash_from_reader(signature)?,
+            Err(ZipError::FileNotFound) => return Err(MsixError::MissingSignature),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut reader = archive.into_inner();
+        reader.seek(SeekFrom::Start(0))?;
+
+        let mut hasher = Sha256Hasher::new();
+        hasher.update_from_reader(reader, progress)?;
+
+        Ok(Self {
+            installer_sha_256: hasher.finalize(),
+            signature_sha_256,
+        })
+    }
+}
+
+fn read_manifest<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Result<AppxManifest, MsixError> {
+    read_to_string(archive, APPX_MANIFEST)?.parse()
+}
+
+fn read_to_string<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+) -> Result<String, MsixError> {
+    let mut contents = String::new();
+    archive.by_name(name)?.read_to_string(&mut contents)?;
+    Ok(contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use sha2::{Digest, Sha256};
+    use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+    use super::{Msix, MsixError, MsixHashes};
+    use crate::{
+        Sha256String,
+        installer::{Architecture, InstallerType},
+    };
+
+    fn manifest(architecture: &str) -> String {
+        format!(
+            r#"<Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10">
+                <Identity Name="Example.App" Publisher="CN=Example" Version="1.2.0.0" ProcessorArchitecture="{architecture}" />
+                <Dependencies>
+                    <TargetDeviceFamily Name="Windows.Desktop" MinVersion="10.0.17763.0" />
+                </Dependencies>
+            </Package>"#
+        )
+    }
+
+    fn zip(files: &[(&str, &[u8])], compression: CompressionMethod) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, contents) in files {
+            writer
+                .start_file(
+                    *name,
+                    SimpleFileOptions::default().compression_method(compression),
+                )
+                .unwrap();
+            writer.write_all(contents).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn single_package() {
+        let package = zip(
+            &[("AppxManifest.xml", manifest("x86").as_bytes())],
+            CompressionMethod::Deflated,
+        );
+
+        let msix = Msix::new(Cursor::new(package)).unwrap();
+        let installers = msix.installers();
+
+        assert!(!msix.is_bundle());
+        assert_eq!(installers.len(), 1);
+        assert_eq!(installers[0].architecture, Architecture::X86);
+        assert_eq!(installers[0].r#type, Some(InstallerType::Msix));
+    }
+
+    #[test]
+    fn bundle() {
+        let x64 = zip(
+            &[("AppxManifest.xml", manifest("x64").as_bytes())],
+            CompressionMethod::Deflated,
+        );
+        let arm64 = zip(
+            &[("AppxManifest.xml", manifest("arm64").as_bytes())],
+            CompressionMethod::Deflated,
+        );
+        let bundle_manifest = r#"<Bundle xmlns="http://schemas.microsoft.com/appx/2013/bundle">
+            <Packages>
+                <Package Type="application" Architecture="x64" FileName="App_x64.msix" />
+                <Package Type="application" Architecture="arm64" FileName="App_arm64.msix" />
+            </Packages>
+        </Bundle>"#;
+
+        for compression in [CompressionMethod::Stored, CompressionMethod::Deflated] {
+            let bundle = zip(
+                &[
+                    (
+                        "AppxMetadata/AppxBundleManifest.xml",
+                        bundle_manifest.as_bytes(),
+                    ),
+                    ("App_x64.msix", &x64),
+                    ("App_arm64.msix", &arm64),
+                ],
+                compression,
+            );
+
+            let msix = Msix::new(Cursor::new(bundle)).unwrap();
+
+            assert!(msix.is_bundle());
+            assert_eq!(
+                msix.installers()
+                    .iter()
+                    .map(|installer| installer.architecture)
+                    .collect::<Vec<_>>(),
+                [Architecture::X64, Architecture::Arm64]
+            );
+        }
+    }
+
+    #[test]
+    fn missing_manifest() {
+        let archive = zip(&[("readme.txt", b"")], CompressionMethod::Stored);
+
+        assert!(Msix::new(Cursor::new(archive)).is_err());
+    }
+
+    #[test]
+    fn hashes() {
+        let signature = b"PKCX signature";
+        let package = zip(
+            &[
+                ("AppxManifest.xml", manifest("x64").as_bytes()),
+                ("AppxSignature.p7x", signature),
+            ],
+            CompressionMethod::Deflated,
+        );
+
+        let hashes = MsixHashes::new(Cursor::new(&package)).unwrap();
+
+        assert_eq!(
+            hashes.installer_sha_256,
+            Sha256String::from_digest(&Sha256::digest(&package))
+        );
+        assert_eq!(
+            hashes.signature_sha_256,
+            Sha256String::from_digest(&Sha256::digest(signature))
+        );
+    }
+
+    #[test]
+    fn missing_signature() {
+        let package = zip(
+            &[("AppxManifest.xml", manifest("x64").as_bytes())],
+            CompressionMethod::Stored,
+        );
+
+        assert!(matches!(
+            MsixHashes::new(Cursor::new(package)),
+            Err(MsixError::MissingSignature)
+        ));
+    }
+}
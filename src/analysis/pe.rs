@@ -0,0 +1,636 @@
+//! Reading of the COFF header and version resource of Portable Executable (`.exe`) files.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use std::io::{self, Read, Seek, SeekFrom};
+
+use thiserror::Error;
+
+use crate::{
+    PackageVersion,
+    installer::Architecture,
+    locale::{Copyright, DefaultLocaleManifest, PackageName, Publisher},
+    utils::bytes::{u16_at, u32_at},
+};
+
+const DOS_SIGNATURE: &[u8; 2] = b"MZ";
+const PE_SIGNATURE: &[u8; 4] = b"PE\0\0";
+const PE_POINTER_OFFSET: u64 = 0x3C;
+
+const IMAGE_FILE_MACHINE_I386: u16 = 0x014C;
+const IMAGE_FILE_MACHINE_ARM: u16 = 0x01C0;
+const IMAGE_FILE_MACHINE_ARMNT: u16 = 0x01C4;
+const IMAGE_FILE_MACHINE_AMD64: u16 = 0x8664;
+const IMAGE_FILE_MACHINE_ARM64: u16 = 0xAA64;
+
+const PE32_MAGIC: u16 = 0x010B;
+const PE32_PLUS_MAGIC: u16 = 0x020B;
+const IMAGE_DIRECTORY_ENTRY_RESOURCE: usize = 2;
+const SECTION_HEADER_SIZE: usize = 40;
+
+const RT_VERSION: u32 = 16;
+const HIGH_BIT: u32 = 1 << 31;
+
+const VS_FIXEDFILEINFO_SIGNATURE: u32 = 0xFEEF_04BD;
+const STRING_FILE_INFO: &str = "StringFileInfo";
+const US_ENGLISH: &str = "0409";
+
+const PRODUCT_NAME: &str = "ProductName";
+const COMPANY_NAME: &str = "CompanyName";
+const FILE_VERSION: &str = "FileVersion";
+const PRODUCT_VERSION: &str = "ProductVersion";
+const LEGAL_COPYRIGHT: &str = "LegalCopyright";
+
+#[derive(Debug, Error)]
+pub enum PeError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("File does not have an MZ signature")]
+    InvalidDosSignature,
+    #[error("File does not have a PE signature")]
+    InvalidPeSignature,
+    #[error("Optional header magic {_0:#06X} is not PE32 or PE32+")]
+    InvalidOptionalHeader(u16),
+}
+
+/// The installer-relevant values of a Portable Executable file.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Pe {
+    /// The machine type from the COFF file header.
+    pub machine: u16,
+
+    /// The `ProductName` from the version resource.
+    pub product_name: Option<String>,
+
+    /// The `CompanyName` from the version resource.
+    pub company_name: Option<String>,
+
+    /// The `FileVersion` from the version resource, or the file version from the fixed file info
+    /// if there is no `FileVersion` string.
+    pub file_version: Option<String>,
+
+    /// The `ProductVersion` from the version resource, or the product version from the fixed
+    /// file info if there is no `ProductVersion` string.
+    pub product_version: Option<String>,
+
+    /// The `LegalCopyright` from the version resource.
+    pub legal_copyright: Option<String>,
+}
+
+impl Pe {
+    /// Reads the COFF header and the `VS_VERSIONINFO` resource of a Portable Executable file.
+    ///
+    /// The US English string table is used if the version resource contains more than one
+    /// language. Files without a version resource are read successfully with no version strings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the reader is not a Portable Executable file or if it could not be read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    ///
+    /// use winget_types::analysis::pe::{Pe, PeError};
+    ///
+    /// fn main() -> Result<(), PeError> {
+    ///     let pe = Pe::new(File::open("npp.8.7.Installer.x64.exe")?)?;
+    ///
+    ///     println!("{:?} by {:?}", pe.package_name(), pe.publisher());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn new<R: Read + Seek>(mut reader: R) -> Result<Self, PeError> {
+        let mut dos_signature = [0; 2];
+        reader.read_exact(&mut dos_signature)?;
+        if &dos_signature != DOS_SIGNATURE {
+            return Err(PeError::InvalidDosSignature);
+        }
+
+        reader.seek(SeekFrom::Start(PE_POINTER_OFFSET))?;
+        let pe_offset = read_u32(&mut reader)?;
+        reader.seek(SeekFrom::Start(pe_offset.into()))?;
+
+        let mut pe_signature = [0; 4];
+        reader.read_exact(&mut pe_signature)?;
+        if &pe_signature != PE_SIGNATURE {
+            return Err(PeError::InvalidPeSignature);
+        }
+
+        let mut file_header = [0; 20];
+        reader.read_exact(&mut file_header)?;
+        let machine = u16_at(&file_header, 0).unwrap_or_default();
+        let number_of_sections = usize::from(u16_at(&file_header, 2).unwrap_or_default());
+        let optional_header_size = usize::from(u16_at(&file_header, 16).unwrap_or_default());
+
+        let mut optional_header = vec![0; optional_header_size];
+        reader.read_exact(&mut optional_header)?;
+
+        let mut section_table = vec![0; number_of_sections * SECTION_HEADER_SIZE];
+        reader.read_exact(&mut section_table)?;
+
+        let mut pe = Self {
+            machine,
+            ..Self::default()
+        };
+
+        if let Some(version_info) =
+            read_version_resource(&mut reader, &optional_header, &section_table)?
+        {
+            pe.read_version_info(&version_info);
+        }
+
+        Ok(pe)
+    }
+
+    /// Returns the architecture from the machine type in the COFF file header.
+    ///
+    /// This reflects the architecture of the executable itself. An installer can be built for a
+    /// different architecture than the application it installs, so the result is a stronger hint
+    /// than [`Architecture::from_url`] but may still need to be checked.
+    #[must_use]
+    pub const fn architecture(&self) -> Option<Architecture> {
+        match self.machine {
+            IMAGE_FILE_MACHINE_I386 => Some(Architecture::X86),
+            IMAGE_FILE_MACHINE_AMD64 => Some(Architecture::X64),
+            IMAGE_FILE_MACHINE_ARM64 => Some(Architecture::Arm64),
+            IMAGE_FILE_MACHINE_ARM | IMAGE_FILE_MACHINE_ARMNT => Some(Architecture::Arm),
+            _ => None,
+        }
+    }
+
+    /// Returns the product name as a [`PackageName`], if it is valid.
+    #[must_use]
+    pub fn package_name(&self) -> Option<PackageName> {
+        PackageName::new(self.product_name.as_deref()?).ok()
+    }
+
+    /// Returns the company name as a [`Publisher`], if it is valid.
+    #[must_use]
+    pub fn publisher(&self) -> Option<Publisher> {
+        Publisher::new(self.company_name.as_deref()?).ok()
+    }
+
+    /// Returns the legal copyright as a [`Copyright`], if it is valid.
+    #[must_use]
+    pub fn copyright(&self) -> Option<Copyright> {
+        Copyright::new(self.legal_copyright.as_deref()?).ok()
+    }
+
+    /// Returns the valid package versions from the product version and file version, in that
+    /// order of preference and without duplicates.
+    #[must_use]
+    pub fn package_versions(&self) -> Vec<PackageVersion> {
+        let mut versions = Vec::with_capacity(2);
+
+        for version in [&self.product_version, &self.file_version]
+            .into_iter()
+            .flatten()
+            .filter_map(|version| PackageVersion::new(version).ok())
+        {
+            if !versions.contains(&version) {
+                versions.push(version);
+            }
+        }
+
+        versions
+    }
+
+    /// Creates a [`DefaultLocaleManifest`] populated with the values from the version resource.
+    ///
+    /// Values that are missing or invalid are left as their defaults.
+    #[must_use]
+    pub fn to_default_locale_manifest(&self) -> DefaultLocaleManifest {
+        DefaultLocaleManifest {
+            package_version: self
+                .package_versions()
+                .into_iter()
+                .next()
+                .unwrap_or_default(),
+            publisher: self.publisher().unwrap_or_default(),
+            package_name: self.package_name().unwrap_or_default(),
+            copyright: self.copyright(),
+            ..DefaultLocaleManifest::default()
+        }
+    }
+
+    fn read_version_info(&mut self, version_info: &[u8]) {
+        let Some(root) = Block::parse(version_info) else {
+            return;
+        };
+
+        // VS_FIXEDFILEINFO holds the versions as pairs of 16-bit values packed into 32-bit values
+        if u32_at(root.value, 0) == Some(VS_FIXEDFILEINFO_SIGNATURE) {
+            let fixed_version = |offset| {
+                let most_significant = u32_at(root.value, offset)?;
+                let least_significant = u32_at(root.value, offset + 4)?;
+                Some(format!(
+                    "{}.{}.{}.{}",
+                    most_significant >> 16,
+                    most_significant & 0xFFFF,
+                    least_significant >> 16,
+                    least_significant & 0xFFFF
+                ))
+            };
+            self.file_version = fixed_version(8);
+            self.product_version = fixed_version(16);
+        }
+
+        let string_tables = root
+            .children()
+            .filter(|block| block.key == STRING_FILE_INFO)
+            .flat_map(|block| block.children().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let Some(string_table) = string_tables
+            .iter()
+            .find(|table| table.key.starts_with(US_ENGLISH))
+            .or_else(|| string_tables.first())
+        else {
+            return;
+        };
+
+        for string in string_table.children() {
+            let value = decode_utf16(string.value);
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+
+            let field = match string.key.as_str() {
+                PRODUCT_NAME => &mut self.product_name,
+                COMPANY_NAME => &mut self.company_name,
+                FILE_VERSION => &mut self.file_version,
+                PRODUCT_VERSION => &mut self.product_version,
+                LEGAL_COPYRIGHT => &mut self.legal_copyright,
+                _ => continue,
+            };
+            *field = Some(value.to_string());
+        }
+    }
+}
+
+/// Reads the data of the first `RT_VERSION` resource, if there is one.
+fn read_version_resource<R: Read + Seek>(
+    reader: &mut R,
+    optional_header: &[u8],
+    section_table: &[u8],
+) -> Result<Option<Vec<u8>>, PeError> {
+    let data_directories_offset = match u16_at(optional_header, 0) {
+        Some(PE32_MAGIC) => 96,
+        Some(PE32_PLUS_MAGIC) => 112,
+        magic => return Err(PeError::InvalidOptionalHeader(magic.unwrap_or_default())),
+    };
+
+    let resource_directory = data_directories_offset + IMAGE_DIRECTORY_ENTRY_RESOURCE * 8;
+    let Some(resource_rva) = u32_at(optional_header, resource_directory).filter(|&rva| rva != 0)
+    else {
+        return Ok(None);
+    };
+
+    // Resource offsets are relative to the start of the resource directory, so the rest of the
+    // section containing it is read
+    let Some((offset, size)) =
+        section_table
+            .chunks_exact(SECTION_HEADER_SIZE)
+            .find_map(|section| {
+                let virtual_address = u32_at(section, 12)?;
+                let raw_size = u32_at(section, 16)?;
+                let raw_pointer = u32_at(section, 20)?;
+                let offset_in_section = resource_rva.checked_sub(virtual_address)?;
+                if offset_in_section >= raw_size {
+                    return None;
+                }
+                Some((
+                    raw_pointer.checked_add(offset_in_section)?,
+                    raw_size - offset_in_section,
+                ))
+            })
+    else {
+        return Ok(None);
+    };
+
+    reader.seek(SeekFrom::Start(offset.into()))?;
+    let mut resources = Vec::new();
+    reader.take(size.into()).read_to_end(&mut resources)?;
+
+    Ok(find_version_resource(&resources, resource_rva).map(<[u8]>::to_vec))
+}
+
+/// Walks the type, name and language levels of the resource directory to the first version
+/// resource.
+fn find_version_resource(resources: &[u8], resource_rva: u32) -> Option<&[u8]> {
+    let entries = |directory: usize| {
+        let named = usize::from(u16_at(resources, directory + 12)?);
+        let ids = usize::from(u16_at(resources, directory + 14)?);
+        Some((0..named + ids).filter_map(move |index| {
+            let entry = directory + 16 + index * 8;
+            Some((u32_at(resources, entry)?, u32_at(resources, entry + 4)?))
+        }))
+    };
+    let subdirectory =
+        |offset: u32| (offset & HIGH_BIT != 0).then(|| usize::try_from(offset & !HIGH_BIT).ok())?;
+
+    let (_, names) = entries(0)?.find(|&(id, _)| id == RT_VERSION)?;
+    let (_, languages) = entries(subdirectory(names)?)?.next()?;
+    let (_, data_entry) = entries(subdirectory(languages)?)?.next()?;
+    if data_entry & HIGH_BIT != 0 {
+        return None;
+    }
+
+    let data_entry = usize::try_from(data_entry).ok()?;
+    let data_rva = u32_at(resources, data_entry)?;
+    let data_size = u32_at(resources, data_entry + 4)?;
+    let start = usize::try_from(data_rva.checked_sub(resource_rva)?).ok()?;
+    let end = start.checked_add(usize::try_from(data_size).ok()?)?;
+
+    resources.get(start..end)
+}
+
+/// A node of a `VS_VERSIONINFO` resource, such as `StringFileInfo`, a `StringTable` or a
+/// `String`.
+struct Block<'data> {
+    key: String,
+    value: &'data [u8],
+    children: &'data [u8],
+}
+
+impl<'data> Block<'data> {
+    fn parse(data: &'data [u8]) -> Option<Self> {
+        let length = usize::from(u16_at(data, 0)?).min(data.len());
+        let value_length = usize::from(u16_at(data, 2)?);
+        let is_text = u16_at(data, 4)? == 1;
+
+        let key_end = (6..length)
+            .step_by(2)
+            .find(|&offset| u16_at(data, offset) == Some(0))?;
+        let key = decode_utf16(&data[6..key_end]);
+
+        let value_start = align(key_end + 2).min(length);
+        // The value length of text is in characters rather than bytes
+        let value_end = if is_text {
+            value_start + value_length * 2
+        } else {
+            value_start + value_length
+        }
+        .min(length);
+
+        Some(Self {
+            key,
+            value: &data[value_start..value_end],
+            children: &data[align(value_end).min(length)..length],
+        })
+    }
+
+    fn children(&self) -> impl Iterator<Item = Block<'data>> {
+        let mut remaining = self.children;
+
+        core::iter::from_fn(move || {
+            let length = usize::from(u16_at(remaining, 0)?);
+            if length == 0 {
+                return None;
+            }
+
+            let block = Self::parse(remaining)?;
+            remaining = remaining.get(align(length)..).unwrap_or_default();
+            Some(block)
+        })
+    }
+}
+
+const fn align(offset: usize) -> usize {
+    (offset + 3) & !3
+}
+
+fn decode_utf16(bytes: &[u8]) -> String {
+    char::decode_utf16(
+        bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .take_while(|&unit| unit != 0),
+    )
+    .map(|char| char.unwrap_or(char::REPLACEMENT_CHARACTER))
+    .collect()
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut buffer = [0; 4];
+    reader.read_exact(&mut buffer)?;
+    Ok(u32::from_le_bytes(buffer))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::{IMAGE_FILE_MACHINE_AMD64, IMAGE_FILE_MACHINE_I386, Pe};
+    use crate::{PackageVersion, installer::Architecture};
+
+    const RESOURCE_RVA: u32 = 0x1000;
+    const SECTION_OFFSET: u32 = 0x200;
+
+    fn utf16(value: &str) -> Vec<u8> {
+        value
+            .encode_utf16()
+            .chain([0])
+            .flat_map(u16::to_le_bytes)
+            .collect()
+    }
+
+    fn pad(bytes: &mut Vec<u8>) {
+        bytes.resize(bytes.len().next_multiple_of(4), 0);
+    }
+
+    fn block(key: &str, value: &[u8], is_text: bool, children: &[Vec<u8>]) -> Vec<u8> {
+        let mut block = vec![0; 6];
+        block.extend(utf16(key));
+        pad(&mut block);
+        block.extend(value);
+        for child in children {
+            pad(&mut block);
+            block.extend(child);
+        }
+
+        let length = u16::try_from(block.len()).unwrap();
+        let value_length = if is_text {
+            value.len() / 2
+        } else {
+            value.len()
+        };
+        block[0..2].copy_from_slice(&length.to_le_bytes());
+        block[2..4].copy_from_slice(&u16::try_from(value_length).unwrap().to_le_bytes());
+        block[4..6].copy_from_slice(&u16::from(is_text).to_le_bytes());
+        block
+    }
+
+    fn version_info(strings: &[(&str, &str)]) -> Vec<u8> {
+        let fixed_file_info = [0xFEEF_04BD_u32, 0x0001_0000, 0x0001_0002, 0x0003_0004]
+            .into_iter()
+            .chain([0x0001_0002, 0x0003_0000])
+            .chain([0; 7])
+            .flat_map(u32::to_le_bytes)
+            .collect::<Vec<_>>();
+
+        let strings = strings
+            .iter()
+            .map(|(key, value)| block(key, &utf16(value), true, &[]))
+            .collect::<Vec<_>>();
+
+        block(
+            "VS_VERSION_INFO",
+            &fixed_file_info,
+            false,
+            &[block(
+                "StringFileInfo",
+                &[],
+                true,
+                &[block("040904b0", &[], true, &strings)],
+            )],
+        )
+    }
+
+    fn directory(entries: &[(u32, u32)]) -> Vec<u8> {
+        let mut directory = vec![0; 16];
+        directory[14..16].copy_from_slice(&u16::try_from(entries.len()).unwrap().to_le_bytes());
+        for (id, offset) in entries {
+            directory.extend(id.to_le_bytes());
+            directory.extend(offset.to_le_bytes());
+        }
+        directory
+    }
+
+    fn pe(machine: u16, version_info: Option<&[u8]>) -> Cursor<Vec<u8>> {
+        let mut resources = Vec::new();
+        if let Some(version_info) = version_info {
+            // Each directory has one entry and is 24 bytes long, followed by a 16 byte data entry
+            resources.extend(directory(&[(16, (1 << 31) | 24)]));
+            resources.extend(directory(&[(1, (1 << 31) | 48)]));
+            resources.extend(directory(&[(0x0409, 72)]));
+            resources.extend((RESOURCE_RVA + 88).to_le_bytes());
+            resources.extend(u32::try_from(version_info.len()).unwrap().to_le_bytes());
+            resources.extend([0; 8]);
+            resources.extend(version_info);
+        }
+
+        let mut file = vec![0; 0x40];
+        file[0..2].copy_from_slice(b"MZ");
+        file[0x3C..0x40].copy_from_slice(&0x40_u32.to_le_bytes());
+
+        file.extend(b"PE\0\0");
+        file.extend(machine.to_le_bytes());
+        file.extend(1_u16.to_le_bytes());
+        file.extend([0; 12]);
+        file.extend(240_u16.to_le_bytes());
+        file.extend([0; 2]);
+
+        let mut optional_header = vec![0; 240];
+        optional_header[0..2].copy_from_slice(&0x020B_u16.to_le_bytes());
+        optional_header[108..112].copy_from_slice(&16_u32.to_le_bytes());
+        if version_info.is_some() {
+            optional_header[128..132].copy_from_slice(&RESOURCE_RVA.to_le_bytes());
+            optional_header[132..136]
+                .copy_from_slice(&u32::try_from(resources.len()).unwrap().to_le_bytes());
+        }
+        file.extend(optional_header);
+
+        let mut section = vec![0; 40];
+        section[0..5].copy_from_slice(b".rsrc");
+        section[12..16].copy_from_slice(&RESOURCE_RVA.to_le_bytes());
+        section[16..20].copy_from_slice(&u32::try_from(resources.len()).unwrap().to_le_bytes());
+        section[20..24].copy_from_slice(&SECTION_OFFSET.to_le_bytes());
+        file.extend(section);
+
+        file.resize(usize::try_from(SECTION_OFFSET).unwrap(), 0);
+        file.extend(resources);
+        Cursor::new(file)
+    }
+
+    #[test]
+    fn read_version_info() {
+        let version_info = version_info(&[
+            ("CompanyName", "Don Ho"),
+            (
+                "FileDescription",
+                "Notepad++ : a free (GNU) source code editor",
+            ),
+            ("ProductName", "Notepad++"),
+            ("ProductVersion", "8.7"),
+            ("LegalCopyright", "Copyright 1998-2024 by Don HO"),
+        ]);
+        let pe = Pe::new(pe(IMAGE_FILE_MACHINE_AMD64, Some(&version_info))).unwrap();
+
+        assert_eq!(pe.architecture(), Some(Architecture::X64));
+        assert_eq!(pe.product_name.as_deref(), Some("Notepad++"));
+        assert_eq!(pe.company_name.as_deref(), Some("Don Ho"));
+        assert_eq!(pe.file_version.as_deref(), Some("1.2.3.4"));
+        assert_eq!(
+            pe.package_versions(),
+            [
+                PackageVersion::new("8.7").unwrap(),
+                PackageVersion::new("1.2.3.4").unwrap()
+            ]
+        );
+
+        let manifest = pe.to_default_locale_manifest();
+        assert_eq!(manifest.package_name.as_str(), "Notepad++");
+        assert_eq!(manifest.publisher.as_str(), "Don Ho");
+        assert_eq!(
+            manifest
+                .copyright
+                .as_ref()
+                .map(|copyright| copyright.as_str()),
+            Some("Copyright 1998-2024 by Don HO")
+        );
+    }
+
+    #[test]
+    fn fixed_file_info_version() {
+        let version_info = version_info(&[("ProductName", "Example")]);
+        let pe = Pe::new(pe(IMAGE_FILE_MACHINE_I386, Some(&version_info))).unwrap();
+
+        assert_eq!(pe.architecture(), Some(Architecture::X86));
+        assert_eq!(pe.product_version.as_deref(), Some("1.2.3.0"));
+        assert_eq!(
+            pe.package_versions(),
+            [
+                PackageVersion::new("1.2.3.0").unwrap(),
+                PackageVersion::new("1.2.3.4").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn no_version_resource() {
+        let pe = Pe::new(pe(IMAGE_FILE_MACHINE_AMD64, None)).unwrap();
+
+        assert_eq!(pe.product_name, None);
+        assert!(pe.package_versions().is_empty());
+    }
+
+    #[test]
+    fn resource_section_offset_overflow() {
+        let version_info = version_info(&[("ProductName", "Example")]);
+        let mut file = pe(IMAGE_FILE_MACHINE_AMD64, Some(&version_info)).into_inner();
+
+        // Move the section to start before the resource directory, with its raw data at the end
+        // of the addressable range, so that the resource directory's file offset overflows
+        let section = 0x40 + 24 + 240;
+        let raw_size = u32::from_le_bytes(file[section + 16..section + 20].try_into().unwrap());
+        file[section + 12..section + 16].copy_from_slice(&(RESOURCE_RVA - 0x10).to_le_bytes());
+        file[section + 16..section + 20].copy_from_slice(&(raw_size + 0x10).to_le_bytes());
+        file[section + 20..section + 24].copy_from_slice(&(u32::MAX - 4).to_le_bytes());
+
+        let pe = Pe::new(Cursor::new(file)).unwrap();
+
+        assert_eq!(pe.product_name, None);
+    }
+
+    #[test]
+    fn not_pe() {
+        assert!(Pe::new(Cursor::new(b"PK\x03\x04".to_vec())).is_err());
+    }
+}
@@ -0,0 +1,302 @@
+use alloc::{string::String, vec::Vec};
+
+use super::RegistryError;
+use crate::utils::bytes::{u16_at, u32_at};
+
+const BASE_BLOCK_SIZE: usize = 4096;
+const REGF_SIGNATURE: &[u8; 4] = b"regf";
+const ROOT_CELL_OFFSET: usize = 36;
+
+const KEY_COMP_NAME: u16 = 0x0020;
+const VALUE_COMP_NAME: u16 = 0x0001;
+const DATA_STORED_IN_OFFSET: u32 = 1 << 31;
+const BIG_DATA_SEGMENT_SIZE: usize = 16344;
+const NO_OFFSET: u32 = u32::MAX;
+
+const REG_SZ: u32 = 1;
+const REG_EXPAND_SZ: u32 = 2;
+const REG_DWORD: u32 = 4;
+
+/// The bins of a registry hive file, which cell offsets are relative to.
+pub(super) struct Bins<'data> {
+    data: &'data [u8],
+    root_offset: u32,
+}
+
+impl<'data> Bins<'data> {
+    pub(super) fn new(hive: &'data [u8]) -> Result<Self, RegistryError> {
+        if hive.get(..REGF_SIGNATURE.len()) != Some(REGF_SIGNATURE) {
+            return Err(RegistryError::InvalidSignature);
+        }
+
+        Ok(Self {
+            data: hive.get(BASE_BLOCK_SIZE..).unwrap_or_default(),
+            root_offset: u32_at(hive, ROOT_CELL_OFFSET).ok_or(RegistryError::InvalidSignature)?,
+        })
+    }
+
+    pub(super) fn root(&self) -> Result<KeyNode<'_>, RegistryError> {
+        KeyNode::new(self, self.root_offset)
+    }
+
+    /// Returns the data of the allocated cell at an offset, excluding its size header.
+    fn cell(&self, offset: u32) -> Result<&'data [u8], RegistryError> {
+        let start = usize::try_from(offset).map_err(|_| RegistryError::InvalidCell(offset))?;
+        let size = u32_at(self.data, start)
+            .map(|size| size.cast_signed())
+            .filter(|&size| size < 0)
+            .ok_or(RegistryError::InvalidCell(offset))?;
+        let end = start
+            .checked_add(size.unsigned_abs() as usize)
+            .ok_or(RegistryError::InvalidCell(offset))?;
+
+        self.data
+            .get(start + 4..end)
+            .ok_or(RegistryError::InvalidCell(offset))
+    }
+
+    fn signed_cell(&self, offset: u32, signature: &[u8; 2]) -> Result<&'data [u8], RegistryError> {
+        let cell = self.cell(offset)?;
+        if cell.starts_with(signature) {
+            Ok(cell)
+        } else {
+            Err(RegistryError::InvalidCell(offset))
+        }
+    }
+}
+
+/// A key node (`nk`) cell.
+#[derive(Clone, Copy)]
+pub(super) struct KeyNode<'bins> {
+    bins: &'bins Bins<'bins>,
+    cell: &'bins [u8],
+    offset: u32,
+}
+
+impl<'bins> KeyNode<'bins> {
+    fn new(bins: &'bins Bins<'bins>, offset: u32) -> Result<Self, RegistryError> {
+        let cell = bins.signed_cell(offset, b"nk")?;
+        if cell.len() < 76 {
+            return Err(RegistryError::InvalidCell(offset));
+        }
+
+        Ok(Self { bins, cell, offset })
+    }
+
+    fn field(&self, offset: usize) -> Result<u32, RegistryError> {
+        u32_at(self.cell, offset).ok_or(RegistryError::InvalidCell(self.offset))
+    }
+
+    pub(super) fn name(&self) -> Result<String, RegistryError> {
+        let flags = u16_at(self.cell, 2).unwrap_or_default();
+        let length = usize::from(u16_at(self.cell, 72).unwrap_or_default());
+        let name = self
+            .cell
+            .get(76..76 + length)
+            .ok_or(RegistryError::InvalidCell(self.offset))?;
+
+        Ok(decode_name(name, flags & KEY_COMP_NAME != 0))
+    }
+
+    pub(super) fn subkeys(&self) -> Result<Vec<Self>, RegistryError> {
+        let mut subkeys = Vec::new();
+        if self.field(20)? != 0 {
+            self.collect_subkeys(self.field(28)?, true, &mut subkeys)?;
+        }
+        Ok(subkeys)
+    }
+
+    /// Collects the subkeys of a subkey list.
+    ///
+    /// An index root (`ri`) lists other subkey lists, which must not be index roots themselves,
+    /// so that a malformed hive cannot recurse indefinitely.
+    fn collect_subkeys(
+        &self,
+        list_offset: u32,
+        allow_index_root: bool,
+        subkeys: &mut Vec<Self>,
+    ) -> Result<(), RegistryError> {
+        let list = self.bins.cell(list_offset)?;
+        let count = usize::from(u16_at(list, 2).ok_or(RegistryError::InvalidCell(list_offset))?);
+
+        let (item_size, is_index_root) = match list.get(..2) {
+            Some(b"lf" | b"lh") => (8, false),
+            Some(b"li") => (4, false),
+            Some(b"ri") if allow_index_root => (4, true),
+            _ => return Err(RegistryError::InvalidCell(list_offset)),
+        };
+
+        for index in 0..count {
+            let offset = u32_at(list, 4 + index * item_size)
+                .ok_or(RegistryError::InvalidCell(list_offset))?;
+            if is_index_root {
+                self.collect_subkeys(offset, false, subkeys)?;
+            } else {
+                subkeys.push(Self::new(self.bins, offset)?);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Finds a subkey by name, ignoring ASCII case like the Windows registry.
+    pub(super) fn subkey(&self, name: &str) -> Result<Option<Self>, RegistryError> {
+        for subkey in self.subkeys()? {
+            if subkey.name()?.eq_ignore_ascii_case(name) {
+                return Ok(Some(subkey));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Follows a path of subkeys separated by `\`.
+    pub(super) fn subpath(&self, path: &str) -> Result<Option<Self>, RegistryError> {
+        let mut key = *self;
+        for name in path.split('\\') {
+            match key.subkey(name)? {
+                Some(subkey) => key = subkey,
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(key))
+    }
+
+    pub(super) fn values(&self) -> Result<Vec<KeyValue<'bins>>, RegistryError> {
+        let count = usize::try_from(self.field(36)?).unwrap_or_default();
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let list_offset = self.field(40)?;
+        let list = self.bins.cell(list_offset)?;
+
+        (0..count)
+            .map(|index| {
+                let offset =
+                    u32_at(list, index * 4).ok_or(RegistryError::InvalidCell(list_offset))?;
+                KeyValue::new(self.bins, offset)
+            })
+            .collect()
+    }
+}
+
+/// A key value (`vk`) cell.
+pub(super) struct KeyValue<'bins> {
+    bins: &'bins Bins<'bins>,
+    cell: &'bins [u8],
+    offset: u32,
+}
+
+impl<'bins> KeyValue<'bins> {
+    fn new(bins: &'bins Bins<'bins>, offset: u32) -> Result<Self, RegistryError> {
+        let cell = bins.signed_cell(offset, b"vk")?;
+        if cell.len() < 20 {
+            return Err(RegistryError::InvalidCell(offset));
+        }
+
+        Ok(Self { bins, cell, offset })
+    }
+
+    pub(super) fn name(&self) -> Result<String, RegistryError> {
+        let length = usize::from(u16_at(self.cell, 2).unwrap_or_default());
+        let flags = u16_at(self.cell, 16).unwrap_or_default();
+        let name = self
+            .cell
+            .get(20..20 + length)
+            .ok_or(RegistryError::InvalidCell(self.offset))?;
+
+        Ok(decode_name(name, flags & VALUE_COMP_NAME != 0))
+    }
+
+    pub(super) fn data_type(&self) -> u32 {
+        u32_at(self.cell, 12).unwrap_or_default()
+    }
+
+    pub(super) fn data(&self) -> Result<Vec<u8>, RegistryError> {
+        let invalid = || RegistryError::InvalidCell(self.offset);
+        let size = u32_at(self.cell, 4).ok_or_else(invalid)?;
+        let data_offset = u32_at(self.cell, 8).ok_or_else(invalid)?;
+
+        // Data of up to four bytes is stored in place of the data offset
+        if size & DATA_STORED_IN_OFFSET != 0 {
+            let size = (size & !DATA_STORED_IN_OFFSET) as usize;
+            return self
+                .cell
+                .get(8..8 + size)
+                .map(<[u8]>::to_vec)
+                .ok_or_else(invalid);
+        }
+
+        let size = size as usize;
+        if size == 0 || data_offset == NO_OFFSET {
+            return Ok(Vec::new());
+        }
+
+        let cell = self.bins.cell(data_offset)?;
+        if size <= BIG_DATA_SEGMENT_SIZE || !cell.starts_with(b"db") {
+            return cell.get(..size).map(<[u8]>::to_vec).ok_or_else(invalid);
+        }
+
+        // Larger data is split into segments referenced by a big data (`db`) cell
+        let segment_count = usize::from(u16_at(cell, 2).ok_or_else(invalid)?);
+        let segments = self.bins.cell(u32_at(cell, 4).ok_or_else(invalid)?)?;
+        // The size is untrusted, so only as much as the segments can hold is reserved
+        let mut data = Vec::with_capacity(size.min(segment_count * BIG_DATA_SEGMENT_SIZE));
+        for index in 0..segment_count {
+            let segment = self
+                .bins
+                .cell(u32_at(segments, index * 4).ok_or_else(invalid)?)?;
+            let remaining = size - data.len();
+            data.extend_from_slice(
+                &segment[..remaining.min(BIG_DATA_SEGMENT_SIZE).min(segment.len())],
+            );
+        }
+
+        if data.len() < size {
+            return Err(invalid());
+        }
+
+        Ok(data)
+    }
+
+    /// Returns the data of a `REG_SZ` or `REG_EXPAND_SZ` value.
+    pub(super) fn string(&self) -> Result<Option<String>, RegistryError> {
+        if !matches!(self.data_type(), REG_SZ | REG_EXPAND_SZ) {
+            return Ok(None);
+        }
+
+        let data = self.data()?;
+        Ok(Some(
+            char::decode_utf16(
+                data.chunks_exact(2)
+                    .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                    .take_while(|&unit| unit != 0),
+            )
+            .map(|char| char.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect(),
+        ))
+    }
+
+    /// Returns the data of a `REG_DWORD` value.
+    pub(super) fn dword(&self) -> Result<Option<u32>, RegistryError> {
+        if self.data_type() != REG_DWORD {
+            return Ok(None);
+        }
+
+        Ok(u32_at(&self.data()?, 0))
+    }
+}
+
+/// Decodes a key or value name, which is either Latin-1 or UTF-16LE.
+fn decode_name(name: &[u8], is_latin1: bool) -> String {
+    if is_latin1 {
+        name.iter().copied().map(char::from).collect()
+    } else {
+        char::decode_utf16(
+            name.chunks_exact(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]])),
+        )
+        .map(|char| char.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+    }
+}
@@ -0,0 +1,329 @@
+//! Reading of `Uninstall` keys from offline Windows registry hive files.
+
+mod hive;
+
+use alloc::{string::String, vec::Vec};
+use std::io::{self, Read};
+
+use hive::{Bins, KeyNode};
+use thiserror::Error;
+
+use crate::{
+    Version,
+    installed::ArpEntry,
+    installer::{AppsAndFeaturesEntry, InstallationMetadata, Scope},
+};
+
+const UNINSTALL: &str = r"Microsoft\Windows\CurrentVersion\Uninstall";
+const WOW6432_NODE: &str = "WOW6432Node";
+const SOFTWARE: &str = "Software";
+
+const DISPLAY_NAME: &str = "DisplayName";
+const PUBLISHER: &str = "Publisher";
+const DISPLAY_VERSION: &str = "DisplayVersion";
+const INSTALL_LOCATION: &str = "InstallLocation";
+const WINDOWS_INSTALLER: &str = "WindowsInstaller";
+const SYSTEM_COMPONENT: &str = "SystemComponent";
+
+#[derive(Debug, Error)]
+pub enum RegistryError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("File is not a registry hive")]
+    InvalidSignature,
+    #[error("Registry hive has an invalid cell at offset {_0:#X}")]
+    InvalidCell(u32),
+}
+
+/// An offline registry hive file, such as `SOFTWARE` or `NTUSER.DAT`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegistryHive {
+    bytes: Vec<u8>,
+}
+
+/// An application registered in an `Uninstall` registry key.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UninstallEntry {
+    /// The name of the application's key, which is its product code.
+    pub product_code: String,
+
+    /// The `DisplayName` value.
+    pub display_name: String,
+
+    /// The `Publisher` value.
+    pub publisher: Option<String>,
+
+    /// The `DisplayVersion` value.
+    pub display_version: Option<Version>,
+
+    /// The `InstallLocation` value.
+    pub install_location: Option<String>,
+
+    /// Whether the `WindowsInstaller` value is `1`, meaning the application was installed by
+    /// Windows Installer.
+    pub windows_installer: bool,
+
+    /// Whether the key is under `WOW6432Node`, meaning it was written by a 32-bit installer.
+    pub is_wow64: bool,
+
+    /// The scope of the hive the key was read from.
+    pub scope: Scope,
+}
+
+impl RegistryHive {
+    /// Reads a registry hive file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the reader could not be read or is not a registry hive.
+    pub fn new<R: Read>(mut reader: R) -> Result<Self, RegistryError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Bins::new(&bytes)?.root()?;
+
+        Ok(Self { bytes })
+    }
+
+    /// Returns the scope of the hive.
+    ///
+    /// A user hive (`NTUSER.DAT`) has a `Software` key at its root, while a machine `SOFTWARE`
+    /// hive is the contents of that key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the root key of the hive is invalid.
+    pub fn scope(&self) -> Result<Scope, RegistryError> {
+        Ok(
+            if Bins::new(&self.bytes)?.root()?.subkey(SOFTWARE)?.is_some() {
+                Scope::User
+            } else {
+                Scope::Machine
+            },
+        )
+    }
+
+    /// Reads the applications registered in the `Uninstall` keys of the hive, including the
+    /// `WOW6432Node` key used by 32-bit installers.
+    ///
+    /// Like Apps and Features, keys without a `DisplayName` and keys marked as a
+    /// `SystemComponent` are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if a cell of the hive is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    ///
+    /// use winget_types::analysis::registry::{RegistryError, RegistryHive};
+    ///
+    /// fn main() -> Result<(), RegistryError> {
+    ///     let hive = RegistryHive::new(File::open("SOFTWARE")?)?;
+    ///
+    ///     for entry in hive.uninstall_entries()? {
+    ///         println!("{}: {}", entry.product_code, entry.display_name);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn uninstall_entries(&self) -> Result<Vec<UninstallEntry>, RegistryError> {
+        let bins = Bins::new(&self.bytes)?;
+        let mut root = bins.root()?;
+
+        let scope = if let Some(software) = root.subkey(SOFTWARE)? {
+            root = software;
+            Scope::User
+        } else {
+            Scope::Machine
+        };
+
+        let mut entries = Vec::new();
+        for (path, is_wow64) in [
+            (UNINSTALL, false),
+            (&*alloc::format!(r"{WOW6432_NODE}\{UNINSTALL}"), true),
+        ] {
+            if let Some(uninstall) = root.subpath(path)? {
+                for key in uninstall.subkeys()? {
+                    if let Some(entry) = read_entry(key, scope, is_wow64)? {
+                        entries.push(entry);
+                    }
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+fn read_entry(
+    key: KeyNode,
+    scope: Scope,
+    is_wow64: bool,
+) -> Result<Option<UninstallEntry>, RegistryError> {
+    let mut entry = UninstallEntry {
+        product_code: key.name()?,
+        display_name: String::new(),
+        publisher: None,
+        display_version: None,
+        install_location: None,
+        windows_installer: false,
+        is_wow64,
+        scope,
+    };
+    let mut system_component = false;
+
+    for value in key.values()? {
+        let name = value.name()?;
+        let string = || {
+            value.string().map(|string| {
+                string
+                    .map(|string| string.trim().to_owned())
+                    .filter(|string| !string.is_empty())
+            })
+        };
+
+        if name.eq_ignore_ascii_case(DISPLAY_NAME) {
+            entry.display_name = string()?.unwrap_or_default();
+        } else if name.eq_ignore_ascii_case(PUBLISHER) {
+            entry.publisher = string()?;
+        } else if name.eq_ignore_ascii_case(DISPLAY_VERSION) {
+            entry.display_version = string()?.map(Version::new);
+        } else if name.eq_ignore_ascii_case(INSTALL_LOCATION) {
+            entry.install_location = string()?;
+        } else if name.eq_ignore_ascii_case(WINDOWS_INSTALLER) {
+            entry.windows_installer = value.dword()? == Some(1);
+        } else if name.eq_ignore_ascii_case(SYSTEM_COMPONENT) {
+            system_component = value.dword()? == Some(1);
+        }
+    }
+
+    Ok((!entry.display_name.is_empty() && !system_component).then_some(entry))
+}
+
+impl UninstallEntry {
+    /// Returns the [`AppsAndFeaturesEntry`] for this application.
+    #[must_use]
+    pub fn apps_and_features_entry(&self) -> AppsAndFeaturesEntry {
+        AppsAndFeaturesEntry::builder()
+            .display_name(self.display_name.as_str())
+            .maybe_publisher(self.publisher.as_deref())
+            .maybe_display_version(self.display_version.clone())
+            .product_code(self.product_code.as_str())
+            .build()
+    }
+
+    /// Returns the [`InstallationMetadata`] with the install location of this application as the
+    /// default install location.
+    #[must_use]
+    pub fn installation_metadata(&self) -> InstallationMetadata {
+        InstallationMetadata {
+            default_install_location: self.install_location.as_deref().map(Into::into),
+            ..InstallationMetadata::default()
+        }
+    }
+
+    /// Returns this application as an [`ArpEntry`] for correlation with packages.
+    #[must_use]
+    pub fn arp_entry(&self) -> ArpEntry {
+        ArpEntry {
+            display_name: Some(self.display_name.clone()),
+            publisher: self.publisher.clone(),
+            display_version: self.display_version.clone(),
+            product_code: Some(self.product_code.clone()),
+            scope: Some(self.scope),
+            ..ArpEntry::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RegistryError, RegistryHive};
+    use crate::{Version, installer::Scope};
+
+    const SOFTWARE: &[u8] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/registry/SOFTWARE"
+    ));
+    const NTUSER: &[u8] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/registry/NTUSER.DAT"
+    ));
+    const RECURSIVE: &[u8] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/registry/RECURSIVE"
+    ));
+
+    #[test]
+    fn machine_hive() {
+        let hive = RegistryHive::new(SOFTWARE).unwrap();
+        let entries = hive.uninstall_entries().unwrap();
+
+        assert_eq!(hive.scope().unwrap(), Scope::Machine);
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.product_code.as_str(), entry.is_wow64))
+                .collect::<Vec<_>>(),
+            [
+                ("7-Zip", false),
+                ("{23170F69-40C1-2702-2409-000001000000}", false),
+                ("Notepad++", true),
+            ]
+        );
+
+        let seven_zip = &entries[1];
+        assert_eq!(seven_zip.display_name, "7-Zip 24.09 (x64 edition)");
+        assert!(seven_zip.windows_installer);
+        assert_eq!(
+            seven_zip
+                .installation_metadata()
+                .default_install_location
+                .as_deref()
+                .map(|location| location.as_str()),
+            Some(r"C:\Program Files\7-Zip\")
+        );
+
+        let entry = seven_zip.apps_and_features_entry();
+        assert_eq!(entry.publisher(), Some("Igor Pavlov"));
+        assert_eq!(entry.display_version(), Some(&Version::new("24.09.00.0")));
+        assert_eq!(
+            entry.product_code(),
+            Some("{23170F69-40C1-2702-2409-000001000000}")
+        );
+    }
+
+    #[test]
+    fn user_hive() {
+        let hive = RegistryHive::new(NTUSER).unwrap();
+        let entries = hive.uninstall_entries().unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].scope, Scope::User);
+        assert_eq!(
+            entries[0].display_name,
+            "Microsoft Visual Studio Code (User)"
+        );
+        assert_eq!(
+            entries[0].arp_entry().product_code.as_deref(),
+            Some("{771FD6B0-FA20-440A-A002-3B3BAC16DC50}_is1")
+        );
+    }
+
+    #[test]
+    fn recursive_index_root() {
+        let hive = RegistryHive::new(RECURSIVE).unwrap();
+
+        assert!(matches!(
+            hive.uninstall_entries(),
+            Err(RegistryError::InvalidCell(_))
+        ));
+    }
+
+    #[test]
+    fn not_a_hive() {
+        assert!(RegistryHive::new(&b"PK\x03\x04"[..]).is_err());
+    }
+}
@@ -0,0 +1,275 @@
+//! Inspection of zip archives used as installers.
+
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+use std::io::{Read, Seek};
+
+use thiserror::Error;
+use zip::{ZipArchive, result::ZipResult};
+
+use crate::installer::{
+    Installer, NestedInstallerFiles, NestedInstallerType, PortableCommandAlias,
+};
+
+/// A file within an archive that could be used as a nested installer.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct NestedInstallerCandidate {
+    /// The path of the file within the archive, using `\` as the separator.
+    pub relative_file_path: String,
+
+    /// The nested installer type suggested by the file's extension.
+    pub nested_installer_type: NestedInstallerType,
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum NestedInstallerFilesError {
+    #[error("Zip installers must have at least one nested installer file")]
+    Missing,
+    #[error("Only portable nested installers can have more than one nested installer file")]
+    MultipleNonPortable,
+    #[error("`{_0}` does not exist in the archive")]
+    NotFound(String),
+    #[error("Portable command alias `{_0}` is used more than once")]
+    DuplicateAlias(PortableCommandAlias),
+    #[error("`{_0}` has a portable command alias but the nested installer is not portable")]
+    AliasOnNonPortable(String),
+}
+
+/// The listing of a zip archive.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Zip {
+    entries: Vec<String>,
+}
+
+impl Zip {
+    /// Reads the file entries of a zip archive. Directory entries are not included.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the reader is not a valid zip archive.
+    pub fn new<R: Read + Seek>(reader: R) -> ZipResult<Self> {
+        let archive = ZipArchive::new(reader)?;
+
+        Ok(Self::from_entries(
+            archive.file_names().filter(|name| !name.ends_with('/')),
+        ))
+    }
+
+    /// Creates a `Zip` from a listing of entry paths.
+    ///
+    /// Forward slashes are converted to backslashes to match the paths used in manifests.
+    pub fn from_entries<I, T>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let mut entries = entries
+            .into_iter()
+            .map(|entry| entry.as_ref().replace('/', "\\"))
+            .collect::<Vec<_>>();
+        entries.sort_unstable();
+
+        Self { entries }
+    }
+
+    /// Returns the paths of the files in the archive, using `\` as the separator.
+    #[must_use]
+    #[inline]
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Returns `true` if the archive contains a file at the given path.
+    ///
+    /// The comparison is case-insensitive and treats `/` and `\` as equivalent, matching how
+    /// Windows resolves the path once the archive has been extracted.
+    #[must_use]
+    pub fn contains<T: AsRef<str>>(&self, path: T) -> bool {
+        let path = path.as_ref();
+
+        self.entries.iter().any(|entry| {
+            entry.len() == path.len()
+                && entry.chars().zip(path.chars()).all(|(left, right)| {
+                    left.eq_ignore_ascii_case(&right)
+                        || (matches!(left, '/' | '\\') && matches!(right, '/' | '\\'))
+                })
+        })
+    }
+
+    /// Returns the files in the archive that could be used as a nested installer, based on their
+    /// file extension.
+    ///
+    /// Executables are suggested as [`exe`], which may need to be changed to [`portable`] or a more
+    /// specific installer type.
+    ///
+    /// [`exe`]: NestedInstallerType::Exe
+    /// [`portable`]: NestedInstallerType::Portable
+    #[must_use]
+    pub fn nested_installer_candidates(&self) -> Vec<NestedInstallerCandidate> {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                let (_, extension) = entry.rsplit_once('.')?;
+                let nested_installer_type = match extension.to_ascii_lowercase().as_str() {
+                    "exe" => NestedInstallerType::Exe,
+                    "msi" => NestedInstallerType::Msi,
+                    "msix" | "msixbundle" => NestedInstallerType::Msix,
+                    "appx" | "appxbundle" => NestedInstallerType::Appx,
+                    "ttf" | "otf" | "ttc" => NestedInstallerType::Font,
+                    _ => return None,
+                };
+
+                Some(NestedInstallerCandidate {
+                    relative_file_path: entry.clone(),
+                    nested_installer_type,
+                })
+            })
+            .collect()
+    }
+
+    /// Checks the nested installer files of a zip installer against this archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` containing every problem found if a nested installer file does not exist
+    /// in the archive or has a portable command alias that
+    /// is used more than once or is not allowed for the nested installer type.
+    pub fn validate(&self, installer: &Installer) -> Result<(), Vec<NestedInstallerFilesError>> {
+        let is_portable = installer
+            .nested_installer_type
+            .is_some_and(|r#type| r#type == NestedInstallerType::Portable);
+
+        let mut errors = Vec::new();
+
+        match installer.nested_installer_files.len() {
+            0 => errors.push(NestedInstallerFilesError::Missing),
+            1 => {}
+            _ if !is_portable => errors.push(NestedInstallerFilesError::MultipleNonPortable),
+            _ => {}
+        }
+
+        let mut aliases = BTreeSet::new();
+        for NestedInstallerFiles {
+            relative_file_path,
+            portable_command_alias,
+        } in &installer.nested_installer_files
+        {
+            let relative_file_path = relative_file_path.as_str();
+
+            if !self.contains(relative_file_path) {
+                errors.push(NestedInstallerFilesError::NotFound(
+                    relative_file_path.into(),
+                ));
+            }
+
+            if let Some(alias) = portable_command_alias {
+                if !is_portable {
+                    errors.push(NestedInstallerFilesError::AliasOnNonPortable(
+                        relative_file_path.into(),
+                    ));
+                }
+
+                // Command aliases are resolved case-insensitively on Windows
+                if !aliases.insert(alias.as_str().to_ascii_lowercase()) {
+                    errors.push(NestedInstallerFilesError::DuplicateAlias(alias.clone()));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeSet;
+
+    use super::{NestedInstallerFilesError, Zip};
+    use crate::installer::{
+        Installer, InstallerType, NestedInstallerFiles, NestedInstallerType, PortableCommandAlias,
+    };
+
+    fn zip() -> Zip {
+        Zip::from_entries(["bin/app.exe", "bin/app-cli.exe", "setup.msi", "README.md"])
+    }
+
+    fn nested_file(path: &str, alias: Option<&str>) -> NestedInstallerFiles {
+        NestedInstallerFiles {
+            relative_file_path: path.parse().unwrap(),
+            portable_command_alias: alias.map(|alias| alias.parse().unwrap()),
+        }
+    }
+
+    #[test]
+    fn candidates() {
+        let candidates = zip()
+            .nested_installer_candidates()
+            .into_iter()
+            .map(|candidate| {
+                (
+                    candidate.relative_file_path,
+                    candidate.nested_installer_type,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            candidates,
+            [
+                (r"bin\app-cli.exe".into(), NestedInstallerType::Exe),
+                (r"bin\app.exe".into(), NestedInstallerType::Exe),
+                ("setup.msi".into(), NestedInstallerType::Msi),
+            ]
+        );
+    }
+
+    #[test]
+    fn valid_portable_files() {
+        let installer = Installer {
+            r#type: Some(InstallerType::Zip),
+            nested_installer_type: Some(NestedInstallerType::Portable),
+            nested_installer_files: BTreeSet::from([
+                nested_file(r"bin\app.exe", Some("app")),
+                nested_file(r"BIN\App-Cli.exe", Some("app-cli")),
+            ]),
+            ..Installer::default()
+        };
+
+        assert_eq!(zip().validate(&installer), Ok(()));
+    }
+
+    #[test]
+    fn invalid_files() {
+        let installer = Installer {
+            r#type: Some(InstallerType::Zip),
+            nested_installer_type: Some(NestedInstallerType::Exe),
+            nested_installer_files: BTreeSet::from([
+                nested_file(r"bin\missing.exe", Some("app")),
+                nested_file(r"app.exe", Some("APP")),
+            ]),
+            ..Installer::default()
+        };
+
+        let errors = zip().validate(&installer).unwrap_err();
+
+        assert!(errors.contains(&NestedInstallerFilesError::MultipleNonPortable));
+        assert!(errors.contains(&NestedInstallerFilesError::NotFound(
+            r"bin\missing.exe".into()
+        )));
+        assert!(errors.contains(&NestedInstallerFilesError::NotFound("app.exe".into())));
+        assert!(errors.contains(&NestedInstallerFilesError::DuplicateAlias(
+            PortableCommandAlias::new("app").unwrap()
+        )));
+    }
+
+    #[test]
+    fn missing_files() {
+        assert_eq!(
+            zip().validate(&Installer::default()),
+            Err(vec![NestedInstallerFilesError::Missing])
+        );
+    }
+}
@@ -0,0 +1,397 @@
+use alloc::vec::Vec;
+use core::mem;
+use std::io;
+
+use crate::{
+    installer::InstallerType,
+    utils::bytes::{u16_at, u32_at},
+};
+
+const MZ_SIGNATURE: &[u8] = b"MZ";
+const PE_SIGNATURE: &[u8] = b"PE\0\0";
+const ZIP_SIGNATURE: &[u8] = b"PK\x03\x04";
+const CFB_SIGNATURE: &[u8] = b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1";
+
+const PE_OFFSET: usize = 0x3C;
+const COFF_HEADER_SIZE: usize = 20;
+const SECTION_HEADER_SIZE: usize = 40;
+const BURN_SECTION_NAME: &[u8; 8] = b".wixburn";
+
+/// The maximum number of bytes that are buffered to read the headers of an executable.
+const MAX_HEADERS_SIZE: usize = 1 << 16;
+
+/// Markers that identify installer types within each container, in order of precedence.
+///
+/// The markers of executables are only searched for in their overlay, which is the data appended
+/// after the last section, where Inno Setup and Nullsoft store their compressed setup data.
+const EXE_MARKERS: [(&[u8], InstallerType); 2] = [
+    (b"Inno Setup Setup Data", InstallerType::Inno),
+    (b"NullsoftInst", InstallerType::Nullsoft),
+];
+const ZIP_MARKERS: [(&[u8], InstallerType); 2] = [
+    (b"AppxManifest.xml", InstallerType::Msix),
+    (b"AppxBundleManifest.xml", InstallerType::Msix),
+];
+const MSI_MARKERS: [(&[u8], InstallerType); 2] = [
+    (b"Windows Installer XML", InstallerType::Wix),
+    (b"WiX Toolset", InstallerType::Wix),
+];
+
+const LONGEST_MARKER: usize = 22;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Container {
+    Exe,
+    Zip,
+    Msi,
+}
+
+impl Container {
+    fn from_signature(header: &[u8]) -> Option<Self> {
+        if header.starts_with(MZ_SIGNATURE) {
+            Some(Self::Exe)
+        } else if header.starts_with(ZIP_SIGNATURE) {
+            Some(Self::Zip)
+        } else if header.starts_with(CFB_SIGNATURE) {
+            Some(Self::Msi)
+        } else {
+            None
+        }
+    }
+
+    const fn markers(self) -> &'static [(&'static [u8], InstallerType)] {
+        match self {
+            Self::Exe => &EXE_MARKERS,
+            Self::Zip => &ZIP_MARKERS,
+            Self::Msi => &MSI_MARKERS,
+        }
+    }
+
+    const fn installer_type(self) -> InstallerType {
+        match self {
+            Self::Exe => InstallerType::Exe,
+            Self::Zip => InstallerType::Zip,
+            Self::Msi => InstallerType::Msi,
+        }
+    }
+}
+
+/// The layout of an executable, read from its PE headers.
+struct PeLayout {
+    /// The offset of the data after the last section.
+    overlay: u64,
+    is_burn: bool,
+}
+
+impl PeLayout {
+    /// Reads the layout from the start of an executable, returning `None` if more of it is
+    /// needed, or `Some(None)` if it is not a valid PE.
+    fn read(header: &[u8]) -> Option<Option<Self>> {
+        let pe = usize::try_from(u32_at(header, PE_OFFSET)?).ok()?;
+        if pe >= MAX_HEADERS_SIZE {
+            return Some(None);
+        }
+
+        let signature = header.get(pe..pe + PE_SIGNATURE.len())?;
+        if signature != PE_SIGNATURE {
+            return Some(None);
+        }
+
+        let coff_header = pe + PE_SIGNATURE.len();
+        let section_count = usize::from(u16_at(header, coff_header + 2)?);
+        let optional_header_size = usize::from(u16_at(header, coff_header + 16)?);
+        let section_table = coff_header + COFF_HEADER_SIZE + optional_header_size;
+        let sections =
+            header.get(section_table..section_table + section_count * SECTION_HEADER_SIZE)?;
+
+        let mut layout = Self {
+            overlay: 0,
+            is_burn: false,
+        };
+        for section in sections.chunks_exact(SECTION_HEADER_SIZE) {
+            let raw_size = u64::from(u32_at(section, 16)?);
+            let raw_pointer = u64::from(u32_at(section, 20)?);
+            layout.overlay = layout.overlay.max(raw_pointer + raw_size);
+            layout.is_burn |= section.starts_with(BURN_SECTION_NAME);
+        }
+
+        Some(Some(layout))
+    }
+}
+
+/// Detects the type of an installer from its contents as they are streamed.
+///
+/// The container is identified by the file's signature: executables start with `MZ`, zip archives
+/// with `PK`, and MSI databases are Compound File Binary files. Within each container, the
+/// installer frameworks are recognised by markers that they embed:
+///
+/// - Inno Setup and Nullsoft executables by the signature of the setup data in the executable's
+///   overlay, and WiX Burn executables by their `.wixburn` section.
+/// - MSIX and APPX packages and bundles by the name of an Appx manifest anywhere in the archive.
+/// - MSI databases built with WiX by the name of the toolset anywhere in the database.
+///
+/// Executables without a known marker are detected as [`Exe`], and archives without an Appx
+/// manifest as [`Zip`]. Portable executables and APPX packages cannot be distinguished from
+/// [`Exe`] and [`Msix`] by their contents.
+///
+/// The detected type is a heuristic. The markers of archives and MSI databases are not parsed
+/// from their structure, so an archive that merely contains the text `AppxManifest.xml` is
+/// detected as [`Msix`]. Analyse the downloaded installer to determine its type reliably.
+///
+/// [`Exe`]: InstallerType::Exe
+/// [`Zip`]: InstallerType::Zip
+/// [`Msix`]: InstallerType::Msix
+///
+/// # Examples
+///
+/// ```
+/// use winget_types::{fetch::InstallerTypeDetector, installer::InstallerType};
+///
+/// let mut detector = InstallerTypeDetector::new();
+/// detector.update(b"PK\x03\x04");
+/// detector.update(b"...AppxManifest.xml...");
+///
+/// assert_eq!(detector.installer_type(), Some(InstallerType::Msix));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct InstallerTypeDetector {
+    /// The start of the file, which is buffered until its container and headers are read.
+    header: Vec<u8>,
+    is_identified: bool,
+    container: Option<Container>,
+    /// The offset that markers are searched for from, or `None` if they are not searched for.
+    markers_from: Option<u64>,
+    is_burn: bool,
+    /// The number of bytes that have been scanned.
+    position: u64,
+    /// The end of the previous chunk, so that markers spanning chunks are found.
+    tail: Vec<u8>,
+    /// The index of the most precedent marker that has been found.
+    found: Option<usize>,
+}
+
+impl InstallerTypeDetector {
+    /// Creates a new `InstallerTypeDetector`.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scans the next chunk of the installer.
+    pub fn update(&mut self, data: &[u8]) {
+        if self.is_identified {
+            self.scan(data);
+            return;
+        }
+
+        let buffered = data.len().min(MAX_HEADERS_SIZE - self.header.len());
+        self.header.extend_from_slice(&data[..buffered]);
+        if self.identify() {
+            let header = mem::take(&mut self.header);
+            self.scan(&header);
+            self.scan(&data[buffered..]);
+        }
+    }
+
+    /// Returns the detected installer type, or `None` if the contents are not a recognised
+    /// installer.
+    #[must_use]
+    pub fn installer_type(&self) -> Option<InstallerType> {
+        // A file that ended before its headers could be read is identified by its signature alone
+        if !self.is_identified {
+            return Container::from_signature(&self.header).map(Container::installer_type);
+        }
+
+        let container = self.container?;
+        Some(
+            self.found
+                .map(|index| container.markers()[index].1)
+                .or_else(|| self.is_burn.then_some(InstallerType::Burn))
+                .unwrap_or_else(|| container.installer_type()),
+        )
+    }
+
+    /// Identifies the container from the buffered header, returning `false` if more of the file
+    /// is needed.
+    fn identify(&mut self) -> bool {
+        let is_full = self.header.len() == MAX_HEADERS_SIZE;
+        if self.header.len() < CFB_SIGNATURE.len() {
+            return false;
+        }
+
+        self.container = Container::from_signature(&self.header);
+        match self.container {
+            Some(Container::Exe) => match PeLayout::read(&self.header) {
+                Some(Some(layout)) => {
+                    self.markers_from = Some(layout.overlay);
+                    self.is_burn = layout.is_burn;
+                }
+                Some(None) => {}
+                None if is_full => {}
+                None => return false,
+            },
+            Some(Container::Zip | Container::Msi) => self.markers_from = Some(0),
+            None => {}
+        }
+
+        self.is_identified = true;
+        true
+    }
+
+    /// Searches the part of a chunk that markers are searched for in.
+    fn scan(&mut self, data: &[u8]) {
+        let position = self.position;
+        self.position += data.len() as u64;
+
+        let (Some(container), Some(markers_from)) = (self.container, self.markers_from) else {
+            return;
+        };
+        if self.found == Some(0) {
+            return;
+        }
+
+        let start = usize::try_from(markers_from.saturating_sub(position))
+            .map_or(data.len(), |start| start.min(data.len()));
+        let data = &data[start..];
+        let markers = container.markers();
+
+        // Only the start of the chunk is appended to the tail, to find markers that span chunks
+        let boundary = data.len().min(LONGEST_MARKER - 1);
+        self.tail.extend_from_slice(&data[..boundary]);
+
+        let unsearched = self.found.unwrap_or(markers.len());
+        if let Some(index) = markers[..unsearched]
+            .iter()
+            .position(|(marker, _)| contains(&self.tail, marker) || contains(data, marker))
+        {
+            self.found = Some(index);
+        }
+
+        if data.len() >= LONGEST_MARKER - 1 {
+            self.tail.clear();
+            self.tail
+                .extend_from_slice(&data[data.len() - (LONGEST_MARKER - 1)..]);
+        } else {
+            let excess = self.tail.len().saturating_sub(LONGEST_MARKER - 1);
+            self.tail.drain(..excess);
+        }
+    }
+}
+
+impl io::Write for InstallerTypeDetector {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use rstest::rstest;
+
+    use super::InstallerTypeDetector;
+    use crate::installer::InstallerType;
+
+    /// Creates an executable with sections of the given names and contents, followed by an
+    /// overlay.
+    fn executable(sections: &[(&[u8; 8], &[u8])], overlay: &[u8]) -> Vec<u8> {
+        let mut file = alloc::vec![0; 0x40];
+        file[0..2].copy_from_slice(b"MZ");
+        file[0x3C..0x40].copy_from_slice(&0x40_u32.to_le_bytes());
+
+        file.extend(b"PE\0\0");
+        file.extend(0x8664_u16.to_le_bytes());
+        file.extend(u16::try_from(sections.len()).unwrap().to_le_bytes());
+        file.extend([0; 16]);
+
+        let mut raw_pointer = file.len() + sections.len() * 40;
+        for (name, contents) in sections {
+            file.extend(*name);
+            file.extend([0; 8]);
+            file.extend(u32::try_from(contents.len()).unwrap().to_le_bytes());
+            file.extend(u32::try_from(raw_pointer).unwrap().to_le_bytes());
+            file.extend([0; 16]);
+            raw_pointer += contents.len();
+        }
+        for (_, contents) in sections {
+            file.extend(*contents);
+        }
+
+        file.extend(overlay);
+        file
+    }
+
+    fn detect(data: &[u8], chunk_size: usize) -> Option<InstallerType> {
+        let mut detector = InstallerTypeDetector::new();
+        for chunk in data.chunks(chunk_size) {
+            detector.update(chunk);
+        }
+        detector.installer_type()
+    }
+
+    #[rstest]
+    #[case(executable(&[(b".text\0\0\0", b"code")], b""), Some(InstallerType::Exe))]
+    #[case(
+        executable(&[(b".text\0\0\0", b"code")], b"Inno Setup Setup Data (6.2.0)"),
+        Some(InstallerType::Inno)
+    )]
+    #[case(
+        executable(&[(b".text\0\0\0", b"code")], b"\xEF\xBE\xAD\xDENullsoftInst"),
+        Some(InstallerType::Nullsoft)
+    )]
+    #[case(
+        executable(&[(b".text\0\0\0", b"code"), (b".wixburn", b"\0\0\0\0")], b"MSCF"),
+        Some(InstallerType::Burn)
+    )]
+    #[case(
+        executable(&[(b".wixburn", b"\0\0\0\0")], b"...NullsoftInst"),
+        Some(InstallerType::Nullsoft)
+    )]
+    #[case(
+        executable(&[(b".rdata\0\0", b"Built with NullsoftInst and Inno Setup Setup Data")], b""),
+        Some(InstallerType::Exe)
+    )]
+    #[case(b"MZ\x90\x00...NullsoftInst...".to_vec(), Some(InstallerType::Exe))]
+    #[case(b"PK\x03\x04...readme.txt".to_vec(), Some(InstallerType::Zip))]
+    #[case(b"PK\x03\x04...AppxManifest.xml".to_vec(), Some(InstallerType::Msix))]
+    #[case(
+        b"PK\x03\x04...AppxMetadata/AppxBundleManifest.xml".to_vec(),
+        Some(InstallerType::Msix)
+    )]
+    #[case(b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1...".to_vec(), Some(InstallerType::Msi))]
+    #[case(
+        b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1...Windows Installer XML Toolset".to_vec(),
+        Some(InstallerType::Wix)
+    )]
+    #[case(b"<html>Not found</html>".to_vec(), None)]
+    #[case(b"MZ".to_vec(), Some(InstallerType::Exe))]
+    #[case(Vec::new(), None)]
+    fn installer_type(
+        #[case] data: Vec<u8>,
+        #[case] expected: Option<InstallerType>,
+        #[values(1, 7, 64, 1 << 16)] chunk_size: usize,
+    ) {
+        assert_eq!(detect(&data, chunk_size), expected);
+    }
+
+    #[test]
+    fn markers_are_not_found_outside_their_container() {
+        assert_eq!(
+            detect(b"PK\x03\x04...NullsoftInst", 64),
+            Some(InstallerType::Zip)
+        );
+    }
+}
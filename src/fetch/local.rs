@@ -0,0 +1,357 @@
+use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
+use std::{
+    fs::File,
+    io::{self, Cursor, Read, Seek, SeekFrom, Take},
+};
+
+use camino::Utf8PathBuf;
+use percent_encoding::percent_decode_str;
+
+use super::{ByteRange, FetchError, FetchResponse, Fetcher};
+use crate::url::DecodedUrl;
+
+const MAX_REDIRECTS: usize = 10;
+
+/// A [`Fetcher`] that serves files and redirects from memory.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Read;
+///
+/// use winget_types::fetch::{Fetcher, MemoryFetcher};
+///
+/// let fetcher = MemoryFetcher::new()
+///     .with_redirect(
+///         "https://example.com/latest".parse().unwrap(),
+///         "https://example.com/v1/app.exe".parse().unwrap(),
+///     )
+///     .with_file("https://example.com/v1/app.exe".parse().unwrap(), b"MZ");
+///
+/// let mut response = fetcher
+///     .fetch(&"https://example.com/latest".parse().unwrap(), None)
+///     .unwrap();
+/// let mut body = Vec::new();
+/// response.body.read_to_end(&mut body).unwrap();
+///
+/// assert_eq!(response.final_url.as_str(), "https://example.com/v1/app.exe");
+/// assert_eq!(body, b"MZ");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MemoryFetcher {
+    files: BTreeMap<DecodedUrl, Arc<[u8]>>,
+    redirects: BTreeMap<DecodedUrl, DecodedUrl>,
+}
+
+impl MemoryFetcher {
+    /// Creates an empty `MemoryFetcher`.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serves `contents` at `url`.
+    #[must_use]
+    pub fn with_file<T: AsRef<[u8]>>(mut self, url: DecodedUrl, contents: T) -> Self {
+        self.insert_file(url, contents);
+        self
+    }
+
+    /// Redirects `from` to `to`.
+    #[must_use]
+    pub fn with_redirect(mut self, from: DecodedUrl, to: DecodedUrl) -> Self {
+        self.insert_redirect(from, to);
+        self
+    }
+
+    /// Serves `contents` at `url`, replacing any existing file at that URL.
+    pub fn insert_file<T: AsRef<[u8]>>(&mut self, url: DecodedUrl, contents: T) {
+        self.files.insert(url, Arc::from(contents.as_ref()));
+    }
+
+    /// Redirects `from` to `to`, replacing any existing redirect from that URL.
+    pub fn insert_redirect(&mut self, from: DecodedUrl, to: DecodedUrl) {
+        self.redirects.insert(from, to);
+    }
+}
+
+impl Fetcher for MemoryFetcher {
+    type Reader = Take<Cursor<Arc<[u8]>>>;
+
+    fn fetch(
+        &self,
+        url: &DecodedUrl,
+        range: Option<ByteRange>,
+    ) -> Result<FetchResponse<Self::Reader>, FetchError> {
+        let mut redirects = Vec::new();
+        let mut hops = 0;
+        let mut final_url = url;
+        while let Some(target) = self.redirects.get(final_url) {
+            if hops == MAX_REDIRECTS {
+                return Err(FetchError::TooManyRedirects {
+                    url: url.clone(),
+                    max: MAX_REDIRECTS,
+                });
+            }
+            if hops > 0 {
+                redirects.push(final_url.clone());
+            }
+            hops += 1;
+            final_url = target;
+        }
+
+        let contents = self
+            .files
+            .get(final_url)
+            .ok_or_else(|| FetchError::NotFound(final_url.clone()))?;
+
+        let mut body = Cursor::new(Arc::clone(contents));
+        let length = contents.len() as u64;
+        let (range, content_length) = restrict(&mut body, final_url, length, range)?;
+
+        Ok(FetchResponse {
+            final_url: final_url.clone(),
+            redirects,
+            content_length: Some(content_length),
+            range,
+            body: body.take(content_length),
+        })
+    }
+}
+
+/// A [`Fetcher`] that serves files from a local directory by the last segment of the URL's path.
+///
+/// The host and the rest of the path are ignored, so `https://example.com/v1/app.exe` is served
+/// from `app.exe` within the directory.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DirectoryFetcher {
+    root: Utf8PathBuf,
+}
+
+impl DirectoryFetcher {
+    /// Creates a `DirectoryFetcher` that serves files from `root`.
+    #[must_use]
+    pub fn new<P: Into<Utf8PathBuf>>(root: P) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl Fetcher for DirectoryFetcher {
+    type Reader = Take<File>;
+
+    fn fetch(
+        &self,
+        url: &DecodedUrl,
+        range: Option<ByteRange>,
+    ) -> Result<FetchResponse<Self::Reader>, FetchError> {
+        let file_name = url
+            .path_segments()
+            .and_then(Iterator::last)
+            .map(|segment| percent_decode_str(segment).decode_utf8_lossy())
+            .filter(|file_name| !file_name.is_empty() && !file_name.contains(['/', '\\']))
+            .ok_or_else(|| FetchError::NotFound(url.clone()))?;
+
+        let mut file = match File::open(self.root.join(&*file_name)) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Err(FetchError::NotFound(url.clone()));
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let length = file.metadata()?.len();
+        let (range, content_length) = restrict(&mut file, url, length, range)?;
+
+        Ok(FetchResponse {
+            final_url: url.clone(),
+            redirects: Vec::new(),
+            content_length: Some(content_length),
+            range,
+            body: file.take(content_length),
+        })
+    }
+}
+
+/// Seeks `body` to the start of `range`, returning the satisfied range and the number of bytes
+/// to read.
+fn restrict<S: Seek>(
+    body: &mut S,
+    url: &DecodedUrl,
+    length: u64,
+    range: Option<ByteRange>,
+) -> Result<(Option<ByteRange>, u64), FetchError> {
+    let Some(range) = range else {
+        return Ok((None, length));
+    };
+
+    if range.start >= length || range.end.is_some_and(|end| end < range.start) {
+        return Err(FetchError::RangeNotSatisfiable {
+            url: url.clone(),
+            range,
+        });
+    }
+
+    let end = range.end.map_or(length - 1, |end| end.min(length - 1));
+    body.seek(SeekFrom::Start(range.start))?;
+
+    Ok((
+        Some(ByteRange::inclusive(range.start, end)),
+        end - range.start + 1,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, vec::Vec};
+    use std::{fs, io::Read};
+
+    use camino::Utf8PathBuf;
+    use rstest::rstest;
+
+    use super::{DirectoryFetcher, MAX_REDIRECTS, MemoryFetcher};
+    use crate::fetch::{ByteRange, FetchError, Fetcher};
+
+    fn read<R: Read>(mut reader: R) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    fn fetcher() -> MemoryFetcher {
+        MemoryFetcher::new()
+            .with_file(
+                "https://example.com/app.exe".parse().unwrap(),
+                b"0123456789",
+            )
+            .with_redirect(
+                "https://example.com/latest".parse().unwrap(),
+                "https://cdn.example.com/latest".parse().unwrap(),
+            )
+            .with_redirect(
+                "https://cdn.example.com/latest".parse().unwrap(),
+                "https://example.com/app.exe".parse().unwrap(),
+            )
+    }
+
+    #[test]
+    fn redirects() {
+        let response = fetcher()
+            .fetch(&"https://example.com/latest".parse().unwrap(), None)
+            .unwrap();
+
+        assert_eq!(response.final_url.as_str(), "https://example.com/app.exe");
+        assert_eq!(
+            response
+                .redirects
+                .iter()
+                .map(|url| url.as_str())
+                .collect::<Vec<_>>(),
+            ["https://cdn.example.com/latest"]
+        );
+        assert_eq!(response.content_length, Some(10));
+        assert_eq!(read(response.body), b"0123456789");
+    }
+
+    #[test]
+    fn redirect_loop() {
+        let fetcher = MemoryFetcher::new()
+            .with_redirect(
+                "https://example.com/a".parse().unwrap(),
+                "https://example.com/b".parse().unwrap(),
+            )
+            .with_redirect(
+                "https://example.com/b".parse().unwrap(),
+                "https://example.com/a".parse().unwrap(),
+            );
+
+        assert!(matches!(
+            fetcher.fetch(&"https://example.com/a".parse().unwrap(), None),
+            Err(FetchError::TooManyRedirects { .. })
+        ));
+    }
+
+    #[rstest]
+    #[case(MAX_REDIRECTS, true)]
+    #[case(MAX_REDIRECTS + 1, false)]
+    fn redirect_limit(#[case] hops: usize, #[case] is_followed: bool) {
+        let url = |hop: usize| format!("https://example.com/{hop}").parse().unwrap();
+
+        let mut fetcher = MemoryFetcher::new().with_file(url(hops), b"MZ");
+        for hop in 0..hops {
+            fetcher.insert_redirect(url(hop), url(hop + 1));
+        }
+
+        match fetcher.fetch(&url(0), None) {
+            Ok(response) => {
+                assert!(is_followed);
+                assert_eq!(response.final_url, url(hops));
+                assert_eq!(response.redirects, (1..hops).map(url).collect::<Vec<_>>());
+            }
+            Err(FetchError::TooManyRedirects { max, .. }) => {
+                assert!(!is_followed);
+                assert_eq!(max, MAX_REDIRECTS);
+            }
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    #[test]
+    fn ranges() {
+        let fetcher = fetcher();
+        let url = "https://example.com/app.exe".parse().unwrap();
+
+        let response = fetcher
+            .fetch(&url, Some(ByteRange::inclusive(2, 4)))
+            .unwrap();
+        assert_eq!(response.range, Some(ByteRange::inclusive(2, 4)));
+        assert_eq!(read(response.body), b"234");
+
+        let response = fetcher
+            .fetch(&url, Some(ByteRange::starting_at(7)))
+            .unwrap();
+        assert_eq!(response.range, Some(ByteRange::inclusive(7, 9)));
+        assert_eq!(read(response.body), b"789");
+
+        assert!(matches!(
+            fetcher.fetch(&url, Some(ByteRange::starting_at(10))),
+            Err(FetchError::RangeNotSatisfiable { .. })
+        ));
+    }
+
+    #[test]
+    fn not_found() {
+        assert!(matches!(
+            fetcher().fetch(&"https://example.com/missing.exe".parse().unwrap(), None),
+            Err(FetchError::NotFound(url)) if url.as_str() == "https://example.com/missing.exe"
+        ));
+    }
+
+    #[test]
+    fn directory() {
+        let root = Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join(alloc::format!(
+                "winget-types-directory-fetcher-{}",
+                std::process::id()
+            ));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("My App.exe"), b"0123456789").unwrap();
+
+        let fetcher = DirectoryFetcher::new(&root);
+        let url = "https://example.com/v1/My%20App.exe".parse().unwrap();
+
+        let full = fetcher
+            .fetch(&url, None)
+            .map(|response| read(response.body));
+        let partial = fetcher
+            .fetch(&url, Some(ByteRange::inclusive(8, 20)))
+            .map(|response| read(response.body));
+        let missing = fetcher.fetch(&"https://example.com/other.exe".parse().unwrap(), None);
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(full.unwrap(), b"0123456789");
+        assert_eq!(partial.unwrap(), b"89");
+        assert!(matches!(missing, Err(FetchError::NotFound(_))));
+    }
+}
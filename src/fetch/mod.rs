@@ -0,0 +1,127 @@
+//! Downloading of installers through a pluggable [`Fetcher`].
+//!
+//! A [`Pipeline`] downloads each [`Installer::url`] through a fetcher, hashing the installer,
+//! detecting its type from its contents and recording where the URL redirected to, all in a single
+//! pass. [`MemoryFetcher`] and [`DirectoryFetcher`] serve installers without a network, for tests.
+//!
+//! [`Installer::url`]: crate::installer::Installer::url
+
+mod detect;
+mod local;
+mod pipeline;
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{error::Error as CoreError, fmt};
+use std::io::{self, Read};
+
+pub use detect::InstallerTypeDetector;
+pub use local::{DirectoryFetcher, MemoryFetcher};
+pub use pipeline::{Download, Pipeline};
+use thiserror::Error;
+
+use crate::url::DecodedUrl;
+
+/// A source of installer bytes, such as an HTTP client.
+pub trait Fetcher {
+    /// The reader of the response body.
+    type Reader: Read;
+
+    /// Fetches a URL, following any redirects, optionally requesting only a range of its bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the URL could not be fetched, or if a range was requested that the
+    /// source cannot satisfy.
+    fn fetch(
+        &self,
+        url: &DecodedUrl,
+        range: Option<ByteRange>,
+    ) -> Result<FetchResponse<Self::Reader>, FetchError>;
+}
+
+impl<F: Fetcher + ?Sized> Fetcher for &F {
+    type Reader = F::Reader;
+
+    #[inline]
+    fn fetch(
+        &self,
+        url: &DecodedUrl,
+        range: Option<ByteRange>,
+    ) -> Result<FetchResponse<Self::Reader>, FetchError> {
+        (**self).fetch(url, range)
+    }
+}
+
+/// A range of bytes to fetch, like an HTTP `Range` header.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct ByteRange {
+    /// The offset of the first byte.
+    pub start: u64,
+
+    /// The offset of the last byte, inclusive, or `None` for the rest of the content.
+    pub end: Option<u64>,
+}
+
+impl ByteRange {
+    /// Creates a range from `start` to the end of the content.
+    #[must_use]
+    #[inline]
+    pub const fn starting_at(start: u64) -> Self {
+        Self { start, end: None }
+    }
+
+    /// Creates a range from `start` to `end`, inclusive.
+    #[must_use]
+    #[inline]
+    pub const fn inclusive(start: u64, end: u64) -> Self {
+        Self {
+            start,
+            end: Some(end),
+        }
+    }
+}
+
+impl fmt::Display for ByteRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bytes={}-", self.start)?;
+        if let Some(end) = self.end {
+            write!(f, "{end}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The response to a [`Fetcher::fetch`].
+#[derive(Debug)]
+pub struct FetchResponse<R> {
+    /// The URL the content was served from, after following any redirects.
+    pub final_url: DecodedUrl,
+
+    /// The URLs that were redirected through, in order, excluding the requested and final URLs.
+    pub redirects: Vec<DecodedUrl>,
+
+    /// The length of the body, if known.
+    pub content_length: Option<u64>,
+
+    /// The range of the content that the body holds, or `None` if it holds all of it.
+    pub range: Option<ByteRange>,
+
+    /// The body of the response.
+    pub body: R,
+}
+
+#[derive(Debug, Error)]
+pub enum FetchError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("`{0}` was not found")]
+    NotFound(DecodedUrl),
+    #[error("`{url}` responded with status {status}")]
+    Status { url: DecodedUrl, status: u16 },
+    #[error("`{url}` exceeded the maximum of {max} redirects")]
+    TooManyRedirects { url: DecodedUrl, max: usize },
+    #[error("Range `{range}` of `{url}` is not satisfiable")]
+    RangeNotSatisfiable { url: DecodedUrl, range: ByteRange },
+    #[error(transparent)]
+    Other(Box<dyn CoreError + Send + Sync>),
+}
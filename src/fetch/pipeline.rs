@@ -0,0 +1,303 @@
+use alloc::vec::Vec;
+use std::io::{self, Write};
+
+use super::{FetchError, Fetcher, InstallerTypeDetector};
+use crate::{
+    Sha256Hasher, Sha256String,
+    installer::{Installer, InstallerType},
+    url::DecodedUrl,
+};
+
+/// The result of downloading an installer.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Download {
+    /// The URL that was requested.
+    pub url: DecodedUrl,
+
+    /// The URL the installer was served from, after following any redirects.
+    pub final_url: DecodedUrl,
+
+    /// The URLs that were redirected through, excluding the requested and final URLs.
+    pub redirects: Vec<DecodedUrl>,
+
+    /// The Sha256 hash of the installer.
+    pub sha_256: Sha256String,
+
+    /// The size of the installer in bytes.
+    pub size: u64,
+
+    /// The installer type detected from the installer's contents, if it is a recognised
+    /// installer.
+    ///
+    /// This is a heuristic, as described by [`InstallerTypeDetector`].
+    pub installer_type: Option<InstallerType>,
+}
+
+impl Download {
+    /// Returns `true` if the download was redirected.
+    #[must_use]
+    pub fn is_redirected(&self) -> bool {
+        self.final_url != self.url
+    }
+
+    /// Sets the [`sha_256`] of an installer to the hash of this download, and its [`type`] to the
+    /// detected installer type if it has none.
+    ///
+    /// [`sha_256`]: Installer::sha_256
+    /// [`type`]: Installer::type
+    pub fn apply(&self, installer: &mut Installer) {
+        installer.sha_256 = self.sha_256;
+        if installer.r#type.is_none() {
+            installer.r#type = self.installer_type;
+        }
+    }
+}
+
+/// Downloads installers through a [`Fetcher`], hashing them and detecting their type in a single
+/// pass.
+///
+/// # Examples
+///
+/// ```
+/// use winget_types::{
+///     fetch::{MemoryFetcher, Pipeline},
+///     installer::{Installer, InstallerType},
+/// };
+///
+/// let fetcher = MemoryFetcher::new().with_file(
+///     "https://example.com/app.msix".parse().unwrap(),
+///     b"PK\x03\x04...AppxManifest.xml",
+/// );
+/// let mut installer = Installer {
+///     url: "https://example.com/app.msix".parse().unwrap(),
+///     ..Installer::default()
+/// };
+///
+/// let download = Pipeline::new(fetcher).download(&installer).unwrap();
+/// download.apply(&mut installer);
+///
+/// assert_eq!(installer.r#type, Some(InstallerType::Msix));
+/// assert_eq!(installer.sha_256, download.sha_256);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Pipeline<F> {
+    fetcher: F,
+}
+
+impl<F: Fetcher> Pipeline<F> {
+    /// Creates a `Pipeline` that downloads through `fetcher`.
+    #[must_use]
+    #[inline]
+    pub const fn new(fetcher: F) -> Self {
+        Self { fetcher }
+    }
+
+    /// Returns the fetcher that installers are downloaded through.
+    #[must_use]
+    #[inline]
+    pub const fn fetcher(&self) -> &F {
+        &self.fetcher
+    }
+
+    /// Downloads an installer's URL, discarding its contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the installer could not be fetched or read.
+    #[inline]
+    pub fn download(&self, installer: &Installer) -> Result<Download, FetchError> {
+        self.download_url(&installer.url, io::sink(), |_| {})
+    }
+
+    /// Downloads an installer's URL while writing its contents to `writer`, such as a file.
+    ///
+    /// `progress` is called after each chunk with the number of bytes downloaded so far.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the installer could not be fetched or read, or if `writer` could not
+    /// be written to.
+    #[inline]
+    pub fn download_to<W, P>(
+        &self,
+        installer: &Installer,
+        writer: W,
+        progress: P,
+    ) -> Result<Download, FetchError>
+    where
+        W: Write,
+        P: FnMut(u64),
+    {
+        self.download_url(&installer.url, writer, progress)
+    }
+
+    /// Downloads each installer, discarding their contents.
+    ///
+    /// Installers that share a URL are downloaded once, and their results are cloned.
+    pub fn download_all(&self, installers: &[Installer]) -> Vec<Result<Download, FetchError>> {
+        let mut downloads = Vec::<Result<Download, FetchError>>::with_capacity(installers.len());
+
+        for (index, installer) in installers.iter().enumerate() {
+            let previous = installers[..index]
+                .iter()
+                .position(|previous| previous.url == installer.url)
+                .and_then(|previous| downloads[previous].as_ref().ok().cloned());
+
+            downloads.push(previous.map_or_else(|| self.download(installer), Ok));
+        }
+
+        downloads
+    }
+
+    fn download_url<W, P>(
+        &self,
+        url: &DecodedUrl,
+        writer: W,
+        progress: P,
+    ) -> Result<Download, FetchError>
+    where
+        W: Write,
+        P: FnMut(u64),
+    {
+        let response = self.fetcher.fetch(url, None)?;
+
+        let mut detector = InstallerTypeDetector::new();
+        let mut hasher = Sha256Hasher::new();
+        let size = hasher.tee_reader(
+            response.body,
+            Detecting {
+                writer,
+                detector: &mut detector,
+            },
+            progress,
+        )?;
+
+        Ok(Download {
+            url: url.clone(),
+            final_url: response.final_url,
+            redirects: response.redirects,
+            sha_256: hasher.finalize(),
+            size,
+            installer_type: detector.installer_type(),
+        })
+    }
+}
+
+/// A writer that detects the installer type of everything written to it.
+struct Detecting<'detector, W> {
+    writer: W,
+    detector: &'detector mut InstallerTypeDetector,
+}
+
+impl<W: Write> Write for Detecting<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        self.detector.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use sha2::{Digest, Sha256};
+
+    use super::Pipeline;
+    use crate::{
+        Sha256String,
+        fetch::{FetchError, MemoryFetcher},
+        installer::{Installer, InstallerType},
+    };
+
+    const INSTALLER: &[u8] = b"PK\x03\x04...AppxMetadata/AppxBundleManifest.xml...";
+
+    fn installer(url: &str) -> Installer {
+        Installer {
+            url: url.parse().unwrap(),
+            ..Installer::default()
+        }
+    }
+
+    fn pipeline() -> Pipeline<MemoryFetcher> {
+        Pipeline::new(
+            MemoryFetcher::new()
+                .with_redirect(
+                    "https://example.com/latest".parse().unwrap(),
+                    "https://example.com/v2/setup.msixbundle".parse().unwrap(),
+                )
+                .with_file(
+                    "https://example.com/v2/setup.msixbundle".parse().unwrap(),
+                    INSTALLER,
+                ),
+        )
+    }
+
+    #[test]
+    fn download() {
+        let mut contents = Vec::new();
+        let mut progress = Vec::new();
+
+        let download = pipeline()
+            .download_to(
+                &installer("https://example.com/latest"),
+                &mut contents,
+                |bytes| progress.push(bytes),
+            )
+            .unwrap();
+
+        assert!(download.is_redirected());
+        assert_eq!(
+            download.final_url.as_str(),
+            "https://example.com/v2/setup.msixbundle"
+        );
+        assert_eq!(
+            download.sha_256,
+            Sha256String::from_digest(&Sha256::digest(INSTALLER))
+        );
+        assert_eq!(download.size, INSTALLER.len() as u64);
+        assert_eq!(download.installer_type, Some(InstallerType::Msix));
+        assert_eq!(contents, INSTALLER);
+        assert_eq!(progress, [INSTALLER.len() as u64]);
+    }
+
+    #[test]
+    fn apply() {
+        let pipeline = pipeline();
+        let download = pipeline
+            .download(&installer("https://example.com/latest"))
+            .unwrap();
+
+        let mut untyped = installer("https://example.com/latest");
+        download.apply(&mut untyped);
+        assert_eq!(untyped.sha_256, download.sha_256);
+        assert_eq!(untyped.r#type, Some(InstallerType::Msix));
+
+        let mut typed = Installer {
+            r#type: Some(InstallerType::Exe),
+            ..installer("https://example.com/latest")
+        };
+        download.apply(&mut typed);
+        assert_eq!(typed.r#type, Some(InstallerType::Exe));
+    }
+
+    #[test]
+    fn download_all() {
+        let downloads = pipeline().download_all(&[
+            installer("https://example.com/latest"),
+            installer("https://example.com/missing.exe"),
+            installer("https://example.com/latest"),
+        ]);
+
+        assert_eq!(downloads.len(), 3);
+        assert!(matches!(downloads[1], Err(FetchError::NotFound(_))));
+        assert_eq!(
+            downloads[0].as_ref().unwrap(),
+            downloads[2].as_ref().unwrap()
+        );
+    }
+}
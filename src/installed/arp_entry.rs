@@ -0,0 +1,55 @@
+use alloc::string::String;
+
+use package_family_name::PackageFamilyName;
+
+use crate::{
+    Version,
+    installer::{AppsAndFeaturesEntry, Scope},
+};
+
+/// An entry in Apps and Features (Add/Remove Programs), as observed on a machine.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
+pub struct ArpEntry {
+    /// The `DisplayName` value of the entry.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub display_name: Option<String>,
+
+    /// The `Publisher` value of the entry.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub publisher: Option<String>,
+
+    /// The `DisplayVersion` value of the entry.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub display_version: Option<Version>,
+
+    /// The product code, which is the name of the entry's `Uninstall` registry key.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub product_code: Option<String>,
+
+    /// The upgrade code of an MSI product.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub upgrade_code: Option<String>,
+
+    /// The package family name of an MSIX package.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub package_family_name: Option<PackageFamilyName<'static>>,
+
+    /// The scope the entry is installed to, based on the registry hive it was found in.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub scope: Option<Scope>,
+}
+
+impl From<&AppsAndFeaturesEntry> for ArpEntry {
+    fn from(entry: &AppsAndFeaturesEntry) -> Self {
+        Self {
+            display_name: entry.display_name().map(String::from),
+            publisher: entry.publisher().map(String::from),
+            display_version: entry.display_version().cloned(),
+            product_code: entry.product_code().map(String::from),
+            upgrade_code: entry.upgrade_code().map(String::from),
+            ..Self::default()
+        }
+    }
+}
@@ -0,0 +1,538 @@
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+
+use package_family_name::PackageFamilyName;
+
+use super::ArpEntry;
+use crate::{
+    PackageIdentifier, PackageVersion, Version,
+    installer::{InstallerManifest, Scope},
+    locale::DefaultLocaleManifest,
+};
+
+const ARCHITECTURE_TOKENS: [&str; 14] = [
+    "x86", "x64", "x86_64", "amd64", "arm", "arm64", "ia64", "32bit", "64bit", "32-bit", "64-bit",
+    "win32", "win64", "edition",
+];
+
+const LEGAL_SUFFIXES: [&str; 16] = [
+    "inc",
+    "incorporated",
+    "corp",
+    "corporation",
+    "co",
+    "company",
+    "llc",
+    "ltd",
+    "limited",
+    "gmbh",
+    "ag",
+    "sa",
+    "srl",
+    "bv",
+    "plc",
+    "pty",
+];
+
+/// How an [`ArpEntry`] was matched to a package.
+///
+/// The methods are ordered from the most to the least reliable.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum CorrelationMethod {
+    /// The product code of the entry matches an installer's product code or one of its Apps and
+    /// Features entries.
+    ProductCode,
+
+    /// The upgrade code of the entry matches one of an installer's Apps and Features entries.
+    UpgradeCode,
+
+    /// The package family name of the entry matches an installer's package family name.
+    PackageFamilyName,
+
+    /// The normalized display name and publisher of the entry match the package.
+    NameAndPublisher,
+}
+
+/// A package that an [`ArpEntry`] corresponds to.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Correlation {
+    /// The identifier of the package.
+    pub package_identifier: PackageIdentifier,
+
+    /// The version of the package, if it could be determined from the entry.
+    pub package_version: Option<PackageVersion>,
+
+    /// How the entry was matched to the package.
+    pub method: CorrelationMethod,
+}
+
+/// The correlation-relevant values of a single installer in a manifest.
+#[derive(Clone, Debug)]
+struct Candidate {
+    package_identifier: PackageIdentifier,
+    package_version: PackageVersion,
+    scope: Option<Scope>,
+    product_codes: BTreeSet<String>,
+    upgrade_codes: BTreeSet<String>,
+    package_family_name: Option<PackageFamilyName<'static>>,
+    names: BTreeSet<String>,
+    publishers: BTreeSet<String>,
+    display_versions: BTreeSet<Version>,
+}
+
+impl Candidate {
+    fn matches(&self, entry: &ArpEntry, method: CorrelationMethod) -> bool {
+        match method {
+            CorrelationMethod::ProductCode => entry
+                .product_code
+                .as_deref()
+                .is_some_and(|code| self.product_codes.contains(&code.to_ascii_lowercase())),
+            CorrelationMethod::UpgradeCode => entry
+                .upgrade_code
+                .as_deref()
+                .is_some_and(|code| self.upgrade_codes.contains(&code.to_ascii_lowercase())),
+            CorrelationMethod::PackageFamilyName => entry
+                .package_family_name
+                .as_ref()
+                .is_some_and(|name| self.package_family_name.as_ref() == Some(name)),
+            CorrelationMethod::NameAndPublisher => {
+                entry
+                    .display_name
+                    .as_deref()
+                    .map(normalize_name)
+                    .is_some_and(|name| self.names.contains(&name))
+                    && entry
+                        .publisher
+                        .as_deref()
+                        .map(normalize_publisher)
+                        .is_some_and(|publisher| self.publishers.contains(&publisher))
+            }
+        }
+    }
+}
+
+/// Matches Apps and Features entries observed on a machine to the packages of a catalog, using the
+/// same heuristics as the Windows Package Manager.
+///
+/// An entry is matched by the first of these that finds a package:
+///
+/// 1. Its product code against each installer's [`product_code`] and the product codes of its
+///    [`apps_and_features_entries`].
+/// 2. Its upgrade code against the upgrade codes of each installer's Apps and Features entries.
+/// 3. Its package family name against each installer's [`package_family_name`].
+/// 4. Its display name and publisher, once normalized, against the package name and publisher of
+///    the default locale and the display names and publishers of each installer's Apps and
+///    Features entries.
+///
+/// Installers with a scope that differs from the scope of the entry are not considered.
+///
+/// [`product_code`]: crate::installer::Installer::product_code
+/// [`apps_and_features_entries`]: crate::installer::Installer::apps_and_features_entries
+/// [`package_family_name`]: crate::installer::Installer::package_family_name
+#[derive(Clone, Debug, Default)]
+pub struct Correlator {
+    candidates: Vec<Candidate>,
+}
+
+impl Correlator {
+    /// Creates a new, empty `Correlator`.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            candidates: Vec::new(),
+        }
+    }
+
+    /// Adds a version of a package to the catalog.
+    ///
+    /// The default locale manifest provides the package name and publisher for name and publisher
+    /// matching. Without it, only the names and publishers of Apps and Features entries are used.
+    pub fn add(
+        &mut self,
+        installer_manifest: &InstallerManifest,
+        default_locale_manifest: Option<&DefaultLocaleManifest>,
+    ) {
+        for installer in &installer_manifest.installers {
+            let apps_and_features_entries = if installer.apps_and_features_entries.is_empty() {
+                &installer_manifest.apps_and_features_entries
+            } else {
+                &installer.apps_and_features_entries
+            };
+
+            let mut candidate = Candidate {
+                package_identifier: installer_manifest.package_identifier.clone(),
+                package_version: installer_manifest.package_version.clone(),
+                scope: installer.scope.or(installer_manifest.scope),
+                product_codes: installer
+                    .product_code
+                    .as_deref()
+                    .or(installer_manifest.product_code.as_deref())
+                    .into_iter()
+                    .chain(
+                        apps_and_features_entries
+                            .iter()
+                            .filter_map(|entry| entry.product_code()),
+                    )
+                    .map(str::to_ascii_lowercase)
+                    .collect(),
+                upgrade_codes: apps_and_features_entries
+                    .iter()
+                    .filter_map(|entry| entry.upgrade_code())
+                    .map(str::to_ascii_lowercase)
+                    .collect(),
+                package_family_name: installer
+                    .package_family_name
+                    .clone()
+                    .or_else(|| installer_manifest.package_family_name.clone()),
+                names: apps_and_features_entries
+                    .iter()
+                    .filter_map(|entry| entry.display_name())
+                    .map(normalize_name)
+                    .collect(),
+                publishers: apps_and_features_entries
+                    .iter()
+                    .filter_map(|entry| entry.publisher())
+                    .map(normalize_publisher)
+                    .collect(),
+                display_versions: apps_and_features_entries
+                    .iter()
+                    .filter_map(|entry| entry.display_version())
+                    .cloned()
+                    .collect(),
+            };
+
+            if let Some(manifest) = default_locale_manifest {
+                candidate
+                    .names
+                    .insert(normalize_name(manifest.package_name.as_str()));
+                candidate
+                    .publishers
+                    .insert(normalize_publisher(manifest.publisher.as_str()));
+            }
+            candidate.names.remove("");
+            candidate.publishers.remove("");
+
+            self.candidates.push(candidate);
+        }
+    }
+
+    /// Returns every package that the entry matches using the most reliable method that finds a
+    /// match, with one correlation per package.
+    #[must_use]
+    pub fn matches(&self, entry: &ArpEntry) -> Vec<Correlation> {
+        for method in [
+            CorrelationMethod::ProductCode,
+            CorrelationMethod::UpgradeCode,
+            CorrelationMethod::PackageFamilyName,
+            CorrelationMethod::NameAndPublisher,
+        ] {
+            let matched =
+                self.candidates
+                    .iter()
+                    .filter(|candidate| {
+                        candidate.scope.zip(entry.scope).is_none_or(
+                            |(installer_scope, entry_scope)| installer_scope == entry_scope,
+                        )
+                    })
+                    .filter(|candidate| candidate.matches(entry, method))
+                    .collect::<Vec<_>>();
+
+            if matched.is_empty() {
+                continue;
+            }
+
+            let package_identifiers = matched
+                .iter()
+                .map(|candidate| &candidate.package_identifier)
+                .collect::<BTreeSet<_>>();
+
+            return package_identifiers
+                .into_iter()
+                .map(|package_identifier| {
+                    // Names and publishers are shared by every version of a package, so the version
+                    // is determined from all of them
+                    let versions = if method == CorrelationMethod::NameAndPublisher {
+                        self.candidates
+                            .iter()
+                            .filter(|candidate| &candidate.package_identifier == package_identifier)
+                            .collect::<Vec<_>>()
+                    } else {
+                        matched
+                            .iter()
+                            .filter(|candidate| &candidate.package_identifier == package_identifier)
+                            .copied()
+                            .collect::<Vec<_>>()
+                    };
+
+                    Correlation {
+                        package_identifier: package_identifier.clone(),
+                        package_version: self.package_version(entry, package_identifier, &versions),
+                        method,
+                    }
+                })
+                .collect();
+        }
+
+        Vec::new()
+    }
+
+    /// Returns the package that the entry corresponds to, or `None` if it matches no package or
+    /// more than one package.
+    #[must_use]
+    pub fn correlate(&self, entry: &ArpEntry) -> Option<Correlation> {
+        let mut matches = self.matches(entry);
+
+        if matches.len() == 1 {
+            matches.pop()
+        } else {
+            None
+        }
+    }
+
+    /// Determines the version of a matched package.
+    ///
+    /// A single matched version is used as is. Otherwise, the display version of the entry is
+    /// compared to the Apps and Features display versions and then to the package versions of
+    /// every version of the package.
+    fn package_version(
+        &self,
+        entry: &ArpEntry,
+        package_identifier: &PackageIdentifier,
+        matched: &[&Candidate],
+    ) -> Option<PackageVersion> {
+        let unique_version = |candidates: &mut dyn Iterator<Item = &Candidate>| {
+            let versions = candidates
+                .map(|candidate| &candidate.package_version)
+                .collect::<BTreeSet<_>>();
+            (versions.len() == 1).then(|| versions.into_iter().next().cloned())?
+        };
+
+        if let Some(version) = unique_version(&mut matched.iter().copied()) {
+            return Some(version);
+        }
+
+        let display_version = entry.display_version.as_ref()?;
+        let package_candidates = || {
+            self.candidates
+                .iter()
+                .filter(move |candidate| &candidate.package_identifier == package_identifier)
+        };
+
+        unique_version(
+            &mut package_candidates()
+                .filter(|candidate| candidate.display_versions.contains(display_version)),
+        )
+        .or_else(|| {
+            unique_version(&mut package_candidates().filter(|candidate| {
+                candidate.display_versions.is_empty()
+                    && candidate.package_version == *display_version
+            }))
+        })
+    }
+}
+
+/// Normalizes a display name by removing architectures, versions and bracketed text, and
+/// then removing everything that is not a letter or a digit.
+fn normalize_name(name: &str) -> String {
+    let mut without_brackets = String::with_capacity(name.len());
+    let mut depth = 0_usize;
+    for char in name.chars() {
+        match char {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => without_brackets.push(char),
+            _ => {}
+        }
+    }
+
+    without_brackets
+        .split_whitespace()
+        .filter(|token| !is_version(token) && !is_architecture(token))
+        .flat_map(str::chars)
+        .filter(|char| char.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Normalizes a publisher by removing legal entity suffixes, and then removing everything that is
+/// not a letter or a digit.
+fn normalize_publisher(publisher: &str) -> String {
+    publisher
+        .split(|char: char| char.is_whitespace() || char == ',')
+        .filter(|token| {
+            let token = token.trim_matches('.');
+            !LEGAL_SUFFIXES
+                .iter()
+                .any(|suffix| suffix.eq_ignore_ascii_case(token))
+        })
+        .flat_map(str::chars)
+        .filter(|char| char.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn is_version(token: &str) -> bool {
+    let token = token.strip_prefix(['v', 'V']).unwrap_or(token);
+
+    token.starts_with(|char: char| char.is_ascii_digit())
+        && token
+            .chars()
+            .all(|char| char.is_ascii_digit() || matches!(char, '.' | '-' | '_'))
+}
+
+fn is_architecture(token: &str) -> bool {
+    let token = token.trim_matches(|char: char| !char.is_alphanumeric());
+
+    ARCHITECTURE_TOKENS
+        .iter()
+        .any(|architecture| architecture.eq_ignore_ascii_case(token))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, vec};
+
+    use rstest::rstest;
+
+    use super::{CorrelationMethod, Correlator, normalize_name, normalize_publisher};
+    use crate::{
+        PackageVersion, Version,
+        installed::ArpEntry,
+        installer::{AppsAndFeaturesEntry, Installer, InstallerManifest, Scope},
+        locale::DefaultLocaleManifest,
+    };
+
+    fn seven_zip(version: &str, product_code: &str) -> InstallerManifest {
+        InstallerManifest {
+            package_identifier: "7zip.7zip".parse().unwrap(),
+            package_version: version.parse().unwrap(),
+            installers: vec![Installer {
+                scope: Some(Scope::Machine),
+                product_code: Some(product_code.into()),
+                apps_and_features_entries: AppsAndFeaturesEntry::builder()
+                    .display_name(format!("7-Zip {version} (x64 edition)"))
+                    .publisher("Igor Pavlov")
+                    .upgrade_code("{23170F69-40C1-2702-0000-000004000000}")
+                    .build()
+                    .into(),
+                ..Installer::default()
+            }],
+            ..InstallerManifest::default()
+        }
+    }
+
+    fn correlator() -> Correlator {
+        let mut correlator = Correlator::new();
+        correlator.add(
+            &seven_zip("24.08", "{23170F69-40C1-2702-2408-000001000000}"),
+            None,
+        );
+        correlator.add(
+            &seven_zip("24.09", "{23170F69-40C1-2702-2409-000001000000}"),
+            None,
+        );
+
+        let mut notepad = InstallerManifest {
+            package_identifier: "Notepad++.Notepad++".parse().unwrap(),
+            package_version: "8.7".parse().unwrap(),
+            installers: vec![Installer::default()],
+            ..InstallerManifest::default()
+        };
+        correlator.add(
+            &notepad,
+            Some(&DefaultLocaleManifest {
+                package_name: "Notepad++".parse().unwrap(),
+                publisher: "Notepad++ Team".parse().unwrap(),
+                ..DefaultLocaleManifest::default()
+            }),
+        );
+        notepad.package_version = "8.6".parse().unwrap();
+        correlator.add(&notepad, None);
+
+        correlator
+    }
+
+    #[test]
+    fn product_code() {
+        let correlation = correlator()
+            .correlate(&ArpEntry {
+                product_code: Some("{23170f69-40c1-2702-2409-000001000000}".into()),
+                ..ArpEntry::default()
+            })
+            .unwrap();
+
+        assert_eq!(correlation.package_identifier.as_str(), "7zip.7zip");
+        assert_eq!(
+            correlation.package_version,
+            Some(PackageVersion::new("24.09").unwrap())
+        );
+        assert_eq!(correlation.method, CorrelationMethod::ProductCode);
+    }
+
+    #[test]
+    fn upgrade_code_with_display_version() {
+        let correlation = correlator()
+            .correlate(&ArpEntry {
+                display_version: Some(Version::new("24.08")),
+                upgrade_code: Some("{23170F69-40C1-2702-0000-000004000000}".into()),
+                ..ArpEntry::default()
+            })
+            .unwrap();
+
+        assert_eq!(correlation.method, CorrelationMethod::UpgradeCode);
+        assert_eq!(
+            correlation.package_version,
+            Some(PackageVersion::new("24.08").unwrap())
+        );
+    }
+
+    #[test]
+    fn name_and_publisher() {
+        let correlation = correlator()
+            .correlate(&ArpEntry {
+                display_name: Some("Notepad++ (64-bit x64)".into()),
+                publisher: Some("Notepad++ Team".into()),
+                display_version: Some(Version::new("8.6")),
+                ..ArpEntry::default()
+            })
+            .unwrap();
+
+        assert_eq!(
+            correlation.package_identifier.as_str(),
+            "Notepad++.Notepad++"
+        );
+        assert_eq!(correlation.method, CorrelationMethod::NameAndPublisher);
+        assert_eq!(
+            correlation.package_version,
+            Some(PackageVersion::new("8.6").unwrap())
+        );
+    }
+
+    #[test]
+    fn scope_mismatch() {
+        assert_eq!(
+            correlator().correlate(&ArpEntry {
+                product_code: Some("{23170F69-40C1-2702-2409-000001000000}".into()),
+                scope: Some(Scope::User),
+                ..ArpEntry::default()
+            }),
+            None
+        );
+    }
+
+    #[rstest]
+    #[case("7-Zip 24.09 (x64 edition)", "7zip")]
+    #[case("Python 3.12.0 (64-bit)", "python")]
+    #[case("Mozilla Firefox ESR x64 v128.0", "mozillafirefoxesr")]
+    fn normalized_name(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(normalize_name(name), expected);
+    }
+
+    #[rstest]
+    #[case("Microsoft Corporation", "microsoft")]
+    #[case("Mozilla, Inc.", "mozilla")]
+    #[case("JetBrains s.r.o.", "jetbrainssro")]
+    fn normalized_publisher(#[case] publisher: &str, #[case] expected: &str) {
+        assert_eq!(normalize_publisher(publisher), expected);
+    }
+}
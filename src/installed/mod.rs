@@ -0,0 +1,13 @@
+//! Matching of software installed on a machine to packages and their manifests.
+
+mod arp_entry;
+mod correlation;
+mod pin;
+mod upgrade;
+mod version_mapping;
+
+pub use arp_entry::ArpEntry;
+pub use correlation::{Correlation, CorrelationMethod, Correlator};
+pub use pin::{GatedVersion, GatedVersionError, Pin, PinType, PinTypeParseError};
+pub use upgrade::{InstalledPackage, Upgrade, UpgradeDecision, UpgradeOptions};
+pub use version_mapping::{DisplayVersionRange, VersionMapping, VersionMappingError};
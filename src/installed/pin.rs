@@ -0,0 +1,353 @@
+use alloc::string::String;
+use core::{fmt, str::FromStr};
+
+use compact_str::CompactString;
+use thiserror::Error;
+
+use crate::{PackageIdentifier, Version};
+
+/// The type of a pin, which restricts how a package is upgraded.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PinType {
+    /// The package is excluded from `winget upgrade --all`, but can be upgraded with
+    /// `--include-pinned` or by targeting it explicitly.
+    #[default]
+    Pinning,
+
+    /// The package is never upgraded, even when targeted explicitly.
+    Blocking,
+
+    /// The package is only upgraded to versions that match its [gated version].
+    ///
+    /// [gated version]: Pin::gated_version
+    Gating,
+}
+
+const PINNING: &str = "Pinning";
+const BLOCKING: &str = "Blocking";
+const GATING: &str = "Gating";
+
+impl PinType {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pinning => PINNING,
+            Self::Blocking => BLOCKING,
+            Self::Gating => GATING,
+        }
+    }
+}
+
+impl fmt::Display for PinType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Error, Debug, Eq, PartialEq)]
+#[error("Pin type did not match any of `{PINNING}`, `{BLOCKING}`, or `{GATING}`")]
+pub struct PinTypeParseError;
+
+impl FromStr for PinType {
+    type Err = PinTypeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            PINNING => Ok(Self::Pinning),
+            BLOCKING => Ok(Self::Blocking),
+            GATING => Ok(Self::Gating),
+            _ => Err(PinTypeParseError),
+        }
+    }
+}
+
+/// A pattern of versions that a gating pin allows upgrading to.
+///
+/// A gated version is either a version prefix followed by a wildcard, such as `1.2.*`, or a
+/// version with an optional comparison operator, such as `1.2.3`, `=1.2.3`, `<2.0` or `>=1.5`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "CompactString"))]
+pub enum GatedVersion {
+    /// Versions that start with the parts of the prefix, such as `1.2.*`.
+    Wildcard(Version),
+    /// Versions equal to the version, such as `1.2.3` or `=1.2.3`.
+    Equal(Version),
+    /// Versions less than the version, such as `<2.0`.
+    Less(Version),
+    /// Versions less than or equal to the version, such as `<=2.0`.
+    LessOrEqual(Version),
+    /// Versions greater than the version, such as `>1.0`.
+    Greater(Version),
+    /// Versions greater than or equal to the version, such as `>=1.0`.
+    GreaterOrEqual(Version),
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum GatedVersionError {
+    #[error("Gated version must not be empty")]
+    Empty,
+    #[error("Gated version may only have a wildcard as its last part and without an operator")]
+    InvalidWildcard,
+}
+
+impl GatedVersion {
+    const WILDCARD: &'static str = "*";
+
+    /// Returns `true` if the version matches this pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::{Version, installed::GatedVersion};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let gated_version = "1.2.*".parse::<GatedVersion>()?;
+    ///
+    /// assert!(gated_version.matches(&Version::new("1.2")));
+    /// assert!(gated_version.matches(&Version::new("1.2.5")));
+    /// assert!(!gated_version.matches(&Version::new("1.20")));
+    ///
+    /// assert!("<2.0".parse::<GatedVersion>()?.matches(&Version::new("1.9.9")));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn matches(&self, version: &Version) -> bool {
+        match self {
+            Self::Wildcard(prefix) => {
+                let count = prefix.as_str().split('.').count();
+                let version = version.as_str();
+                let head = version
+                    .match_indices('.')
+                    .nth(count - 1)
+                    .map_or(version, |(index, _)| &version[..index]);
+
+                head.split('.').count() == count && Version::new(head) == *prefix
+            }
+            Self::Equal(gate) => version == gate,
+            Self::Less(gate) => version < gate,
+            Self::LessOrEqual(gate) => version <= gate,
+            Self::Greater(gate) => version > gate,
+            Self::GreaterOrEqual(gate) => version >= gate,
+        }
+    }
+}
+
+impl fmt::Display for GatedVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Wildcard(prefix) => write!(f, "{prefix}.{}", Self::WILDCARD),
+            Self::Equal(version) => version.fmt(f),
+            Self::Less(version) => write!(f, "<{version}"),
+            Self::LessOrEqual(version) => write!(f, "<={version}"),
+            Self::Greater(version) => write!(f, ">{version}"),
+            Self::GreaterOrEqual(version) => write!(f, ">={version}"),
+        }
+    }
+}
+
+impl FromStr for GatedVersion {
+    type Err = GatedVersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        let (constructor, version): (fn(Version) -> Self, &str) =
+            if let Some(version) = s.strip_prefix("<=") {
+                (Self::LessOrEqual, version)
+            } else if let Some(version) = s.strip_prefix(">=") {
+                (Self::GreaterOrEqual, version)
+            } else if let Some(version) = s.strip_prefix('<') {
+                (Self::Less, version)
+            } else if let Some(version) = s.strip_prefix('>') {
+                (Self::Greater, version)
+            } else if let Some(version) = s.strip_prefix('=') {
+                (Self::Equal, version)
+            } else if let Some(prefix) = s.strip_suffix(Self::WILDCARD) {
+                let prefix = prefix
+                    .strip_suffix('.')
+                    .filter(|prefix| !prefix.is_empty())
+                    .ok_or(GatedVersionError::InvalidWildcard)?;
+                (Self::Wildcard, prefix)
+            } else {
+                (Self::Equal, s)
+            };
+
+        let version = version.trim();
+        if version.is_empty() {
+            return Err(GatedVersionError::Empty);
+        }
+        if version.contains(Self::WILDCARD) {
+            return Err(GatedVersionError::InvalidWildcard);
+        }
+
+        Ok(constructor(Version::new(version)))
+    }
+}
+
+impl TryFrom<CompactString> for GatedVersion {
+    type Error = GatedVersionError;
+
+    #[inline]
+    fn try_from(value: CompactString) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for GatedVersion {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// A pin on a package, as listed by `winget pin list`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
+pub struct Pin {
+    /// The identifier of the pinned package.
+    pub package_identifier: PackageIdentifier,
+
+    /// The identifier of the source the package is pinned from.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub source_id: Option<String>,
+
+    /// The type of the pin.
+    pub pin_type: PinType,
+
+    /// The versions that a [gating] pin allows upgrading to.
+    ///
+    /// A gating pin without a gated version blocks every upgrade.
+    ///
+    /// [gating]: PinType::Gating
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub gated_version: Option<GatedVersion>,
+}
+
+impl Pin {
+    /// Returns `true` if this pin allows upgrading to the version.
+    ///
+    /// A [pinning] pin only allows upgrades when pinned packages are included, such as with
+    /// `--include-pinned` or by targeting the package explicitly. A [blocking] pin never allows
+    /// upgrades and a [gating] pin only allows upgrades to versions that match its gated version.
+    ///
+    /// [pinning]: PinType::Pinning
+    /// [blocking]: PinType::Blocking
+    /// [gating]: PinType::Gating
+    #[must_use]
+    pub fn allows(&self, version: &Version, include_pinned: bool) -> bool {
+        match self.pin_type {
+            PinType::Pinning => include_pinned,
+            PinType::Blocking => false,
+            PinType::Gating => self
+                .gated_version
+                .as_ref()
+                .is_some_and(|gated_version| gated_version.matches(version)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use rstest::rstest;
+
+    use super::{GatedVersion, GatedVersionError, Pin, PinType};
+    use crate::Version;
+
+    #[rstest]
+    #[case("1.2.*", "1.2", true)]
+    #[case("1.2.*", "1.2.0.5", true)]
+    #[case("1.2.*", "v1.2.3", true)]
+    #[case("1.2.*", "1.20", false)]
+    #[case("1.2.*", "1.3", false)]
+    #[case("1.2.*", "1", false)]
+    #[case("1.2", "1.2.0", true)]
+    #[case("=1.2", "1.2.1", false)]
+    #[case("<2.0", "1.9.9", true)]
+    #[case("<2.0", "2.0", false)]
+    #[case("<=2.0", "2.0", true)]
+    #[case(">1.0", "1.0.1", true)]
+    #[case(">=1.0", "0.9", false)]
+    fn matches(#[case] gated_version: &str, #[case] version: &str, #[case] expected: bool) {
+        assert_eq!(
+            gated_version
+                .parse::<GatedVersion>()
+                .unwrap()
+                .matches(&Version::new(version)),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("1.2.*")]
+    #[case("1.2.3")]
+    #[case("<2.0")]
+    #[case("<=2.0")]
+    #[case(">1.0")]
+    #[case(">=1.0")]
+    fn display(#[case] gated_version: &str) {
+        assert_eq!(
+            gated_version.parse::<GatedVersion>().unwrap().to_string(),
+            gated_version
+        );
+    }
+
+    #[rstest]
+    #[case("", GatedVersionError::Empty)]
+    #[case("<", GatedVersionError::Empty)]
+    #[case("*", GatedVersionError::InvalidWildcard)]
+    #[case("1.*.3", GatedVersionError::InvalidWildcard)]
+    #[case("<1.*", GatedVersionError::InvalidWildcard)]
+    #[case("1.2*", GatedVersionError::InvalidWildcard)]
+    fn invalid(#[case] gated_version: &str, #[case] expected: GatedVersionError) {
+        assert_eq!(gated_version.parse::<GatedVersion>(), Err(expected));
+    }
+
+    #[rstest]
+    #[case(PinType::Pinning, None, false, false)]
+    #[case(PinType::Pinning, None, true, true)]
+    #[case(PinType::Blocking, None, true, false)]
+    #[case(PinType::Gating, Some("1.*"), false, true)]
+    #[case(PinType::Gating, Some("<1.5"), true, false)]
+    #[case(PinType::Gating, None, true, false)]
+    fn allows(
+        #[case] pin_type: PinType,
+        #[case] gated_version: Option<&str>,
+        #[case] include_pinned: bool,
+        #[case] expected: bool,
+    ) {
+        let pin = Pin {
+            pin_type,
+            gated_version: gated_version.map(|version| version.parse().unwrap()),
+            ..Pin::default()
+        };
+
+        assert_eq!(pin.allows(&Version::new("1.5"), include_pinned), expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        use indoc::indoc;
+
+        const PIN: &str = indoc! {"
+            PackageIdentifier: Microsoft.PowerToys
+            SourceId: winget
+            PinType: Gating
+            GatedVersion: 0.85.*
+        "};
+
+        let pin = serde_yaml::from_str::<Pin>(PIN).unwrap();
+        assert_eq!(pin.pin_type, PinType::Gating);
+        assert!(pin.allows(&Version::new("0.85.1"), false));
+        assert_eq!(serde_yaml::to_string(&pin).unwrap(), PIN);
+    }
+}
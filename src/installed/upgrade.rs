@@ -0,0 +1,505 @@
+use alloc::vec::Vec;
+
+use super::Pin;
+use crate::{
+    PackageVersion, Version,
+    installer::{
+        Architecture, Channel, Installer, InstallerManifest, InstallerType, Scope, UpgradeBehavior,
+    },
+};
+
+/// The state of a package installed on a machine.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct InstalledPackage {
+    /// The installed version, which may be [unknown].
+    ///
+    /// [unknown]: Version::is_unknown
+    pub version: Version,
+
+    /// The channel the installed version was released on.
+    pub channel: Option<Channel>,
+
+    /// The scope the package is installed in, if known.
+    pub scope: Option<Scope>,
+
+    /// The architecture of the installed package, if known.
+    pub architecture: Option<Architecture>,
+
+    /// The type of installer that installed the package, if known.
+    pub installer_type: Option<InstallerType>,
+
+    /// The pin on the package, if it is pinned.
+    pub pin: Option<Pin>,
+}
+
+/// Options that change which upgrades are considered, matching the options of `winget upgrade`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct UpgradeOptions {
+    /// Consider packages with an unknown installed version upgradable, like `--include-unknown`.
+    pub include_unknown: bool,
+
+    /// The package is targeted by its identifier rather than through `--all`, which allows
+    /// upgrading packages that [require an explicit upgrade].
+    ///
+    /// [require an explicit upgrade]: Installer::require_explicit_upgrade
+    pub explicit: bool,
+
+    /// Consider packages with a [pinning] pin upgradable, like `--include-pinned`.
+    ///
+    /// [pinning]: super::PinType::Pinning
+    pub include_pinned: bool,
+}
+
+/// An upgrade of an installed package to a newer version.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Upgrade<'manifest> {
+    /// The manifest of the version to upgrade to.
+    pub manifest: &'manifest InstallerManifest,
+
+    /// The installer used for the upgrade.
+    pub installer: &'manifest Installer,
+
+    /// Whether the installed version must be uninstalled before installing the new version.
+    pub uninstall_previous: bool,
+}
+
+impl Upgrade<'_> {
+    /// Returns the version to upgrade to.
+    #[must_use]
+    #[inline]
+    pub const fn version(&self) -> &PackageVersion {
+        &self.manifest.package_version
+    }
+}
+
+/// Whether an installed package can be upgraded, and how.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpgradeDecision<'manifest> {
+    /// An upgrade is available.
+    Available(Upgrade<'manifest>),
+
+    /// An upgrade is available but is only performed when the package is targeted explicitly.
+    RequiresExplicit(Upgrade<'manifest>),
+
+    /// A newer version is available, but its installer does not allow upgrading.
+    Denied(Upgrade<'manifest>),
+
+    /// A newer version is available, but the pin on the package does not allow upgrading to it.
+    Pinned(&'manifest InstallerManifest),
+
+    /// A newer version is available, but none of its installers are applicable to the installed
+    /// package.
+    NoApplicableInstaller(&'manifest InstallerManifest),
+
+    /// The installed version is unknown, so it is not compared to the available versions.
+    UnknownVersion,
+
+    /// No newer version is available.
+    UpToDate,
+}
+
+impl UpgradeDecision<'_> {
+    /// Returns `true` if `winget upgrade` would list the package as upgradable.
+    #[must_use]
+    #[inline]
+    pub const fn is_upgradable(&self) -> bool {
+        matches!(self, Self::Available(_))
+    }
+}
+
+impl InstalledPackage {
+    /// Decides whether this package can be upgraded to one of the available versions of it.
+    ///
+    /// Only versions on the same channel as the installed version are considered. The newest of
+    /// those that is greater than the installed version and allowed by the pin on the package is
+    /// used, and the installer used for the upgrade must match the installed scope, architecture
+    /// and installer type where they are known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::{
+    ///     Version,
+    ///     installed::{InstalledPackage, UpgradeOptions},
+    ///     installer::{Installer, InstallerManifest},
+    /// };
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let manifest = InstallerManifest {
+    ///     package_version: "1.2.0".parse()?,
+    ///     installers: vec![Installer::default()],
+    ///     ..InstallerManifest::default()
+    /// };
+    ///
+    /// let installed = InstalledPackage {
+    ///     version: Version::new("1.1.0"),
+    ///     ..InstalledPackage::default()
+    /// };
+    ///
+    /// assert!(
+    ///     installed
+    ///         .upgrade([&manifest], UpgradeOptions::default())
+    ///         .is_upgradable()
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn upgrade<'manifest, I>(
+        &self,
+        manifests: I,
+        options: UpgradeOptions,
+    ) -> UpgradeDecision<'manifest>
+    where
+        I: IntoIterator<Item = &'manifest InstallerManifest>,
+    {
+        if self.version.is_unknown() && !options.include_unknown {
+            return UpgradeDecision::UnknownVersion;
+        }
+
+        let newer = manifests
+            .into_iter()
+            .filter(|manifest| {
+                self.is_same_channel(manifest.channel.as_ref())
+                    && *manifest.package_version.inner() > self.version
+            })
+            .collect::<Vec<_>>();
+        let newest = |manifests: &mut dyn Iterator<Item = &'manifest InstallerManifest>| {
+            manifests.max_by(|a, b| a.package_version.cmp(&b.package_version))
+        };
+
+        let Some(newest_manifest) = newest(&mut newer.iter().copied()) else {
+            return UpgradeDecision::UpToDate;
+        };
+
+        let Some(manifest) = newest(&mut newer.iter().copied().filter(|manifest| {
+            self.pin.as_ref().is_none_or(|pin| {
+                pin.allows(
+                    manifest.package_version.inner(),
+                    options.include_pinned || options.explicit,
+                )
+            })
+        })) else {
+            return UpgradeDecision::Pinned(newest_manifest);
+        };
+
+        let Some(installer) = manifest
+            .installers
+            .iter()
+            .filter(|installer| self.is_applicable(manifest, installer))
+            .min_by_key(|installer| self.preference(manifest, installer))
+        else {
+            return UpgradeDecision::NoApplicableInstaller(manifest);
+        };
+
+        let upgrade_behavior = installer
+            .upgrade_behavior
+            .or(manifest.upgrade_behavior)
+            .unwrap_or(UpgradeBehavior::Install);
+        let upgrade = Upgrade {
+            manifest,
+            installer,
+            uninstall_previous: upgrade_behavior == UpgradeBehavior::UninstallPrevious,
+        };
+
+        if upgrade_behavior == UpgradeBehavior::Deny {
+            UpgradeDecision::Denied(upgrade)
+        } else if (installer.require_explicit_upgrade || manifest.require_explicit_upgrade)
+            && !options.explicit
+        {
+            UpgradeDecision::RequiresExplicit(upgrade)
+        } else {
+            UpgradeDecision::Available(upgrade)
+        }
+    }
+
+    fn is_same_channel(&self, channel: Option<&Channel>) -> bool {
+        match (&self.channel, channel) {
+            (Some(installed), Some(channel)) => {
+                installed.as_str().eq_ignore_ascii_case(channel.as_str())
+            }
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    fn is_applicable(&self, manifest: &InstallerManifest, installer: &Installer) -> bool {
+        let scope = installer.scope.or(manifest.scope);
+        let installer_type = effective_type(manifest, installer);
+
+        self.scope
+            .zip(scope)
+            .is_none_or(|(installed, scope)| installed == scope)
+            && self.architecture.is_none_or(|architecture| {
+                installer.architecture == architecture
+                    || installer.architecture == Architecture::Neutral
+            })
+            && self
+                .installer_type
+                .zip(installer_type)
+                .is_none_or(|(installed, installer_type)| {
+                    is_compatible_type(installed, installer_type)
+                })
+    }
+
+    /// Orders applicable installers, preferring an exact architecture and installer type.
+    fn preference(&self, manifest: &InstallerManifest, installer: &Installer) -> (bool, bool) {
+        (
+            self.architecture
+                .is_some_and(|architecture| installer.architecture != architecture),
+            self.installer_type
+                .is_some_and(|installed| effective_type(manifest, installer) != Some(installed)),
+        )
+    }
+}
+
+/// Returns the installer type of an installer, using the nested installer type of archives.
+fn effective_type(manifest: &InstallerManifest, installer: &Installer) -> Option<InstallerType> {
+    match installer.r#type.or(manifest.r#type)? {
+        InstallerType::Zip => installer
+            .nested_installer_type
+            .or(manifest.nested_installer_type)
+            .map(InstallerType::from),
+        installer_type => Some(installer_type),
+    }
+}
+
+/// Returns `true` if a package installed by one installer type can be upgraded by another.
+///
+/// Installer types that write the same kind of installation record are compatible with each other.
+fn is_compatible_type(installed: InstallerType, available: InstallerType) -> bool {
+    use InstallerType::{Appx, Burn, Exe, Inno, Msi, Msix, Nullsoft, Wix};
+
+    installed == available
+        || matches!(
+            (installed, available),
+            (Msi | Wix, Msi | Wix)
+                | (Msix | Appx, Msix | Appx)
+                | (Exe | Inno | Nullsoft | Burn, Exe | Inno | Nullsoft | Burn)
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use rstest::rstest;
+
+    use super::{InstalledPackage, UpgradeDecision, UpgradeOptions};
+    use crate::{
+        Version,
+        installed::{Pin, PinType},
+        installer::{
+            Architecture, Channel, Installer, InstallerManifest, InstallerType, Scope,
+            UpgradeBehavior,
+        },
+    };
+
+    fn manifest(version: &str) -> InstallerManifest {
+        InstallerManifest {
+            package_version: version.parse().unwrap(),
+            r#type: Some(InstallerType::Msi),
+            installers: vec![
+                Installer {
+                    architecture: Architecture::X86,
+                    scope: Some(Scope::Machine),
+                    ..Installer::default()
+                },
+                Installer {
+                    architecture: Architecture::X64,
+                    scope: Some(Scope::Machine),
+                    ..Installer::default()
+                },
+            ],
+            ..InstallerManifest::default()
+        }
+    }
+
+    fn installed(version: &str) -> InstalledPackage {
+        InstalledPackage {
+            version: Version::new(version),
+            scope: Some(Scope::Machine),
+            architecture: Some(Architecture::X64),
+            installer_type: Some(InstallerType::Wix),
+            ..InstalledPackage::default()
+        }
+    }
+
+    #[test]
+    fn available() {
+        let manifests = [manifest("1.0"), manifest("1.2"), manifest("1.1")];
+
+        let UpgradeDecision::Available(upgrade) =
+            installed("1.0").upgrade(&manifests, UpgradeOptions::default())
+        else {
+            panic!("Expected an available upgrade");
+        };
+        assert_eq!(upgrade.version().as_str(), "1.2");
+        assert_eq!(upgrade.installer.architecture, Architecture::X64);
+        assert!(!upgrade.uninstall_previous);
+    }
+
+    #[rstest]
+    #[case("1.2")]
+    #[case("1.3")]
+    fn up_to_date(#[case] version: &str) {
+        assert_eq!(
+            installed(version).upgrade(&[manifest("1.2")], UpgradeOptions::default()),
+            UpgradeDecision::UpToDate
+        );
+    }
+
+    #[test]
+    fn unknown_version() {
+        let manifests = [manifest("1.2")];
+        let installed = installed("Unknown");
+
+        assert_eq!(
+            installed.upgrade(&manifests, UpgradeOptions::default()),
+            UpgradeDecision::UnknownVersion
+        );
+        assert!(
+            installed
+                .upgrade(
+                    &manifests,
+                    UpgradeOptions {
+                        include_unknown: true,
+                        ..UpgradeOptions::default()
+                    }
+                )
+                .is_upgradable()
+        );
+    }
+
+    #[test]
+    fn upgrade_behavior() {
+        let mut manifest = manifest("1.2");
+
+        manifest.upgrade_behavior = Some(UpgradeBehavior::UninstallPrevious);
+        assert!(matches!(
+            installed("1.0").upgrade([&manifest], UpgradeOptions::default()),
+            UpgradeDecision::Available(upgrade) if upgrade.uninstall_previous
+        ));
+
+        manifest.installers[1].upgrade_behavior = Some(UpgradeBehavior::Deny);
+        assert!(matches!(
+            installed("1.0").upgrade([&manifest], UpgradeOptions::default()),
+            UpgradeDecision::Denied(_)
+        ));
+    }
+
+    #[test]
+    fn require_explicit_upgrade() {
+        let mut manifest = manifest("1.2");
+        manifest.require_explicit_upgrade = true;
+
+        assert!(matches!(
+            installed("1.0").upgrade([&manifest], UpgradeOptions::default()),
+            UpgradeDecision::RequiresExplicit(_)
+        ));
+        assert!(
+            installed("1.0")
+                .upgrade(
+                    [&manifest],
+                    UpgradeOptions {
+                        explicit: true,
+                        ..UpgradeOptions::default()
+                    }
+                )
+                .is_upgradable()
+        );
+    }
+
+    #[test]
+    fn channel() {
+        let mut beta = manifest("2.0-beta");
+        beta.channel = Some(Channel::new("beta").unwrap());
+        let manifests = [manifest("1.2"), beta];
+
+        assert!(matches!(
+            installed("1.0").upgrade(&manifests, UpgradeOptions::default()),
+            UpgradeDecision::Available(upgrade) if upgrade.version().as_str() == "1.2"
+        ));
+        assert!(matches!(
+            InstalledPackage {
+                channel: Some(Channel::new("Beta").unwrap()),
+                ..installed("1.0")
+            }
+            .upgrade(&manifests, UpgradeOptions::default()),
+            UpgradeDecision::Available(upgrade) if upgrade.version().as_str() == "2.0-beta"
+        ));
+    }
+
+    #[test]
+    fn pin() {
+        let manifests = [manifest("1.1"), manifest("1.2"), manifest("2.0")];
+        let pinned = |pin_type, gated_version: Option<&str>| InstalledPackage {
+            pin: Some(Pin {
+                pin_type,
+                gated_version: gated_version.map(|version| version.parse().unwrap()),
+                ..Pin::default()
+            }),
+            ..installed("1.0")
+        };
+
+        assert!(matches!(
+            pinned(PinType::Pinning, None).upgrade(&manifests, UpgradeOptions::default()),
+            UpgradeDecision::Pinned(manifest) if manifest.package_version.as_str() == "2.0"
+        ));
+        assert!(
+            pinned(PinType::Pinning, None)
+                .upgrade(
+                    &manifests,
+                    UpgradeOptions {
+                        include_pinned: true,
+                        ..UpgradeOptions::default()
+                    }
+                )
+                .is_upgradable()
+        );
+        assert!(matches!(
+            pinned(PinType::Blocking, None).upgrade(
+                &manifests,
+                UpgradeOptions {
+                    explicit: true,
+                    include_pinned: true,
+                    ..UpgradeOptions::default()
+                }
+            ),
+            UpgradeDecision::Pinned(_)
+        ));
+        assert!(matches!(
+            pinned(PinType::Gating, Some("1.*")).upgrade(&manifests, UpgradeOptions::default()),
+            UpgradeDecision::Available(upgrade) if upgrade.version().as_str() == "1.2"
+        ));
+    }
+
+    #[rstest]
+    #[case(Some(Scope::User), Some(Architecture::X64), Some(InstallerType::Msi))]
+    #[case(
+        Some(Scope::Machine),
+        Some(Architecture::Arm64),
+        Some(InstallerType::Msi)
+    )]
+    #[case(
+        Some(Scope::Machine),
+        Some(Architecture::X64),
+        Some(InstallerType::Inno)
+    )]
+    fn no_applicable_installer(
+        #[case] scope: Option<Scope>,
+        #[case] architecture: Option<Architecture>,
+        #[case] installer_type: Option<InstallerType>,
+    ) {
+        let installed = InstalledPackage {
+            scope,
+            architecture,
+            installer_type,
+            ..installed("1.0")
+        };
+
+        assert!(matches!(
+            installed.upgrade(&[manifest("1.2")], UpgradeOptions::default()),
+            UpgradeDecision::NoApplicableInstaller(_)
+        ));
+    }
+}
@@ -0,0 +1,352 @@
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+use core::fmt;
+
+use thiserror::Error;
+
+use crate::{PackageVersion, Version, installer::InstallerManifest};
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum VersionMappingError {
+    #[error(
+        "DisplayVersion range of version {version} overlaps DisplayVersion range of version {other}"
+    )]
+    Overlap {
+        version: Box<PackageVersion>,
+        other: Box<PackageVersion>,
+    },
+    #[error(
+        "Version {version} has no DisplayVersion and is within DisplayVersion range of version {other}"
+    )]
+    Ambiguous {
+        version: Box<PackageVersion>,
+        other: Box<PackageVersion>,
+    },
+}
+
+/// The inclusive range between the lowest and highest `DisplayVersion` of a package version.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DisplayVersionRange {
+    pub min: Version,
+    pub max: Version,
+}
+
+impl DisplayVersionRange {
+    /// Returns `true` if the version is within this range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::{Version, installed::DisplayVersionRange};
+    ///
+    /// let range = DisplayVersionRange {
+    ///     min: Version::new("24.1.0.1234"),
+    ///     max: Version::new("24.1.0.1240"),
+    /// };
+    ///
+    /// assert!(range.contains(&Version::new("24.1.0.1236")));
+    /// assert!(!range.contains(&Version::new("24.1")));
+    /// ```
+    #[must_use]
+    pub fn contains(&self, version: &Version) -> bool {
+        (&self.min..=&self.max).contains(&version)
+    }
+
+    /// Returns `true` if any version is within both this range and the other range.
+    #[must_use]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.min <= other.max && other.min <= self.max
+    }
+}
+
+impl fmt::Display for DisplayVersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}]", self.min, self.max)
+    }
+}
+
+/// Maps an installed `DisplayVersion` back to the version of the package that installed it, in the
+/// same way as the Windows Package Manager.
+///
+/// Each package version with Apps and Features entries covers the range between the lowest and
+/// highest [`display_version`] of those entries. A package version without any display versions
+/// is only matched by a display version equal to the package version.
+///
+/// [`display_version`]: crate::installer::AppsAndFeaturesEntry::display_version
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct VersionMapping {
+    ranges: BTreeMap<PackageVersion, Option<DisplayVersionRange>>,
+}
+
+impl VersionMapping {
+    /// Builds the mapping from the installer manifests of every version of a package.
+    ///
+    /// The display versions of the root and installer-level Apps and Features entries are all
+    /// used. Manifests with the same package version are merged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the display version ranges of two package versions overlap, or if a
+    /// package version without display versions is within the range of another package version.
+    pub fn new<'manifest, I>(installer_manifests: I) -> Result<Self, VersionMappingError>
+    where
+        I: IntoIterator<Item = &'manifest InstallerManifest>,
+    {
+        Self::from_display_versions(installer_manifests.into_iter().map(|manifest| {
+            (
+                manifest.package_version.clone(),
+                manifest
+                    .apps_and_features_entries
+                    .iter()
+                    .chain(
+                        manifest
+                            .installers
+                            .iter()
+                            .flat_map(|installer| &installer.apps_and_features_entries),
+                    )
+                    .filter_map(|entry| entry.display_version())
+                    .cloned()
+                    .collect::<Vec<_>>(),
+            )
+        }))
+    }
+
+    /// Builds the mapping from package versions and their display versions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the display version ranges of two package versions overlap, or if a
+    /// package version without display versions is within the range of another package version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::{PackageVersion, Version, installed::VersionMapping};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mapping = VersionMapping::from_display_versions([
+    ///     ("24.1".parse()?, vec![Version::new("24.1.0.1234")]),
+    ///     ("24.2".parse()?, vec![Version::new("24.2.0.1500"), Version::new("24.2.0.1502")]),
+    /// ])?;
+    ///
+    /// assert_eq!(
+    ///     mapping.map(&Version::new("24.2.0.1501")),
+    ///     Some(&"24.2".parse::<PackageVersion>()?)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_display_versions<I, V>(versions: I) -> Result<Self, VersionMappingError>
+    where
+        I: IntoIterator<Item = (PackageVersion, V)>,
+        V: IntoIterator<Item = Version>,
+    {
+        let mut ranges = BTreeMap::<PackageVersion, Option<DisplayVersionRange>>::new();
+        for (package_version, display_versions) in versions {
+            let range = ranges.entry(package_version).or_default();
+            for display_version in display_versions {
+                if display_version.is_unknown() {
+                    continue;
+                }
+                match range {
+                    Some(range) if display_version < range.min => range.min = display_version,
+                    Some(range) if display_version > range.max => range.max = display_version,
+                    Some(_) => {}
+                    None => {
+                        *range = Some(DisplayVersionRange {
+                            min: display_version.clone(),
+                            max: display_version,
+                        });
+                    }
+                }
+            }
+        }
+
+        let mapping = Self { ranges };
+        mapping.validate()?;
+        Ok(mapping)
+    }
+
+    fn validate(&self) -> Result<(), VersionMappingError> {
+        for (index, (version, range)) in self.ranges.iter().enumerate() {
+            for (other, other_range) in self.ranges.iter().skip(index + 1) {
+                match (range, other_range) {
+                    (Some(range), Some(other_range)) if range.overlaps(other_range) => {
+                        return Err(VersionMappingError::Overlap {
+                            version: Box::new(version.clone()),
+                            other: Box::new(other.clone()),
+                        });
+                    }
+                    (None, Some(range)) if range.contains(version.inner()) => {
+                        return Err(VersionMappingError::Ambiguous {
+                            version: Box::new(version.clone()),
+                            other: Box::new(other.clone()),
+                        });
+                    }
+                    (Some(range), None) if range.contains(other.inner()) => {
+                        return Err(VersionMappingError::Ambiguous {
+                            version: Box::new(other.clone()),
+                            other: Box::new(version.clone()),
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the display version range of a package version, or `None` if the package version
+    /// has no display versions or is not part of the mapping.
+    #[must_use]
+    pub fn range(&self, package_version: &PackageVersion) -> Option<&DisplayVersionRange> {
+        self.ranges.get(package_version)?.as_ref()
+    }
+
+    /// Returns the package version that an installed display version maps to.
+    ///
+    /// The display version is first matched against the display version range of each package
+    /// version, and then against the package versions without display versions. Returns `None` if
+    /// the display version falls outside every range.
+    #[must_use]
+    pub fn map(&self, display_version: &Version) -> Option<&PackageVersion> {
+        self.ranges
+            .iter()
+            .find(|(_, range)| {
+                range
+                    .as_ref()
+                    .is_some_and(|range| range.contains(display_version))
+            })
+            .or_else(|| {
+                self.ranges
+                    .iter()
+                    .find(|(version, range)| range.is_none() && *version == display_version)
+            })
+            .map(|(version, _)| version)
+    }
+
+    /// Returns an iterator over the package versions in the mapping and their display version
+    /// ranges, in ascending order of package version.
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (&PackageVersion, Option<&DisplayVersionRange>)> + '_ {
+        self.ranges
+            .iter()
+            .map(|(version, range)| (version, range.as_ref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use rstest::rstest;
+
+    use super::{VersionMapping, VersionMappingError};
+    use crate::{
+        PackageVersion, Version,
+        installer::{AppsAndFeaturesEntry, Installer, InstallerManifest},
+    };
+
+    fn manifest(version: &str, display_versions: &[&str]) -> InstallerManifest {
+        InstallerManifest {
+            package_version: version.parse().unwrap(),
+            installers: display_versions
+                .iter()
+                .map(|display_version| Installer {
+                    apps_and_features_entries: AppsAndFeaturesEntry::builder()
+                        .display_version(*display_version)
+                        .build()
+                        .into(),
+                    ..Installer::default()
+                })
+                .collect(),
+            ..InstallerManifest::default()
+        }
+    }
+
+    fn mapping() -> VersionMapping {
+        VersionMapping::new(&[
+            manifest("24.1", &["24.1.0.1234"]),
+            manifest("24.2", &["24.2.0.1500", "24.2.0.1502"]),
+            manifest("25.0", &[]),
+        ])
+        .unwrap()
+    }
+
+    #[rstest]
+    #[case("24.1.0.1234", Some("24.1"))]
+    #[case("24.2.0.1500", Some("24.2"))]
+    #[case("24.2.0.1501", Some("24.2"))]
+    #[case("24.2.0.1502", Some("24.2"))]
+    #[case("25.0.0.0", Some("25.0"))]
+    #[case("24.1", None)]
+    #[case("24.2.0.1503", None)]
+    fn map(#[case] display_version: &str, #[case] expected: Option<&str>) {
+        assert_eq!(
+            mapping()
+                .map(&Version::new(display_version))
+                .map(PackageVersion::as_str),
+            expected
+        );
+    }
+
+    #[test]
+    fn range() {
+        let mapping = mapping();
+
+        assert_eq!(
+            mapping
+                .range(&"24.2".parse().unwrap())
+                .map(ToString::to_string)
+                .as_deref(),
+            Some("[24.2.0.1500, 24.2.0.1502]")
+        );
+        assert_eq!(mapping.range(&"25.0".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn overlap() {
+        let error = VersionMapping::new(&[
+            manifest("1.0", &["1.0.0", "1.5.0"]),
+            manifest("1.1", &["1.1.0"]),
+        ])
+        .unwrap_err();
+
+        assert!(matches!(
+            error,
+            VersionMappingError::Overlap { version, other, .. }
+                if version.as_str() == "1.0" && other.as_str() == "1.1"
+        ));
+    }
+
+    #[test]
+    fn ambiguous() {
+        let error =
+            VersionMapping::new(&[manifest("1.0", &["0.9.0", "1.1.0"]), manifest("1.1", &[])])
+                .unwrap_err();
+
+        assert!(matches!(
+            error,
+            VersionMappingError::Ambiguous { version, other, .. }
+                if version.as_str() == "1.1" && other.as_str() == "1.0"
+        ));
+    }
+
+    #[test]
+    fn same_version_is_merged() {
+        let mapping = VersionMapping::new(&[
+            manifest("1.0", &["1.0.1"]),
+            manifest("1.0", &["1.0.3"]),
+            manifest("1.1", &["1.1.0"]),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            mapping
+                .map(&Version::new("1.0.2"))
+                .map(PackageVersion::as_str),
+            Some("1.0")
+        );
+        assert_eq!(mapping.iter().count(), 2);
+    }
+}
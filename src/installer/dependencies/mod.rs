@@ -1,8 +1,12 @@
 use alloc::{collections::BTreeSet, string::String};
 
 pub use package::PackageDependencies;
+pub use resolver::{
+    DependencyChain, DependencyError, DependencyResolver, Requirement, Resolution, ResolvedPackage,
+};
 
 mod package;
+mod resolver;
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -1,4 +1,4 @@
-use crate::shared::{PackageIdentifier, PackageVersion};
+use crate::shared::{PackageIdentifier, PackageVersion, VersionRange};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -30,4 +30,15 @@ impl PackageDependencies {
             minimum_version: Some(minimum_version),
         }
     }
+
+    /// Returns the range of versions that satisfy this dependency, which is every version at or
+    /// above the minimum version.
+    #[must_use]
+    pub fn version_range(&self) -> VersionRange {
+        self.minimum_version
+            .as_ref()
+            .map_or_else(VersionRange::any, |version| {
+                VersionRange::at_least(version.inner().clone())
+            })
+    }
 }
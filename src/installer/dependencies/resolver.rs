@@ -0,0 +1,419 @@
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
+use core::fmt;
+
+use thiserror::Error;
+
+use super::{Dependencies, PackageDependencies};
+use crate::{PackageIdentifier, PackageVersion, installer::InstallerManifest};
+
+/// A path of packages from a requested package to a dependency, such as `A -> B -> C`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct DependencyChain(pub Vec<PackageIdentifier>);
+
+impl fmt::Display for DependencyChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, package_identifier) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" -> ")?;
+            }
+            package_identifier.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum DependencyError {
+    #[error("Dependency cycle: {_0}")]
+    Cycle(DependencyChain),
+    #[error("Dependency is missing: {_0}")]
+    Missing(DependencyChain),
+    #[error("No version satisfies minimum version {minimum_version} of dependency: {chain}")]
+    Unsatisfiable {
+        chain: DependencyChain,
+        minimum_version: Box<PackageVersion>,
+    },
+}
+
+/// A dependency that is not a package and must be satisfied outside of the resolver.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Requirement {
+    /// A Windows feature, from [`Dependencies::windows_features`].
+    WindowsFeature(String),
+    /// A Windows library, from [`Dependencies::windows_libraries`].
+    WindowsLibrary(String),
+    /// A package from another source, from [`Dependencies::external`].
+    External(String),
+}
+
+/// A package version chosen by the resolver.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ResolvedPackage {
+    pub package_identifier: PackageIdentifier,
+    pub package_version: PackageVersion,
+}
+
+/// The result of resolving the dependencies of a set of packages.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Resolution {
+    /// The chosen packages in install order, where every package comes after its dependencies.
+    pub packages: Vec<ResolvedPackage>,
+
+    /// The dependencies of the chosen packages that are not packages.
+    pub requirements: BTreeSet<Requirement>,
+}
+
+/// Resolves the package dependencies of packages from a set of loaded manifests.
+///
+/// The newest version of each package is chosen, as long as it satisfies the minimum version of
+/// every package that depends on it. The dependencies of a version are those of the manifest and
+/// of every installer in it.
+#[derive(Clone, Debug, Default)]
+pub struct DependencyResolver {
+    packages: BTreeMap<PackageIdentifier, BTreeMap<PackageVersion, Dependencies>>,
+}
+
+impl DependencyResolver {
+    /// Creates a new, empty `DependencyResolver`.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            packages: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a version of a package to the resolver.
+    pub fn add(&mut self, installer_manifest: &InstallerManifest) {
+        let dependencies = self
+            .packages
+            .entry(installer_manifest.package_identifier.clone())
+            .or_default()
+            .entry(installer_manifest.package_version.clone())
+            .or_default();
+
+        for source in core::iter::once(&installer_manifest.dependencies).chain(
+            installer_manifest
+                .installers
+                .iter()
+                .map(|installer| &installer.dependencies),
+        ) {
+            dependencies
+                .windows_features
+                .extend(source.windows_features.iter().cloned());
+            dependencies
+                .windows_libraries
+                .extend(source.windows_libraries.iter().cloned());
+            dependencies.package.extend(source.package.iter().cloned());
+            dependencies
+                .external
+                .extend(source.external.iter().cloned());
+        }
+    }
+
+    /// Resolves the requested packages and all of their dependencies.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` with the chain of dependencies from a requested package if the
+    /// dependencies form a cycle, a package is missing, or no version of a package satisfies a
+    /// minimum version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::installer::{
+    ///     DependencyResolver, InstallerManifest, PackageDependencies,
+    /// };
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut resolver = DependencyResolver::new();
+    ///
+    /// let mut app = InstallerManifest {
+    ///     package_identifier: "Contoso.App".parse()?,
+    ///     package_version: "1.0".parse()?,
+    ///     ..InstallerManifest::default()
+    /// };
+    /// app.dependencies
+    ///     .package
+    ///     .insert(PackageDependencies::new("Contoso.Runtime".parse()?));
+    /// resolver.add(&app);
+    /// resolver.add(&InstallerManifest {
+    ///     package_identifier: "Contoso.Runtime".parse()?,
+    ///     package_version: "8.0".parse()?,
+    ///     ..InstallerManifest::default()
+    /// });
+    ///
+    /// let resolution = resolver.resolve([&PackageDependencies::new("Contoso.App".parse()?)])?;
+    ///
+    /// assert_eq!(resolution.packages[0].package_identifier.as_str(), "Contoso.Runtime");
+    /// assert_eq!(resolution.packages[1].package_identifier.as_str(), "Contoso.App");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resolve<'dependency, I>(&self, packages: I) -> Result<Resolution, DependencyError>
+    where
+        I: IntoIterator<Item = &'dependency PackageDependencies>,
+    {
+        let mut walk = Walk {
+            resolver: self,
+            chosen: BTreeMap::new(),
+            stack: Vec::new(),
+            resolution: Resolution::default(),
+        };
+
+        for package in packages {
+            walk.visit(package)?;
+        }
+
+        Ok(walk.resolution)
+    }
+}
+
+/// The state of a depth-first walk of the dependency graph.
+struct Walk<'resolver> {
+    resolver: &'resolver DependencyResolver,
+    chosen: BTreeMap<&'resolver PackageIdentifier, &'resolver PackageVersion>,
+    stack: Vec<&'resolver PackageIdentifier>,
+    resolution: Resolution,
+}
+
+impl<'resolver> Walk<'resolver> {
+    fn chain(&self, package_identifier: &PackageIdentifier) -> DependencyChain {
+        DependencyChain(
+            self.stack
+                .iter()
+                .copied()
+                .chain(core::iter::once(package_identifier))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    fn visit(&mut self, dependency: &PackageDependencies) -> Result<(), DependencyError> {
+        let package_identifier = &dependency.package_identifier;
+        let unsatisfiable =
+            |walk: &Self, minimum_version: &PackageVersion| DependencyError::Unsatisfiable {
+                chain: walk.chain(package_identifier),
+                minimum_version: Box::new(minimum_version.clone()),
+            };
+
+        if let Some(position) = self.stack.iter().position(|id| *id == package_identifier) {
+            return Err(DependencyError::Cycle(DependencyChain(
+                self.stack[position..]
+                    .iter()
+                    .copied()
+                    .chain(core::iter::once(package_identifier))
+                    .cloned()
+                    .collect(),
+            )));
+        }
+
+        if let Some(&version) = self.chosen.get(package_identifier) {
+            return match &dependency.minimum_version {
+                Some(minimum_version) if version < minimum_version => {
+                    Err(unsatisfiable(self, minimum_version))
+                }
+                _ => Ok(()),
+            };
+        }
+
+        let (package_identifier, versions) = self
+            .resolver
+            .packages
+            .get_key_value(package_identifier)
+            .ok_or_else(|| DependencyError::Missing(self.chain(package_identifier)))?;
+
+        let range = dependency.version_range();
+        let (version, dependencies) = versions
+            .iter()
+            .next_back()
+            .filter(|(version, _)| range.contains(version.inner()))
+            .ok_or_else(|| {
+                dependency.minimum_version.as_ref().map_or_else(
+                    || DependencyError::Missing(self.chain(package_identifier)),
+                    |minimum_version| unsatisfiable(self, minimum_version),
+                )
+            })?;
+
+        self.chosen.insert(package_identifier, version);
+        self.stack.push(package_identifier);
+        for dependency in &dependencies.package {
+            self.visit(dependency)?;
+        }
+        self.stack.pop();
+
+        let requirements = &mut self.resolution.requirements;
+        requirements.extend(
+            dependencies
+                .windows_features
+                .iter()
+                .cloned()
+                .map(Requirement::WindowsFeature),
+        );
+        requirements.extend(
+            dependencies
+                .windows_libraries
+                .iter()
+                .cloned()
+                .map(Requirement::WindowsLibrary),
+        );
+        requirements.extend(
+            dependencies
+                .external
+                .iter()
+                .cloned()
+                .map(Requirement::External),
+        );
+
+        self.resolution.packages.push(ResolvedPackage {
+            package_identifier: package_identifier.clone(),
+            package_version: version.clone(),
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec::Vec};
+
+    use super::{DependencyError, DependencyResolver, Requirement};
+    use crate::installer::{InstallerManifest, PackageDependencies};
+
+    fn dependency(dependency: &str) -> PackageDependencies {
+        match dependency.split_once(">=") {
+            Some((identifier, minimum_version)) => PackageDependencies::new_with_min_version(
+                identifier.parse().unwrap(),
+                minimum_version.parse().unwrap(),
+            ),
+            None => PackageDependencies::new(dependency.parse().unwrap()),
+        }
+    }
+
+    fn resolver(packages: &[(&str, &str, &[&str])]) -> DependencyResolver {
+        let mut resolver = DependencyResolver::new();
+        for (identifier, version, dependencies) in packages {
+            let mut manifest = InstallerManifest {
+                package_identifier: identifier.parse().unwrap(),
+                package_version: version.parse().unwrap(),
+                ..InstallerManifest::default()
+            };
+            manifest
+                .dependencies
+                .package
+                .extend(dependencies.iter().copied().map(dependency));
+            resolver.add(&manifest);
+        }
+        resolver
+    }
+
+    #[test]
+    fn install_order() {
+        let mut resolver = resolver(&[
+            (
+                "Contoso.App",
+                "1.0",
+                &["Contoso.Runtime>=8.0", "Contoso.Library"],
+            ),
+            ("Contoso.Library", "2.0", &["Contoso.Runtime>=7.0"]),
+            ("Contoso.Runtime", "7.0", &[]),
+            ("Contoso.Runtime", "8.0.1", &[]),
+        ]);
+        let mut app = InstallerManifest {
+            package_identifier: "Contoso.App".parse().unwrap(),
+            package_version: "1.0".parse().unwrap(),
+            ..InstallerManifest::default()
+        };
+        app.dependencies
+            .windows_features
+            .insert("NetFx3".to_string());
+        app.dependencies
+            .external
+            .insert("Microsoft.VCRedist.2015+.x64".to_string());
+        resolver.add(&app);
+
+        let resolution = resolver.resolve([&dependency("Contoso.App")]).unwrap();
+
+        assert_eq!(
+            resolution
+                .packages
+                .iter()
+                .map(|package| (
+                    package.package_identifier.as_str(),
+                    package.package_version.as_str()
+                ))
+                .collect::<Vec<_>>(),
+            [
+                ("Contoso.Runtime", "8.0.1"),
+                ("Contoso.Library", "2.0"),
+                ("Contoso.App", "1.0")
+            ]
+        );
+        assert_eq!(
+            resolution.requirements.into_iter().collect::<Vec<_>>(),
+            [
+                Requirement::WindowsFeature("NetFx3".to_string()),
+                Requirement::External("Microsoft.VCRedist.2015+.x64".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn cycle() {
+        let error = resolver(&[
+            ("Contoso.App", "1.0", &["Contoso.Library"]),
+            ("Contoso.Library", "1.0", &["Contoso.Runtime"]),
+            ("Contoso.Runtime", "1.0", &["Contoso.Library"]),
+        ])
+        .resolve([&dependency("Contoso.App")])
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Dependency cycle: Contoso.Library -> Contoso.Runtime -> Contoso.Library"
+        );
+    }
+
+    #[test]
+    fn missing() {
+        let error = resolver(&[
+            ("Contoso.App", "1.0", &["Contoso.Library"]),
+            ("Contoso.Library", "1.0", &["Contoso.Runtime"]),
+        ])
+        .resolve([&dependency("Contoso.App")])
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Dependency is missing: Contoso.App -> Contoso.Library -> Contoso.Runtime"
+        );
+    }
+
+    #[test]
+    fn unsatisfiable() {
+        let error = resolver(&[
+            (
+                "Contoso.App",
+                "1.0",
+                &["Contoso.Library", "Contoso.Runtime>=9.0"],
+            ),
+            ("Contoso.Library", "1.0", &["Contoso.Runtime>=7.0"]),
+            ("Contoso.Runtime", "8.0", &[]),
+        ])
+        .resolve([&dependency("Contoso.App")])
+        .unwrap_err();
+
+        assert!(matches!(
+            error,
+            DependencyError::Unsatisfiable { chain, minimum_version }
+                if chain.to_string() == "Contoso.App -> Contoso.Runtime" && minimum_version.as_str() == "9.0"
+        ));
+    }
+}
@@ -0,0 +1,276 @@
+use super::{Installer, InstallerType, RepairBehavior, ReturnResponse};
+use crate::shared::url::DecodedUrl;
+
+/// The operation an installer was run for.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum InstallContext {
+    #[default]
+    Install,
+    Upgrade,
+    Repair,
+}
+
+/// The result of an installer run, as determined from its exit code.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum InstallOutcome {
+    /// The installer succeeded.
+    Success,
+
+    /// The installer succeeded, but a reboot is required to finish or has been initiated.
+    SuccessRebootRequired,
+
+    /// The installer failed for a reason that is likely to be resolved by running it again
+    /// later, such as another installation being in progress.
+    Retryable {
+        return_response: ReturnResponse,
+        return_response_url: Option<DecodedUrl>,
+    },
+
+    /// The installer failed.
+    ///
+    /// The return response is `None` if the exit code is not known.
+    Failure {
+        return_response: Option<ReturnResponse>,
+        return_response_url: Option<DecodedUrl>,
+    },
+}
+
+impl InstallOutcome {
+    /// Returns `true` if the installer succeeded, regardless of whether a reboot is required.
+    #[must_use]
+    #[inline]
+    pub const fn is_success(&self) -> bool {
+        matches!(self, Self::Success | Self::SuccessRebootRequired)
+    }
+
+    fn from_response(
+        return_response: ReturnResponse,
+        return_response_url: Option<DecodedUrl>,
+    ) -> Self {
+        match return_response {
+            ReturnResponse::RebootRequiredToFinish | ReturnResponse::RebootInitiated => {
+                Self::SuccessRebootRequired
+            }
+            ReturnResponse::PackageInUse
+            | ReturnResponse::PackageInUseByApplication
+            | ReturnResponse::InstallInProgress
+            | ReturnResponse::FileInUse
+            | ReturnResponse::NoNetwork => Self::Retryable {
+                return_response,
+                return_response_url,
+            },
+            _ => Self::Failure {
+                return_response: Some(return_response),
+                return_response_url,
+            },
+        }
+    }
+}
+
+impl Installer {
+    /// Interprets the exit code of a run of this installer in the same way as the Windows
+    /// Package Manager.
+    ///
+    /// An exit code of `0` and any of the installer's [success codes] are successful. Otherwise,
+    /// the exit code is looked up in the installer's [expected return codes] and then in the
+    /// return codes that the Windows Package Manager knows for the installer type, such as `3010`
+    /// for an MSI that requires a reboot.
+    ///
+    /// When repairing with the [`Modify`] or [`Uninstaller`] repair behavior, the installer itself
+    /// is not run, so only the known return codes of the installer type are used.
+    ///
+    /// Values from the root of a manifest should be merged into the installer with
+    /// [`merge_with`] beforehand. The exit code is an `i32` like [`ExitStatus::code`], so a
+    /// `DWORD` exit code should be cast with `as i32`.
+    ///
+    /// [success codes]: Installer::success_codes
+    /// [expected return codes]: Installer::expected_return_codes
+    /// [`Modify`]: RepairBehavior::Modify
+    /// [`Uninstaller`]: RepairBehavior::Uninstaller
+    /// [`merge_with`]: Installer::merge_with
+    /// [`ExitStatus::code`]: https://doc.rust-lang.org/std/process/struct.ExitStatus.html#method.code
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::installer::{
+    ///     InstallContext, InstallOutcome, Installer, InstallerType, ReturnResponse,
+    /// };
+    ///
+    /// let installer = Installer {
+    ///     r#type: Some(InstallerType::Msi),
+    ///     ..Installer::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     installer.exit_code_outcome(InstallContext::Install, 3010),
+    ///     InstallOutcome::SuccessRebootRequired
+    /// );
+    /// assert!(matches!(
+    ///     installer.exit_code_outcome(InstallContext::Install, 1618),
+    ///     InstallOutcome::Retryable {
+    ///         return_response: ReturnResponse::InstallInProgress,
+    ///         ..
+    ///     }
+    /// ));
+    /// ```
+    #[must_use]
+    pub fn exit_code_outcome(&self, context: InstallContext, exit_code: i32) -> InstallOutcome {
+        if exit_code == 0 {
+            return InstallOutcome::Success;
+        }
+
+        let bits = exit_code.cast_unsigned();
+        let runs_installer = context != InstallContext::Repair
+            || !matches!(
+                self.repair_behavior,
+                Some(RepairBehavior::Modify | RepairBehavior::Uninstaller)
+            );
+
+        if runs_installer {
+            if self.success_codes.iter().any(|code| code.to_bits() == bits) {
+                return InstallOutcome::Success;
+            }
+
+            if let Some(expected) = self.expected_return_codes.iter().find(|expected| {
+                expected
+                    .installer_return_code
+                    .is_some_and(|code| code.to_bits() == bits)
+            }) {
+                return InstallOutcome::from_response(
+                    expected.return_response,
+                    expected.return_response_url.clone(),
+                );
+            }
+        }
+
+        self.effective_type()
+            .map_or(&[][..], InstallerType::known_return_codes)
+            .iter()
+            .find(|known| known.implicit && known.return_code.to_bits() == bits)
+            .map_or(
+                InstallOutcome::Failure {
+                    return_response: None,
+                    return_response_url: None,
+                },
+                |known| InstallOutcome::from_response(known.return_response, None),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{InstallContext, InstallOutcome};
+    use crate::installer::{
+        ExpectedReturnCodes, Installer, InstallerReturnCode, InstallerType, RepairBehavior,
+        ReturnResponse,
+    };
+
+    fn installer(installer_type: InstallerType) -> Installer {
+        Installer {
+            r#type: Some(installer_type),
+            success_codes: [InstallerReturnCode::from_u32(42).unwrap()].into(),
+            expected_return_codes: [
+                ExpectedReturnCodes {
+                    installer_return_code: InstallerReturnCode::from_i32(-1),
+                    return_response: ReturnResponse::FileInUse,
+                    return_response_url: None,
+                },
+                ExpectedReturnCodes {
+                    installer_return_code: InstallerReturnCode::from_u32(1602),
+                    return_response: ReturnResponse::Custom,
+                    return_response_url: Some("https://example.com/1602".parse().unwrap()),
+                },
+            ]
+            .into(),
+            ..Installer::default()
+        }
+    }
+
+    #[rstest]
+    #[case(InstallerType::Msi, 0, InstallOutcome::Success)]
+    #[case(InstallerType::Msi, 42, InstallOutcome::Success)]
+    #[case(InstallerType::Wix, 3010, InstallOutcome::SuccessRebootRequired)]
+    #[case(InstallerType::Burn, 1641, InstallOutcome::SuccessRebootRequired)]
+    #[case(
+        InstallerType::Msi,
+        1618,
+        InstallOutcome::Retryable {
+            return_response: ReturnResponse::InstallInProgress,
+            return_response_url: None,
+        }
+    )]
+    #[case(
+        InstallerType::Exe,
+        -1,
+        InstallOutcome::Retryable {
+            return_response: ReturnResponse::FileInUse,
+            return_response_url: None,
+        }
+    )]
+    #[case(
+        InstallerType::Msi,
+        1638,
+        InstallOutcome::Failure {
+            return_response: Some(ReturnResponse::AlreadyInstalled),
+            return_response_url: None,
+        }
+    )]
+    #[case(
+        InstallerType::Exe,
+        3010,
+        InstallOutcome::Failure {
+            return_response: None,
+            return_response_url: None,
+        }
+    )]
+    fn outcome(
+        #[case] installer_type: InstallerType,
+        #[case] exit_code: i32,
+        #[case] expected: InstallOutcome,
+    ) {
+        assert_eq!(
+            installer(installer_type).exit_code_outcome(InstallContext::Install, exit_code),
+            expected
+        );
+    }
+
+    #[test]
+    fn expected_return_code_overrides_known() {
+        assert!(matches!(
+            installer(InstallerType::Msi).exit_code_outcome(InstallContext::Upgrade, 1602),
+            InstallOutcome::Failure {
+                return_response: Some(ReturnResponse::Custom),
+                return_response_url: Some(_),
+            }
+        ));
+    }
+
+    #[test]
+    fn repair_without_installer() {
+        let installer = Installer {
+            repair_behavior: Some(RepairBehavior::Modify),
+            ..installer(InstallerType::Msi)
+        };
+
+        assert!(
+            !installer
+                .exit_code_outcome(InstallContext::Repair, 42)
+                .is_success()
+        );
+        assert_eq!(
+            installer.exit_code_outcome(InstallContext::Repair, 1602),
+            InstallOutcome::Failure {
+                return_response: Some(ReturnResponse::CancelledByUser),
+                return_response_url: None,
+            }
+        );
+        assert!(
+            installer
+                .exit_code_outcome(InstallContext::Install, 42)
+                .is_success()
+        );
+    }
+}
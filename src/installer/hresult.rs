@@ -0,0 +1,1350 @@
+use core::{fmt, num::ParseIntError, str::FromStr};
+
+use super::InstallerReturnCode;
+
+/// A 32-bit `HRESULT` error code, as returned by the Windows Package Manager and by MSIX
+/// deployment.
+///
+/// An `HRESULT` is made up of a [severity], a [facility] that identifies the source of the error,
+/// and a [code] that is specific to the facility. It is displayed in hexadecimal, such as
+/// `0x8A150011`, and can be converted to the signed decimal form that appears in logs with
+/// [`to_i32`].
+///
+/// [severity]: HResult::severity
+/// [facility]: HResult::facility
+/// [code]: HResult::code
+/// [`to_i32`]: HResult::to_i32
+///
+/// # Examples
+///
+/// ```
+/// use winget_types::installer::{Facility, HResult, Severity};
+///
+/// # fn main() -> Result<(), core::num::ParseIntError> {
+/// let hresult = "0x8A150011".parse::<HResult>()?;
+///
+/// assert_eq!(hresult.severity(), Severity::Failure);
+/// assert_eq!(hresult.facility(), Facility::AppInstaller);
+/// assert_eq!(hresult.code(), 0x11);
+/// assert_eq!(hresult.to_i32(), -1_978_335_215);
+/// assert_eq!(
+///     hresult.known().map(|known| known.name),
+///     Some("APPINSTALLER_CLI_ERROR_INSTALLER_HASH_MISMATCH")
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct HResult(u32);
+
+/// Whether an [`HResult`] represents success or failure.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Severity {
+    Success,
+    Failure,
+}
+
+/// The source of an [`HResult`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Facility {
+    /// `FACILITY_NULL`, used for broadly applicable codes such as `E_FAIL`.
+    Null,
+    /// `FACILITY_RPC`.
+    Rpc,
+    /// `FACILITY_DISPATCH`.
+    Dispatch,
+    /// `FACILITY_STORAGE`.
+    Storage,
+    /// `FACILITY_ITF`, used for interface-specific codes.
+    Interface,
+    /// `FACILITY_WIN32`, used for Win32 error codes, including Windows Installer and MSIX
+    /// deployment errors.
+    Win32,
+    /// `FACILITY_WINDOWS`.
+    Windows,
+    /// `FACILITY_SECURITY`.
+    Security,
+    /// `FACILITY_CERT`, used for certificate and trust errors such as those from signature
+    /// verification.
+    Certificate,
+    /// The facility of the Windows Package Manager, `0xA15`.
+    AppInstaller,
+    /// Any other facility.
+    Other(u16),
+}
+
+impl Facility {
+    const NULL: u16 = 0;
+    const RPC: u16 = 1;
+    const DISPATCH: u16 = 2;
+    const STORAGE: u16 = 3;
+    const INTERFACE: u16 = 4;
+    const WIN32: u16 = 7;
+    const WINDOWS: u16 = 8;
+    const SECURITY: u16 = 9;
+    const CERTIFICATE: u16 = 11;
+    const APP_INSTALLER: u16 = 0xA15;
+
+    /// Returns the numeric value of the facility.
+    #[must_use]
+    pub const fn to_u16(self) -> u16 {
+        match self {
+            Self::Null => Self::NULL,
+            Self::Rpc => Self::RPC,
+            Self::Dispatch => Self::DISPATCH,
+            Self::Storage => Self::STORAGE,
+            Self::Interface => Self::INTERFACE,
+            Self::Win32 => Self::WIN32,
+            Self::Windows => Self::WINDOWS,
+            Self::Security => Self::SECURITY,
+            Self::Certificate => Self::CERTIFICATE,
+            Self::AppInstaller => Self::APP_INSTALLER,
+            Self::Other(facility) => facility,
+        }
+    }
+
+    const fn from_u16(facility: u16) -> Self {
+        match facility {
+            Self::NULL => Self::Null,
+            Self::RPC => Self::Rpc,
+            Self::DISPATCH => Self::Dispatch,
+            Self::STORAGE => Self::Storage,
+            Self::INTERFACE => Self::Interface,
+            Self::WIN32 => Self::Win32,
+            Self::WINDOWS => Self::Windows,
+            Self::SECURITY => Self::Security,
+            Self::CERTIFICATE => Self::Certificate,
+            Self::APP_INSTALLER => Self::AppInstaller,
+            facility => Self::Other(facility),
+        }
+    }
+}
+
+impl HResult {
+    const SEVERITY_BIT: u32 = 1 << 31;
+
+    /// Creates an `HResult` from its bits.
+    #[must_use]
+    #[inline]
+    pub const fn new(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Creates an `HResult` from a Win32 error code, in the same way as `HRESULT_FROM_WIN32`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::installer::HResult;
+    ///
+    /// // ERROR_INSTALL_FAILURE
+    /// assert_eq!(HResult::from_win32(1603), HResult::new(0x8007_0643));
+    /// assert_eq!(HResult::from_win32(0), HResult::new(0));
+    /// ```
+    #[must_use]
+    pub const fn from_win32(code: u32) -> Self {
+        if code.cast_signed() <= 0 {
+            Self(code)
+        } else {
+            Self(Self::SEVERITY_BIT | ((Facility::WIN32 as u32) << 16) | (code & 0xFFFF))
+        }
+    }
+
+    /// Returns the bits of the `HResult`.
+    #[must_use]
+    #[inline]
+    pub const fn to_bits(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the `HResult` as a signed decimal, as it appears in many logs.
+    #[must_use]
+    #[inline]
+    pub const fn to_i32(self) -> i32 {
+        self.0.cast_signed()
+    }
+
+    /// Returns the severity of the `HResult`.
+    #[must_use]
+    pub const fn severity(self) -> Severity {
+        if self.is_failure() {
+            Severity::Failure
+        } else {
+            Severity::Success
+        }
+    }
+
+    /// Returns `true` if the severity of the `HResult` is a failure.
+    #[must_use]
+    #[inline]
+    pub const fn is_failure(self) -> bool {
+        self.0 & Self::SEVERITY_BIT != 0
+    }
+
+    /// Returns the facility of the `HResult`, in the same way as `HRESULT_FACILITY`.
+    #[must_use]
+    pub const fn facility(self) -> Facility {
+        Facility::from_u16(((self.0 >> 16) & 0x1FFF) as u16)
+    }
+
+    /// Returns the facility-specific code of the `HResult`.
+    #[must_use]
+    #[inline]
+    pub const fn code(self) -> u16 {
+        (self.0 & 0xFFFF) as u16
+    }
+
+    /// Returns the symbolic name and description of the `HResult` if it is well known.
+    ///
+    /// The Windows Package Manager's general errors from `0x8A150001` to `0x8A150086` and its
+    /// installer errors from `0x8A150101` to `0x8A150113` are known, along with common Windows,
+    /// Windows Installer and MSIX deployment errors. Configuration errors (`0x8A15C0xx`) are not.
+    ///
+    /// A Win32 error code that is not an `HResult`, such as the exit code `1603` of an MSI, is
+    /// looked up as if converted with [`from_win32`].
+    ///
+    /// [`from_win32`]: HResult::from_win32
+    #[must_use]
+    pub fn known(self) -> Option<&'static KnownHResult> {
+        let hresult = if self.is_failure() {
+            self
+        } else {
+            Self::from_win32(self.0)
+        };
+
+        KNOWN_HRESULTS
+            .binary_search_by_key(&hresult, |known| known.hresult)
+            .ok()
+            .map(|index| &KNOWN_HRESULTS[index])
+    }
+}
+
+impl fmt::Display for HResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{:08X}", self.0)
+    }
+}
+
+impl FromStr for HResult {
+    type Err = ParseIntError;
+
+    /// Parses an `HResult` from hexadecimal prefixed with `0x`, or from signed or unsigned
+    /// decimal.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            return u32::from_str_radix(hex, 16).map(Self);
+        }
+
+        s.parse::<i32>()
+            .map(i32::cast_unsigned)
+            .or_else(|_| s.parse::<u32>())
+            .map(Self)
+    }
+}
+
+impl From<InstallerReturnCode> for HResult {
+    #[inline]
+    fn from(return_code: InstallerReturnCode) -> Self {
+        Self(return_code.to_bits())
+    }
+}
+
+impl From<HResult> for u32 {
+    #[inline]
+    fn from(hresult: HResult) -> Self {
+        hresult.0
+    }
+}
+
+impl InstallerReturnCode {
+    /// Returns the return code as an [`HResult`] if it has the severity bit of an `HResult` set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::installer::{HResult, InstallerReturnCode};
+    ///
+    /// # fn doctest() -> Option<()> {
+    /// let return_code = InstallerReturnCode::from_i32(-2_147_009_274)?;
+    /// assert_eq!(return_code.to_hresult(), Some(HResult::new(0x8007_3D06)));
+    ///
+    /// assert!(InstallerReturnCode::from_u32(1603)?.to_hresult().is_none());
+    /// # Some(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub const fn to_hresult(self) -> Option<HResult> {
+        let hresult = HResult::new(self.to_bits());
+        if hresult.is_failure() {
+            Some(hresult)
+        } else {
+            None
+        }
+    }
+}
+
+/// An [`HResult`] with a well-known meaning.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct KnownHResult {
+    /// The `HResult`.
+    pub hresult: HResult,
+
+    /// The symbolic name of the `HResult`, such as `E_ACCESSDENIED`.
+    pub name: &'static str,
+
+    /// A description of the `HResult`.
+    pub description: &'static str,
+}
+
+impl KnownHResult {
+    const fn new(hresult: u32, name: &'static str, description: &'static str) -> Self {
+        Self {
+            hresult: HResult::new(hresult),
+            name,
+            description,
+        }
+    }
+}
+
+/// Well-known `HResult`s, sorted by their bits.
+const KNOWN_HRESULTS: &[KnownHResult] = &[
+    KnownHResult::new(0x8000_4001, "E_NOTIMPL", "Not implemented"),
+    KnownHResult::new(0x8000_4002, "E_NOINTERFACE", "No such interface supported"),
+    KnownHResult::new(0x8000_4004, "E_ABORT", "Operation aborted"),
+    KnownHResult::new(0x8000_4005, "E_FAIL", "Unspecified error"),
+    KnownHResult::new(0x8000_FFFF, "E_UNEXPECTED", "Catastrophic failure"),
+    KnownHResult::new(
+        0x8007_0002,
+        "ERROR_FILE_NOT_FOUND",
+        "The system cannot find the file specified",
+    ),
+    KnownHResult::new(0x8007_0005, "E_ACCESSDENIED", "Access is denied"),
+    KnownHResult::new(
+        0x8007_000E,
+        "E_OUTOFMEMORY",
+        "Not enough memory resources are available to complete this operation",
+    ),
+    KnownHResult::new(0x8007_0057, "E_INVALIDARG", "The parameter is incorrect"),
+    KnownHResult::new(
+        0x8007_0070,
+        "ERROR_DISK_FULL",
+        "There is not enough space on the disk",
+    ),
+    KnownHResult::new(
+        0x8007_04C7,
+        "ERROR_CANCELLED",
+        "The operation was canceled by the user",
+    ),
+    KnownHResult::new(
+        0x8007_0641,
+        "ERROR_INSTALL_SERVICE_FAILURE",
+        "The Windows Installer service could not be accessed",
+    ),
+    KnownHResult::new(
+        0x8007_0642,
+        "ERROR_INSTALL_USEREXIT",
+        "User cancelled installation",
+    ),
+    KnownHResult::new(
+        0x8007_0643,
+        "ERROR_INSTALL_FAILURE",
+        "Fatal error during installation",
+    ),
+    KnownHResult::new(
+        0x8007_0652,
+        "ERROR_INSTALL_ALREADY_RUNNING",
+        "Another installation is already in progress",
+    ),
+    KnownHResult::new(
+        0x8007_0661,
+        "ERROR_INSTALL_PLATFORM_UNSUPPORTED",
+        "This installation package is not supported by this processor type",
+    ),
+    KnownHResult::new(
+        0x8007_0666,
+        "ERROR_PRODUCT_VERSION",
+        "Another version of this product is already installed",
+    ),
+    KnownHResult::new(
+        0x8007_0669,
+        "ERROR_SUCCESS_REBOOT_INITIATED",
+        "The requested operation is successful and a restart has been initiated",
+    ),
+    KnownHResult::new(
+        0x8007_0BC2,
+        "ERROR_SUCCESS_REBOOT_REQUIRED",
+        "The requested operation is successful but requires a restart to take effect",
+    ),
+    KnownHResult::new(
+        0x8007_2EE2,
+        "ERROR_INTERNET_TIMEOUT",
+        "The operation timed out",
+    ),
+    KnownHResult::new(
+        0x8007_2EE7,
+        "ERROR_INTERNET_NAME_NOT_RESOLVED",
+        "The server name or address could not be resolved",
+    ),
+    KnownHResult::new(
+        0x8007_2EFD,
+        "ERROR_INTERNET_CANNOT_CONNECT",
+        "A connection with the server could not be established",
+    ),
+    KnownHResult::new(
+        0x8007_3CF0,
+        "ERROR_INSTALL_OPEN_PACKAGE_FAILED",
+        "The package could not be opened",
+    ),
+    KnownHResult::new(
+        0x8007_3CF1,
+        "ERROR_INSTALL_PACKAGE_NOT_FOUND",
+        "The package was not found",
+    ),
+    KnownHResult::new(
+        0x8007_3CF2,
+        "ERROR_INSTALL_INVALID_PACKAGE",
+        "The package data is invalid",
+    ),
+    KnownHResult::new(
+        0x8007_3CF3,
+        "ERROR_INSTALL_RESOLVE_DEPENDENCY_FAILED",
+        "The package failed update, dependency or conflict validation",
+    ),
+    KnownHResult::new(
+        0x8007_3CF4,
+        "ERROR_INSTALL_OUT_OF_DISK_SPACE",
+        "There is not enough disk space to install the package",
+    ),
+    KnownHResult::new(
+        0x8007_3CF5,
+        "ERROR_INSTALL_NETWORK_FAILURE",
+        "There was a problem downloading the package",
+    ),
+    KnownHResult::new(
+        0x8007_3CF6,
+        "ERROR_INSTALL_REGISTRATION_FAILURE",
+        "The package could not be registered",
+    ),
+    KnownHResult::new(
+        0x8007_3CF7,
+        "ERROR_INSTALL_DEREGISTRATION_FAILURE",
+        "The package could not be unregistered",
+    ),
+    KnownHResult::new(
+        0x8007_3CF8,
+        "ERROR_INSTALL_CANCEL",
+        "The user cancelled the install request",
+    ),
+    KnownHResult::new(0x8007_3CF9, "ERROR_INSTALL_FAILED", "Install failed"),
+    KnownHResult::new(0x8007_3CFA, "ERROR_REMOVE_FAILED", "Removal failed"),
+    KnownHResult::new(
+        0x8007_3CFB,
+        "ERROR_PACKAGE_ALREADY_EXISTS",
+        "The package already exists",
+    ),
+    KnownHResult::new(
+        0x8007_3CFC,
+        "ERROR_NEEDS_REMEDIATION",
+        "The application cannot be started and should be reinstalled",
+    ),
+    KnownHResult::new(
+        0x8007_3CFD,
+        "ERROR_INSTALL_PREREQUISITE_FAILED",
+        "A prerequisite for the install could not be satisfied",
+    ),
+    KnownHResult::new(
+        0x8007_3CFE,
+        "ERROR_PACKAGE_REPOSITORY_CORRUPTED",
+        "The package repository is corrupted",
+    ),
+    KnownHResult::new(
+        0x8007_3CFF,
+        "ERROR_INSTALL_POLICY_FAILURE",
+        "Installing the package requires a developer license or sideloading to be enabled",
+    ),
+    KnownHResult::new(
+        0x8007_3D00,
+        "ERROR_PACKAGE_UPDATING",
+        "The application cannot be started because it is currently updating",
+    ),
+    KnownHResult::new(
+        0x8007_3D01,
+        "ERROR_DEPLOYMENT_BLOCKED_BY_POLICY",
+        "The package deployment operation is blocked by policy",
+    ),
+    KnownHResult::new(
+        0x8007_3D02,
+        "ERROR_PACKAGES_IN_USE",
+        "The package could not be installed because resources it modifies are currently in use",
+    ),
+    KnownHResult::new(
+        0x8007_3D05,
+        "ERROR_DELETING_EXISTING_APPLICATIONDATA_STORE_FAILED",
+        "The package could not be recovered because necessary data for recovery have been \
+         corrupted",
+    ),
+    KnownHResult::new(
+        0x8007_3D06,
+        "ERROR_INSTALL_PACKAGE_DOWNGRADE",
+        "A higher version of the package is already installed",
+    ),
+    KnownHResult::new(
+        0x800B_0100,
+        "TRUST_E_NOSIGNATURE",
+        "No signature was present in the subject",
+    ),
+    KnownHResult::new(
+        0x800B_0109,
+        "CERT_E_UNTRUSTEDROOT",
+        "A certificate chain terminated in a root certificate which is not trusted",
+    ),
+    KnownHResult::new(
+        0x8A15_0001,
+        "APPINSTALLER_CLI_ERROR_INTERNAL_ERROR",
+        "Internal error",
+    ),
+    KnownHResult::new(
+        0x8A15_0002,
+        "APPINSTALLER_CLI_ERROR_INVALID_CL_ARGUMENTS",
+        "Invalid command line arguments",
+    ),
+    KnownHResult::new(
+        0x8A15_0003,
+        "APPINSTALLER_CLI_ERROR_COMMAND_FAILED",
+        "Executing command failed",
+    ),
+    KnownHResult::new(
+        0x8A15_0004,
+        "APPINSTALLER_CLI_ERROR_MANIFEST_FAILED",
+        "Opening manifest failed",
+    ),
+    KnownHResult::new(
+        0x8A15_0005,
+        "APPINSTALLER_CLI_ERROR_CTRL_SIGNAL_RECEIVED",
+        "Cancellation signal received",
+    ),
+    KnownHResult::new(
+        0x8A15_0006,
+        "APPINSTALLER_CLI_ERROR_SHELLEXEC_INSTALL_FAILED",
+        "Running ShellExecute failed",
+    ),
+    KnownHResult::new(
+        0x8A15_0007,
+        "APPINSTALLER_CLI_ERROR_UNSUPPORTED_MANIFESTVERSION",
+        "Cannot process manifest. The manifest version is higher than supported",
+    ),
+    KnownHResult::new(
+        0x8A15_0008,
+        "APPINSTALLER_CLI_ERROR_DOWNLOAD_FAILED",
+        "Downloading installer failed",
+    ),
+    KnownHResult::new(
+        0x8A15_0009,
+        "APPINSTALLER_CLI_ERROR_CANNOT_WRITE_TO_UPLEVEL_INDEX",
+        "Cannot write to index; it is a higher schema version",
+    ),
+    KnownHResult::new(
+        0x8A15_000A,
+        "APPINSTALLER_CLI_ERROR_INDEX_INTEGRITY_COMPROMISED",
+        "The index is corrupt",
+    ),
+    KnownHResult::new(
+        0x8A15_000B,
+        "APPINSTALLER_CLI_ERROR_SOURCES_INVALID",
+        "The configured source information is corrupt",
+    ),
+    KnownHResult::new(
+        0x8A15_000C,
+        "APPINSTALLER_CLI_ERROR_SOURCE_NAME_ALREADY_EXISTS",
+        "The source name is already configured",
+    ),
+    KnownHResult::new(
+        0x8A15_000D,
+        "APPINSTALLER_CLI_ERROR_INVALID_SOURCE_TYPE",
+        "The source type is invalid",
+    ),
+    KnownHResult::new(
+        0x8A15_000E,
+        "APPINSTALLER_CLI_ERROR_PACKAGE_IS_BUNDLE",
+        "The MSIX file is a bundle, not a package",
+    ),
+    KnownHResult::new(
+        0x8A15_000F,
+        "APPINSTALLER_CLI_ERROR_SOURCE_DATA_MISSING",
+        "Data required by the source is missing",
+    ),
+    KnownHResult::new(
+        0x8A15_0010,
+        "APPINSTALLER_CLI_ERROR_NO_APPLICABLE_INSTALLER",
+        "None of the installers are applicable for the current system",
+    ),
+    KnownHResult::new(
+        0x8A15_0011,
+        "APPINSTALLER_CLI_ERROR_INSTALLER_HASH_MISMATCH",
+        "The installer file's hash does not match the manifest",
+    ),
+    KnownHResult::new(
+        0x8A15_0012,
+        "APPINSTALLER_CLI_ERROR_SOURCE_NAME_DOES_NOT_EXIST",
+        "The source name does not exist",
+    ),
+    KnownHResult::new(
+        0x8A15_0013,
+        "APPINSTALLER_CLI_ERROR_SOURCE_ARG_ALREADY_EXISTS",
+        "The source location is already configured under another name",
+    ),
+    KnownHResult::new(
+        0x8A15_0014,
+        "APPINSTALLER_CLI_ERROR_NO_APPLICATIONS_FOUND",
+        "No packages found",
+    ),
+    KnownHResult::new(
+        0x8A15_0015,
+        "APPINSTALLER_CLI_ERROR_NO_SOURCES_DEFINED",
+        "No sources are configured",
+    ),
+    KnownHResult::new(
+        0x8A15_0016,
+        "APPINSTALLER_CLI_ERROR_MULTIPLE_APPLICATIONS_FOUND",
+        "Multiple packages found matching the criteria",
+    ),
+    KnownHResult::new(
+        0x8A15_0017,
+        "APPINSTALLER_CLI_ERROR_NO_MANIFEST_FOUND",
+        "No manifest found matching the criteria",
+    ),
+    KnownHResult::new(
+        0x8A15_0018,
+        "APPINSTALLER_CLI_ERROR_EXTENSION_PUBLIC_FAILED",
+        "Failed to get Public folder from source package",
+    ),
+    KnownHResult::new(
+        0x8A15_0019,
+        "APPINSTALLER_CLI_ERROR_COMMAND_REQUIRES_ADMIN",
+        "Command requires administrator privileges to run",
+    ),
+    KnownHResult::new(
+        0x8A15_001A,
+        "APPINSTALLER_CLI_ERROR_SOURCE_NOT_SECURE",
+        "The source location must be secure",
+    ),
+    KnownHResult::new(
+        0x8A15_001B,
+        "APPINSTALLER_CLI_ERROR_MSSTORE_BLOCKED_BY_POLICY",
+        "The Microsoft Store client is blocked by policy",
+    ),
+    KnownHResult::new(
+        0x8A15_001C,
+        "APPINSTALLER_CLI_ERROR_MSSTORE_APP_BLOCKED_BY_POLICY",
+        "The Microsoft Store app is blocked by policy",
+    ),
+    KnownHResult::new(
+        0x8A15_001D,
+        "APPINSTALLER_CLI_ERROR_EXPERIMENTAL_FEATURE_DISABLED",
+        "The feature is currently under development. It can be enabled using winget settings",
+    ),
+    KnownHResult::new(
+        0x8A15_001E,
+        "APPINSTALLER_CLI_ERROR_MSSTORE_INSTALL_FAILED",
+        "Failed to install the Microsoft Store app",
+    ),
+    KnownHResult::new(
+        0x8A15_001F,
+        "APPINSTALLER_CLI_ERROR_COMPLETE_INPUT_BAD",
+        "Failed to perform auto complete",
+    ),
+    KnownHResult::new(
+        0x8A15_0020,
+        "APPINSTALLER_CLI_ERROR_YAML_INIT_FAILED",
+        "Failed to initialize YAML parser",
+    ),
+    KnownHResult::new(
+        0x8A15_0021,
+        "APPINSTALLER_CLI_ERROR_YAML_INVALID_MAPPING_KEY",
+        "Encountered an invalid YAML key",
+    ),
+    KnownHResult::new(
+        0x8A15_0022,
+        "APPINSTALLER_CLI_ERROR_YAML_DUPLICATE_MAPPING_KEY",
+        "Encountered a duplicate YAML key",
+    ),
+    KnownHResult::new(
+        0x8A15_0023,
+        "APPINSTALLER_CLI_ERROR_YAML_INVALID_OPERATION",
+        "Invalid YAML operation",
+    ),
+    KnownHResult::new(
+        0x8A15_0024,
+        "APPINSTALLER_CLI_ERROR_YAML_DOC_BUILD_FAILED",
+        "Failed to build YAML doc",
+    ),
+    KnownHResult::new(
+        0x8A15_0025,
+        "APPINSTALLER_CLI_ERROR_YAML_INVALID_EMITTER_STATE",
+        "Invalid YAML emitter state",
+    ),
+    KnownHResult::new(
+        0x8A15_0026,
+        "APPINSTALLER_CLI_ERROR_YAML_INVALID_DATA",
+        "Invalid YAML data",
+    ),
+    KnownHResult::new(
+        0x8A15_0027,
+        "APPINSTALLER_CLI_ERROR_LIBYAML_ERROR",
+        "LibYAML error",
+    ),
+    KnownHResult::new(
+        0x8A15_0028,
+        "APPINSTALLER_CLI_ERROR_MANIFEST_VALIDATION_WARNING",
+        "Manifest validation succeeded with warning",
+    ),
+    KnownHResult::new(
+        0x8A15_0029,
+        "APPINSTALLER_CLI_ERROR_MANIFEST_VALIDATION_FAILURE",
+        "Manifest validation failed",
+    ),
+    KnownHResult::new(
+        0x8A15_002A,
+        "APPINSTALLER_CLI_ERROR_INVALID_MANIFEST",
+        "Manifest is invalid",
+    ),
+    KnownHResult::new(
+        0x8A15_002B,
+        "APPINSTALLER_CLI_ERROR_UPDATE_NOT_APPLICABLE",
+        "No applicable update found",
+    ),
+    KnownHResult::new(
+        0x8A15_002C,
+        "APPINSTALLER_CLI_ERROR_UPDATE_ALL_HAS_FAILURE",
+        "winget upgrade --all completed with failures",
+    ),
+    KnownHResult::new(
+        0x8A15_002D,
+        "APPINSTALLER_CLI_ERROR_INSTALLER_SECURITY_CHECK_FAILED",
+        "Installer failed security check",
+    ),
+    KnownHResult::new(
+        0x8A15_002E,
+        "APPINSTALLER_CLI_ERROR_DOWNLOAD_SIZE_MISMATCH",
+        "Download size does not match expected content length",
+    ),
+    KnownHResult::new(
+        0x8A15_002F,
+        "APPINSTALLER_CLI_ERROR_NO_UNINSTALL_INFO_FOUND",
+        "Uninstall command not found",
+    ),
+    KnownHResult::new(
+        0x8A15_0030,
+        "APPINSTALLER_CLI_ERROR_EXEC_UNINSTALL_COMMAND_FAILED",
+        "Running uninstall command failed",
+    ),
+    KnownHResult::new(
+        0x8A15_0031,
+        "APPINSTALLER_CLI_ERROR_ICU_BREAK_ITERATOR_ERROR",
+        "ICU break iterator error",
+    ),
+    KnownHResult::new(
+        0x8A15_0032,
+        "APPINSTALLER_CLI_ERROR_ICU_CASEMAP_ERROR",
+        "ICU casemap error",
+    ),
+    KnownHResult::new(
+        0x8A15_0033,
+        "APPINSTALLER_CLI_ERROR_ICU_REGEX_ERROR",
+        "ICU regex error",
+    ),
+    KnownHResult::new(
+        0x8A15_0034,
+        "APPINSTALLER_CLI_ERROR_IMPORT_INSTALL_FAILED",
+        "Failed to install one or more imported packages",
+    ),
+    KnownHResult::new(
+        0x8A15_0035,
+        "APPINSTALLER_CLI_ERROR_NOT_ALL_PACKAGES_FOUND",
+        "Could not find one or more requested packages",
+    ),
+    KnownHResult::new(
+        0x8A15_0036,
+        "APPINSTALLER_CLI_ERROR_JSON_INVALID_FILE",
+        "Json file is invalid",
+    ),
+    KnownHResult::new(
+        0x8A15_0037,
+        "APPINSTALLER_CLI_ERROR_SOURCE_NOT_REMOTE",
+        "The source location is not remote",
+    ),
+    KnownHResult::new(
+        0x8A15_0038,
+        "APPINSTALLER_CLI_ERROR_UNSUPPORTED_RESTSOURCE",
+        "The configured rest source is not supported",
+    ),
+    KnownHResult::new(
+        0x8A15_0039,
+        "APPINSTALLER_CLI_ERROR_RESTSOURCE_INVALID_DATA",
+        "Invalid data returned by rest source",
+    ),
+    KnownHResult::new(
+        0x8A15_003A,
+        "APPINSTALLER_CLI_ERROR_BLOCKED_BY_POLICY",
+        "Operation is blocked by Group Policy",
+    ),
+    KnownHResult::new(
+        0x8A15_003B,
+        "APPINSTALLER_CLI_ERROR_RESTAPI_INTERNAL_ERROR",
+        "Rest API internal error",
+    ),
+    KnownHResult::new(
+        0x8A15_003C,
+        "APPINSTALLER_CLI_ERROR_RESTSOURCE_INVALID_URL",
+        "Invalid rest source url",
+    ),
+    KnownHResult::new(
+        0x8A15_003D,
+        "APPINSTALLER_CLI_ERROR_RESTAPI_UNSUPPORTED_MIME_TYPE",
+        "Unsupported MIME type returned by rest API",
+    ),
+    KnownHResult::new(
+        0x8A15_003E,
+        "APPINSTALLER_CLI_ERROR_RESTSOURCE_INVALID_VERSION",
+        "Invalid rest source contract version",
+    ),
+    KnownHResult::new(
+        0x8A15_003F,
+        "APPINSTALLER_CLI_ERROR_SOURCE_DATA_INTEGRITY_FAILURE",
+        "The source data is corrupted or tampered",
+    ),
+    KnownHResult::new(
+        0x8A15_0040,
+        "APPINSTALLER_CLI_ERROR_STREAM_READ_FAILURE",
+        "Error reading from the stream",
+    ),
+    KnownHResult::new(
+        0x8A15_0041,
+        "APPINSTALLER_CLI_ERROR_PACKAGE_AGREEMENTS_NOT_ACCEPTED",
+        "Package agreements were not agreed to",
+    ),
+    KnownHResult::new(
+        0x8A15_0042,
+        "APPINSTALLER_CLI_ERROR_PROMPT_INPUT_ERROR",
+        "Error reading input in prompt",
+    ),
+    KnownHResult::new(
+        0x8A15_0043,
+        "APPINSTALLER_CLI_ERROR_UNSUPPORTED_SOURCE_REQUEST",
+        "The search request is not supported by one or more sources",
+    ),
+    KnownHResult::new(
+        0x8A15_0044,
+        "APPINSTALLER_CLI_ERROR_RESTAPI_ENDPOINT_NOT_FOUND",
+        "The rest API endpoint is not found",
+    ),
+    KnownHResult::new(
+        0x8A15_0045,
+        "APPINSTALLER_CLI_ERROR_SOURCE_OPEN_FAILED",
+        "Failed to open the source",
+    ),
+    KnownHResult::new(
+        0x8A15_0046,
+        "APPINSTALLER_CLI_ERROR_SOURCE_AGREEMENTS_NOT_ACCEPTED",
+        "Source agreements were not agreed to",
+    ),
+    KnownHResult::new(
+        0x8A15_0047,
+        "APPINSTALLER_CLI_ERROR_CUSTOMHEADER_EXCEEDS_MAXLENGTH",
+        "Header size exceeds the allowable limit of 1024 characters",
+    ),
+    KnownHResult::new(
+        0x8A15_0048,
+        "APPINSTALLER_CLI_ERROR_MISSING_RESOURCE_FILE",
+        "Missing resource file",
+    ),
+    KnownHResult::new(
+        0x8A15_0049,
+        "APPINSTALLER_CLI_ERROR_MSI_INSTALL_FAILED",
+        "Running MSI install failed",
+    ),
+    KnownHResult::new(
+        0x8A15_004A,
+        "APPINSTALLER_CLI_ERROR_INVALID_MSIEXEC_ARGUMENT",
+        "Arguments for msiexec are invalid",
+    ),
+    KnownHResult::new(
+        0x8A15_004B,
+        "APPINSTALLER_CLI_ERROR_FAILED_TO_OPEN_ALL_SOURCES",
+        "Failed to open one or more sources",
+    ),
+    KnownHResult::new(
+        0x8A15_004C,
+        "APPINSTALLER_CLI_ERROR_DEPENDENCIES_VALIDATION_FAILED",
+        "Failed to validate dependencies",
+    ),
+    KnownHResult::new(
+        0x8A15_004D,
+        "APPINSTALLER_CLI_ERROR_MISSING_PACKAGE",
+        "One or more package is missing",
+    ),
+    KnownHResult::new(
+        0x8A15_004E,
+        "APPINSTALLER_CLI_ERROR_INVALID_TABLE_COLUMN",
+        "Invalid table column",
+    ),
+    KnownHResult::new(
+        0x8A15_004F,
+        "APPINSTALLER_CLI_ERROR_UPGRADE_VERSION_NOT_NEWER",
+        "The upgrade version is not newer than the installed version",
+    ),
+    KnownHResult::new(
+        0x8A15_0050,
+        "APPINSTALLER_CLI_ERROR_UPGRADE_VERSION_UNKNOWN",
+        "Upgrade version is unknown and override is not specified",
+    ),
+    KnownHResult::new(
+        0x8A15_0051,
+        "APPINSTALLER_CLI_ERROR_ICU_CONVERSION_ERROR",
+        "ICU conversion error",
+    ),
+    KnownHResult::new(
+        0x8A15_0052,
+        "APPINSTALLER_CLI_ERROR_PORTABLE_INSTALL_FAILED",
+        "Failed to install portable package",
+    ),
+    KnownHResult::new(
+        0x8A15_0053,
+        "APPINSTALLER_CLI_ERROR_PORTABLE_REPARSE_POINT_NOT_SUPPORTED",
+        "Volume does not support reparse points",
+    ),
+    KnownHResult::new(
+        0x8A15_0054,
+        "APPINSTALLER_CLI_ERROR_PORTABLE_PACKAGE_ALREADY_EXISTS",
+        "Portable package from a different source already exists",
+    ),
+    KnownHResult::new(
+        0x8A15_0055,
+        "APPINSTALLER_CLI_ERROR_PORTABLE_SYMLINK_PATH_IS_DIRECTORY",
+        "Unable to create symlink, path points to a directory",
+    ),
+    KnownHResult::new(
+        0x8A15_0056,
+        "APPINSTALLER_CLI_ERROR_INSTALLER_PROHIBITS_ELEVATION",
+        "The installer cannot be run from an administrator context",
+    ),
+    KnownHResult::new(
+        0x8A15_0057,
+        "APPINSTALLER_CLI_ERROR_PORTABLE_UNINSTALL_FAILED",
+        "Failed to uninstall portable package",
+    ),
+    KnownHResult::new(
+        0x8A15_0058,
+        "APPINSTALLER_CLI_ERROR_ARP_VERSION_VALIDATION_FAILED",
+        "Failed to validate DisplayVersion values against index",
+    ),
+    KnownHResult::new(
+        0x8A15_0059,
+        "APPINSTALLER_CLI_ERROR_UNSUPPORTED_ARGUMENT",
+        "One or more arguments are not supported",
+    ),
+    KnownHResult::new(
+        0x8A15_005A,
+        "APPINSTALLER_CLI_ERROR_BIND_WITH_EMBEDDED_NULL",
+        "Embedded null characters are disallowed for SQLite",
+    ),
+    KnownHResult::new(
+        0x8A15_005B,
+        "APPINSTALLER_CLI_ERROR_NESTEDINSTALLER_NOT_FOUND",
+        "Failed to find the nested installer in the archive",
+    ),
+    KnownHResult::new(
+        0x8A15_005C,
+        "APPINSTALLER_CLI_ERROR_EXTRACT_ARCHIVE_FAILED",
+        "Failed to extract archive",
+    ),
+    KnownHResult::new(
+        0x8A15_005D,
+        "APPINSTALLER_CLI_ERROR_NESTEDINSTALLER_INVALID_PATH",
+        "Invalid relative file path to nested installer provided",
+    ),
+    KnownHResult::new(
+        0x8A15_005E,
+        "APPINSTALLER_CLI_ERROR_PINNED_CERTIFICATE_MISMATCH",
+        "The server certificate did not match any of the expected values",
+    ),
+    KnownHResult::new(
+        0x8A15_005F,
+        "APPINSTALLER_CLI_ERROR_INSTALL_LOCATION_REQUIRED",
+        "Install location must be provided",
+    ),
+    KnownHResult::new(
+        0x8A15_0060,
+        "APPINSTALLER_CLI_ERROR_ARCHIVE_SCAN_FAILED",
+        "Archive malware scan failed",
+    ),
+    KnownHResult::new(
+        0x8A15_0061,
+        "APPINSTALLER_CLI_ERROR_PACKAGE_ALREADY_INSTALLED",
+        "Found at least one version of the package installed",
+    ),
+    KnownHResult::new(
+        0x8A15_0062,
+        "APPINSTALLER_CLI_ERROR_PIN_ALREADY_EXISTS",
+        "A pin already exists for the package",
+    ),
+    KnownHResult::new(
+        0x8A15_0063,
+        "APPINSTALLER_CLI_ERROR_PIN_DOES_NOT_EXIST",
+        "There is no pin for the package",
+    ),
+    KnownHResult::new(
+        0x8A15_0064,
+        "APPINSTALLER_CLI_ERROR_CANNOT_OPEN_PINNING_INDEX",
+        "Unable to open the pin database",
+    ),
+    KnownHResult::new(
+        0x8A15_0065,
+        "APPINSTALLER_CLI_ERROR_MULTIPLE_INSTALL_FAILED",
+        "One or more applications failed to install",
+    ),
+    KnownHResult::new(
+        0x8A15_0066,
+        "APPINSTALLER_CLI_ERROR_MULTIPLE_UNINSTALL_FAILED",
+        "One or more applications failed to uninstall",
+    ),
+    KnownHResult::new(
+        0x8A15_0067,
+        "APPINSTALLER_CLI_ERROR_NOT_ALL_QUERIES_FOUND_SINGLE",
+        "One or more queries did not return exactly one match",
+    ),
+    KnownHResult::new(
+        0x8A15_0068,
+        "APPINSTALLER_CLI_ERROR_PACKAGE_IS_PINNED",
+        "The package has a pin that prevents upgrade",
+    ),
+    KnownHResult::new(
+        0x8A15_0069,
+        "APPINSTALLER_CLI_ERROR_PACKAGE_IS_STUB",
+        "The currently installed package is the stub package",
+    ),
+    KnownHResult::new(
+        0x8A15_006A,
+        "APPINSTALLER_CLI_ERROR_APPTERMINATION_RECEIVED",
+        "Application shutdown signal received",
+    ),
+    KnownHResult::new(
+        0x8A15_006B,
+        "APPINSTALLER_CLI_ERROR_DOWNLOAD_DEPENDENCIES",
+        "Failed to download package dependencies",
+    ),
+    KnownHResult::new(
+        0x8A15_006C,
+        "APPINSTALLER_CLI_ERROR_DOWNLOAD_COMMAND_PROHIBITED",
+        "Failed to download package. Download for offline installation is prohibited",
+    ),
+    KnownHResult::new(
+        0x8A15_006D,
+        "APPINSTALLER_CLI_ERROR_SERVICE_UNAVAILABLE",
+        "A required service is busy or unavailable. Try again later",
+    ),
+    KnownHResult::new(
+        0x8A15_006E,
+        "APPINSTALLER_CLI_ERROR_RESUME_ID_NOT_FOUND",
+        "The guid provided does not correspond to a valid resume state",
+    ),
+    KnownHResult::new(
+        0x8A15_006F,
+        "APPINSTALLER_CLI_ERROR_CLIENT_VERSION_MISMATCH",
+        "The current client version did not match the client version of the saved state",
+    ),
+    KnownHResult::new(
+        0x8A15_0070,
+        "APPINSTALLER_CLI_ERROR_INVALID_RESUME_STATE",
+        "The resume state data is invalid",
+    ),
+    KnownHResult::new(
+        0x8A15_0071,
+        "APPINSTALLER_CLI_ERROR_CANNOT_OPEN_CHECKPOINT_INDEX",
+        "Unable to open the checkpoint database",
+    ),
+    KnownHResult::new(
+        0x8A15_0072,
+        "APPINSTALLER_CLI_ERROR_RESUME_LIMIT_EXCEEDED",
+        "Exceeded max resume limit",
+    ),
+    KnownHResult::new(
+        0x8A15_0073,
+        "APPINSTALLER_CLI_ERROR_INVALID_AUTHENTICATION_INFO",
+        "Invalid authentication info",
+    ),
+    KnownHResult::new(
+        0x8A15_0074,
+        "APPINSTALLER_CLI_ERROR_AUTHENTICATION_METHOD_NOT_SUPPORTED",
+        "Authentication method not supported",
+    ),
+    KnownHResult::new(
+        0x8A15_0075,
+        "APPINSTALLER_CLI_ERROR_AUTHENTICATION_FAILED",
+        "Authentication failed",
+    ),
+    KnownHResult::new(
+        0x8A15_0076,
+        "APPINSTALLER_CLI_ERROR_AUTHENTICATION_INTERACTIVE_REQUIRED",
+        "Authentication failed. Interactive authentication required",
+    ),
+    KnownHResult::new(
+        0x8A15_0077,
+        "APPINSTALLER_CLI_ERROR_AUTHENTICATION_CANCELLED_BY_USER",
+        "Authentication failed. User cancelled",
+    ),
+    KnownHResult::new(
+        0x8A15_0078,
+        "APPINSTALLER_CLI_ERROR_AUTHENTICATION_INCORRECT_ACCOUNT",
+        "Authentication failed. Authenticated account is not the desired account",
+    ),
+    KnownHResult::new(
+        0x8A15_0079,
+        "APPINSTALLER_CLI_ERROR_NO_REPAIR_INFO_FOUND",
+        "Repair command not found",
+    ),
+    KnownHResult::new(
+        0x8A15_007A,
+        "APPINSTALLER_CLI_ERROR_REPAIR_NOT_APPLICABLE",
+        "Repair operation is not applicable",
+    ),
+    KnownHResult::new(
+        0x8A15_007B,
+        "APPINSTALLER_CLI_ERROR_EXEC_REPAIR_FAILED",
+        "Repair operation failed",
+    ),
+    KnownHResult::new(
+        0x8A15_007C,
+        "APPINSTALLER_CLI_ERROR_REPAIR_NOT_SUPPORTED",
+        "The installer technology in use doesn't support repair",
+    ),
+    KnownHResult::new(
+        0x8A15_007D,
+        "APPINSTALLER_CLI_ERROR_ADMIN_CONTEXT_REPAIR_PROHIBITED",
+        "Repair operations involving administrator privileges are not permitted on packages \
+         installed within the user scope",
+    ),
+    KnownHResult::new(
+        0x8A15_007E,
+        "APPINSTALLER_CLI_ERROR_SQLITE_CONNECTION_TERMINATED",
+        "The SQLite connection was terminated to prevent corruption",
+    ),
+    KnownHResult::new(
+        0x8A15_007F,
+        "APPINSTALLER_CLI_ERROR_DISPLAYCATALOG_API_FAILED",
+        "Failed to get Microsoft Store package catalog",
+    ),
+    KnownHResult::new(
+        0x8A15_0080,
+        "APPINSTALLER_CLI_ERROR_NO_APPLICABLE_DISPLAYCATALOG_PACKAGE",
+        "No applicable Microsoft Store package found from Microsoft Store package catalog",
+    ),
+    KnownHResult::new(
+        0x8A15_0081,
+        "APPINSTALLER_CLI_ERROR_SFSCLIENT_API_FAILED",
+        "Failed to get Microsoft Store package download information",
+    ),
+    KnownHResult::new(
+        0x8A15_0082,
+        "APPINSTALLER_CLI_ERROR_NO_APPLICABLE_SFSCLIENT_PACKAGE",
+        "No applicable Microsoft Store package download information found",
+    ),
+    KnownHResult::new(
+        0x8A15_0083,
+        "APPINSTALLER_CLI_ERROR_LICENSING_API_FAILED",
+        "Failed to retrieve Microsoft Store package license",
+    ),
+    KnownHResult::new(
+        0x8A15_0084,
+        "APPINSTALLER_CLI_ERROR_SFSCLIENT_PACKAGE_NOT_SUPPORTED",
+        "The Microsoft Store package does not support download command",
+    ),
+    KnownHResult::new(
+        0x8A15_0085,
+        "APPINSTALLER_CLI_ERROR_LICENSING_API_FAILED_FORBIDDEN",
+        "Failed to retrieve Microsoft Store package license. The Microsoft Entra Id account does \
+         not have required privilege",
+    ),
+    KnownHResult::new(
+        0x8A15_0086,
+        "APPINSTALLER_CLI_ERROR_INSTALLER_ZERO_BYTE_FILE",
+        "Downloaded zero byte installer; ensure that your network connection is working properly",
+    ),
+    KnownHResult::new(
+        0x8A15_0101,
+        "APPINSTALLER_CLI_ERROR_INSTALL_PACKAGE_IN_USE",
+        "Application is currently running",
+    ),
+    KnownHResult::new(
+        0x8A15_0102,
+        "APPINSTALLER_CLI_ERROR_INSTALL_INSTALL_IN_PROGRESS",
+        "Another installation is already in progress",
+    ),
+    KnownHResult::new(
+        0x8A15_0103,
+        "APPINSTALLER_CLI_ERROR_INSTALL_FILE_IN_USE",
+        "One or more files are being used",
+    ),
+    KnownHResult::new(
+        0x8A15_0104,
+        "APPINSTALLER_CLI_ERROR_INSTALL_MISSING_DEPENDENCY",
+        "This package has a dependency missing from your system",
+    ),
+    KnownHResult::new(
+        0x8A15_0105,
+        "APPINSTALLER_CLI_ERROR_INSTALL_DISK_FULL",
+        "There's no more space on your PC",
+    ),
+    KnownHResult::new(
+        0x8A15_0106,
+        "APPINSTALLER_CLI_ERROR_INSTALL_INSUFFICIENT_MEMORY",
+        "There's not enough memory available to install",
+    ),
+    KnownHResult::new(
+        0x8A15_0107,
+        "APPINSTALLER_CLI_ERROR_INSTALL_NO_NETWORK",
+        "This application requires internet connectivity",
+    ),
+    KnownHResult::new(
+        0x8A15_0108,
+        "APPINSTALLER_CLI_ERROR_INSTALL_CONTACT_SUPPORT",
+        "This application encountered an error during installation",
+    ),
+    KnownHResult::new(
+        0x8A15_0109,
+        "APPINSTALLER_CLI_ERROR_INSTALL_REBOOT_REQUIRED_TO_FINISH",
+        "Restart your PC to finish installation",
+    ),
+    KnownHResult::new(
+        0x8A15_010A,
+        "APPINSTALLER_CLI_ERROR_INSTALL_REBOOT_REQUIRED_FOR_INSTALL",
+        "Installation failed. Restart your PC then try again",
+    ),
+    KnownHResult::new(
+        0x8A15_010B,
+        "APPINSTALLER_CLI_ERROR_INSTALL_REBOOT_INITIATED",
+        "Your PC will restart to finish installation",
+    ),
+    KnownHResult::new(
+        0x8A15_010C,
+        "APPINSTALLER_CLI_ERROR_INSTALL_CANCELLED_BY_USER",
+        "You cancelled the installation",
+    ),
+    KnownHResult::new(
+        0x8A15_010D,
+        "APPINSTALLER_CLI_ERROR_INSTALL_ALREADY_INSTALLED",
+        "Another version of this application is already installed",
+    ),
+    KnownHResult::new(
+        0x8A15_010E,
+        "APPINSTALLER_CLI_ERROR_INSTALL_DOWNGRADE",
+        "A higher version of this application is already installed",
+    ),
+    KnownHResult::new(
+        0x8A15_010F,
+        "APPINSTALLER_CLI_ERROR_INSTALL_BLOCKED_BY_POLICY",
+        "Organization policies are preventing installation",
+    ),
+    KnownHResult::new(
+        0x8A15_0110,
+        "APPINSTALLER_CLI_ERROR_INSTALL_DEPENDENCIES",
+        "Failed to install package dependencies",
+    ),
+    KnownHResult::new(
+        0x8A15_0111,
+        "APPINSTALLER_CLI_ERROR_INSTALL_PACKAGE_IN_USE_BY_APPLICATION",
+        "Application is currently in use by another application",
+    ),
+    KnownHResult::new(
+        0x8A15_0112,
+        "APPINSTALLER_CLI_ERROR_INSTALL_INVALID_PARAMETER",
+        "Invalid parameter",
+    ),
+    KnownHResult::new(
+        0x8A15_0113,
+        "APPINSTALLER_CLI_ERROR_INSTALL_SYSTEM_NOT_SUPPORTED",
+        "Package not supported on the system",
+    ),
+];
+
+impl fmt::Display for KnownHResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.hresult, self.name, self.description)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use rstest::rstest;
+
+    use super::{Facility, HResult, KNOWN_HRESULTS, Severity};
+    use crate::installer::InstallerReturnCode;
+
+    #[test]
+    fn known_hresults_are_sorted() {
+        assert!(
+            KNOWN_HRESULTS
+                .windows(2)
+                .all(|pair| pair[0].hresult < pair[1].hresult)
+        );
+    }
+
+    #[rstest]
+    #[case(0x8A15_0011, Severity::Failure, Facility::AppInstaller, 0x11)]
+    #[case(0x8007_3CF9, Severity::Failure, Facility::Win32, 0x3CF9)]
+    #[case(0x800B_0100, Severity::Failure, Facility::Certificate, 0x0100)]
+    #[case(0x8000_4005, Severity::Failure, Facility::Null, 0x4005)]
+    #[case(0x0004_0000, Severity::Success, Facility::Interface, 0)]
+    #[case(0xC00D_0001, Severity::Failure, Facility::Other(13), 1)]
+    fn decode(
+        #[case] bits: u32,
+        #[case] severity: Severity,
+        #[case] facility: Facility,
+        #[case] code: u16,
+    ) {
+        let hresult = HResult::new(bits);
+        assert_eq!(hresult.severity(), severity);
+        assert_eq!(hresult.facility(), facility);
+        assert_eq!(hresult.code(), code);
+    }
+
+    #[rstest]
+    #[case("0x8A150011", 0x8A15_0011)]
+    #[case("0x8a150011", 0x8A15_0011)]
+    #[case("-1978335215", 0x8A15_0011)]
+    #[case("2316632081", 0x8A15_0011)]
+    #[case("1603", 1603)]
+    fn from_str(#[case] s: &str, #[case] bits: u32) {
+        assert_eq!(s.parse::<HResult>(), Ok(HResult::new(bits)));
+    }
+
+    #[test]
+    fn display() {
+        let hresult = HResult::new(0x8007_0005);
+        assert_eq!(hresult.to_string(), "0x80070005");
+        assert_eq!(hresult.to_i32().to_string(), "-2147024891");
+        assert_eq!(
+            hresult.known().unwrap().to_string(),
+            "0x80070005 (E_ACCESSDENIED): Access is denied"
+        );
+    }
+
+    #[rstest]
+    #[case(1603, Some("ERROR_INSTALL_FAILURE"))]
+    #[case(3010, Some("ERROR_SUCCESS_REBOOT_REQUIRED"))]
+    #[case(-2_147_009_274, Some("ERROR_INSTALL_PACKAGE_DOWNGRADE"))]
+    #[case(-1_978_334_963, Some("APPINSTALLER_CLI_ERROR_INSTALL_ALREADY_INSTALLED"))]
+    #[case(-1_978_335_189, Some("APPINSTALLER_CLI_ERROR_UPDATE_NOT_APPLICABLE"))]
+    #[case(-1_978_335_135, Some("APPINSTALLER_CLI_ERROR_PACKAGE_ALREADY_INSTALLED"))]
+    #[case(-1_978_335_098, Some("APPINSTALLER_CLI_ERROR_INSTALLER_ZERO_BYTE_FILE"))]
+    #[case(42, None)]
+    fn known_return_code(#[case] return_code: i32, #[case] name: Option<&str>) {
+        let return_code = InstallerReturnCode::from_i32(return_code).unwrap();
+        assert_eq!(
+            HResult::from(return_code).known().map(|known| known.name),
+            name
+        );
+    }
+}
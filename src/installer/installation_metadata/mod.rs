@@ -1,8 +1,14 @@
+#[cfg(feature = "std")]
+mod verify;
+
 use alloc::{collections::BTreeSet, string::String};
 use core::fmt;
 
+#[cfg(feature = "std")]
+pub use verify::MetadataIssue;
+
 use super::Sha256String;
-use crate::Path;
+use crate::{Path, RelativePath};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -33,7 +39,7 @@ impl InstallationMetadata {
 #[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
 pub struct MetadataFiles {
     /// The path to the installed file relative to the default install location.
-    pub relative_file_path: Path,
+    pub relative_file_path: RelativePath,
 
     /// The Sha256 hash of the installed file.
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
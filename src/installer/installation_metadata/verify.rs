@@ -0,0 +1,343 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use std::{fs::File, io};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use thiserror::Error;
+
+use super::{InstallationMetadata, MetadataFileType, MetadataFiles};
+use crate::{RelativePath, Sha256String};
+
+const LAUNCH_EXTENSIONS: [&str; 4] = ["exe", "com", "bat", "cmd"];
+
+const UNINSTALLER_PREFIX: &str = "unins";
+
+/// A discrepancy between [`InstallationMetadata`] and an installed directory tree.
+#[derive(Clone, Debug, Error, Eq, PartialEq)]
+pub enum MetadataIssue {
+    #[error("Declared file `{0}` does not exist")]
+    Missing(RelativePath),
+    #[error("File `{relative_file_path}` has a SHA256 of {actual} but {expected} was declared")]
+    HashMismatch {
+        relative_file_path: RelativePath,
+        expected: Sha256String,
+        actual: Sha256String,
+    },
+    #[error("Launchable file `{0}` is not declared in the installation metadata")]
+    UndeclaredLaunchFile(RelativePath),
+    #[error("File `{0}` cannot be declared as its path is not a valid relative file path")]
+    InvalidPath(Utf8PathBuf),
+}
+
+impl InstallationMetadata {
+    /// Verifies the declared [`files`] against a directory representing the install location.
+    ///
+    /// Every declared file must exist, and must match its [`file_sha_256`] if one is declared.
+    /// Files that would be considered [`launch`] files, such as executables, are reported if they
+    /// are present in the directory but not declared. Paths are matched case-insensitively, as
+    /// they would be on Windows. Files whose path is not a valid [`RelativePath`], such as paths
+    /// longer than [`RelativePath::MAX_CHAR_LENGTH`] or reserved names like `aux.js`, cannot be
+    /// declared and are reported as [`InvalidPath`].
+    ///
+    /// An empty `Vec` means the directory matches the metadata.
+    ///
+    /// [`files`]: InstallationMetadata::files
+    /// [`file_sha_256`]: MetadataFiles::file_sha_256
+    /// [`launch`]: MetadataFileType::Launch
+    /// [`InvalidPath`]: MetadataIssue::InvalidPath
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the directory could not be walked or a file could not be read.
+    pub fn verify<P: AsRef<Utf8Path>>(&self, directory: P) -> io::Result<Vec<MetadataIssue>> {
+        let (mut installed, invalid) = walk(directory.as_ref())?;
+        let mut issues = Vec::new();
+
+        for file in &self.files {
+            let Some(path) = installed.remove(&file.relative_file_path) else {
+                issues.push(MetadataIssue::Missing(file.relative_file_path.clone()));
+                continue;
+            };
+
+            if let Some(expected) = &file.file_sha_256 {
+                let actual = Sha256String::hash_from_reader(File::open(path)?)?;
+                if *expected != actual {
+                    issues.push(MetadataIssue::HashMismatch {
+                        relative_file_path: file.relative_file_path.clone(),
+                        expected: *expected,
+                        actual,
+                    });
+                }
+            }
+        }
+
+        issues.extend(
+            installed
+                .into_keys()
+                .filter(|relative_path| file_type(relative_path) == Some(MetadataFileType::Launch))
+                .map(MetadataIssue::UndeclaredLaunchFile),
+        );
+        issues.extend(invalid.into_iter().map(MetadataIssue::InvalidPath));
+
+        Ok(issues)
+    }
+}
+
+impl MetadataFiles {
+    /// Generates metadata for every file within a directory representing the install location.
+    ///
+    /// Each file is hashed. Executables are marked as [`launch`] files, or as [`uninstall`] files
+    /// if their name starts with `unins`, such as `unins000.exe` or `uninstall.exe`. All other
+    /// files have no file type, meaning they are treated as [`other`]. Files whose path is not a
+    /// valid [`RelativePath`] cannot be declared and are skipped.
+    ///
+    /// [`launch`]: MetadataFileType::Launch
+    /// [`uninstall`]: MetadataFileType::Uninstall
+    /// [`other`]: MetadataFileType::Other
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the directory could not be walked or a file could not be read.
+    pub fn from_directory<P: AsRef<Utf8Path>>(directory: P) -> io::Result<BTreeSet<Self>> {
+        walk(directory.as_ref())?
+            .0
+            .into_iter()
+            .map(|(relative_file_path, path)| {
+                Ok(Self {
+                    file_sha_256: Some(Sha256String::hash_from_reader(File::open(path)?)?),
+                    file_type: file_type(&relative_file_path),
+                    relative_file_path,
+                    invocation_parameter: None,
+                    display_name: None,
+                })
+            })
+            .collect()
+    }
+}
+
+fn file_type(relative_path: &RelativePath) -> Option<MetadataFileType> {
+    let is_launchable = relative_path.extension().is_some_and(|extension| {
+        LAUNCH_EXTENSIONS
+            .iter()
+            .any(|launch| launch.eq_ignore_ascii_case(extension))
+    });
+
+    if !is_launchable {
+        return None;
+    }
+
+    let is_uninstaller = relative_path
+        .file_stem()
+        .get(..UNINSTALLER_PREFIX.len())
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(UNINSTALLER_PREFIX));
+
+    Some(if is_uninstaller {
+        MetadataFileType::Uninstall
+    } else {
+        MetadataFileType::Launch
+    })
+}
+
+/// The regular files within a directory keyed by their relative path, and the paths relative to
+/// the directory of the files that are not a valid [`RelativePath`].
+type Walk = (BTreeMap<RelativePath, Utf8PathBuf>, Vec<Utf8PathBuf>);
+
+/// Recursively collects every regular file within `root`.
+///
+/// Symbolic links are not followed.
+fn walk(root: &Utf8Path) -> io::Result<Walk> {
+    let mut files = BTreeMap::new();
+    let mut invalid = Vec::new();
+    let mut pending = Vec::from([root.to_path_buf()]);
+
+    while let Some(directory) = pending.pop() {
+        for entry in directory.read_dir_utf8()? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                pending.push(entry.into_path());
+            } else if file_type.is_file() {
+                let path = entry.path().strip_prefix(root).map_err(io::Error::other)?;
+                match RelativePath::new(path) {
+                    Ok(relative_path) => {
+                        files.insert(relative_path, entry.into_path());
+                    }
+                    Err(_) => invalid.push(path.to_path_buf()),
+                }
+            }
+        }
+    }
+
+    invalid.sort_unstable();
+    Ok((files, invalid))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, vec};
+    use std::fs;
+
+    use camino::Utf8PathBuf;
+    use sha2::{Digest, Sha256};
+
+    use super::MetadataIssue;
+    use crate::{
+        RelativePath, Sha256String,
+        installer::{InstallationMetadata, MetadataFileType, MetadataFiles},
+    };
+
+    struct TempDir(Utf8PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = Utf8PathBuf::try_from(std::env::temp_dir())
+                .unwrap()
+                .join(format!("winget-types-{name}-{}", std::process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(path.join("bin")).unwrap();
+            fs::write(path.join("bin").join("App.exe"), b"app").unwrap();
+            fs::write(path.join("bin").join("helper.dll"), b"helper").unwrap();
+            fs::write(path.join("unins000.exe"), b"uninstall").unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn sha256(data: &[u8]) -> Sha256String {
+        Sha256String::from_digest(&Sha256::digest(data))
+    }
+
+    fn path(path: &str) -> RelativePath {
+        path.parse().unwrap()
+    }
+
+    #[test]
+    fn from_directory() {
+        let dir = TempDir::new("metadata-from-directory");
+
+        let files = MetadataFiles::from_directory(&dir.0).unwrap();
+
+        assert_eq!(
+            files.into_iter().collect::<vec::Vec<_>>(),
+            vec![
+                MetadataFiles {
+                    relative_file_path: path(r"bin\App.exe"),
+                    file_sha_256: Some(sha256(b"app")),
+                    file_type: Some(MetadataFileType::Launch),
+                    invocation_parameter: None,
+                    display_name: None,
+                },
+                MetadataFiles {
+                    relative_file_path: path(r"bin\helper.dll"),
+                    file_sha_256: Some(sha256(b"helper")),
+                    file_type: None,
+                    invocation_parameter: None,
+                    display_name: None,
+                },
+                MetadataFiles {
+                    relative_file_path: path("unins000.exe"),
+                    file_sha_256: Some(sha256(b"uninstall")),
+                    file_type: Some(MetadataFileType::Uninstall),
+                    invocation_parameter: None,
+                    display_name: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn generated_metadata_verifies() {
+        let dir = TempDir::new("metadata-round-trip");
+
+        let metadata = InstallationMetadata {
+            default_install_location: None,
+            files: MetadataFiles::from_directory(&dir.0).unwrap(),
+        };
+
+        assert_eq!(metadata.verify(&dir.0).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn invalid_paths() {
+        let dir = TempDir::new("metadata-invalid-paths");
+        let deep = dir.0.join("a".repeat(200)).join("b".repeat(200));
+        fs::create_dir_all(&deep).unwrap();
+        fs::write(deep.join("c".repeat(200)), b"deep").unwrap();
+        fs::write(dir.0.join("aux.js"), b"reserved").unwrap();
+        fs::write(dir.0.join("bin").join("trailing."), b"trailing").unwrap();
+
+        let files = MetadataFiles::from_directory(&dir.0).unwrap();
+        assert_eq!(files.len(), 3);
+
+        let metadata = InstallationMetadata {
+            default_install_location: None,
+            files,
+        };
+
+        assert_eq!(
+            metadata.verify(&dir.0).unwrap(),
+            vec![
+                MetadataIssue::InvalidPath(
+                    ["a".repeat(200), "b".repeat(200), "c".repeat(200)]
+                        .iter()
+                        .collect()
+                ),
+                MetadataIssue::InvalidPath("aux.js".into()),
+                MetadataIssue::InvalidPath(["bin", "trailing."].iter().collect()),
+            ]
+        );
+    }
+
+    #[test]
+    fn verify_issues() {
+        let dir = TempDir::new("metadata-verify");
+
+        let metadata = InstallationMetadata {
+            default_install_location: None,
+            files: [
+                MetadataFiles {
+                    relative_file_path: path(r"BIN\helper.DLL"),
+                    file_sha_256: Some(sha256(b"other")),
+                    file_type: None,
+                    invocation_parameter: None,
+                    display_name: None,
+                },
+                MetadataFiles {
+                    relative_file_path: path("unins000.exe"),
+                    file_sha_256: None,
+                    file_type: Some(MetadataFileType::Uninstall),
+                    invocation_parameter: None,
+                    display_name: None,
+                },
+                MetadataFiles {
+                    relative_file_path: path("missing.txt"),
+                    file_sha_256: None,
+                    file_type: None,
+                    invocation_parameter: None,
+                    display_name: None,
+                },
+            ]
+            .into(),
+        };
+
+        assert_eq!(
+            metadata.verify(&dir.0).unwrap(),
+            vec![
+                MetadataIssue::HashMismatch {
+                    relative_file_path: path(r"BIN\helper.DLL"),
+                    expected: sha256(b"other"),
+                    actual: sha256(b"helper"),
+                },
+                MetadataIssue::Missing(path("missing.txt")),
+                MetadataIssue::UndeclaredLaunchFile(path(r"bin\App.exe")),
+            ]
+        );
+    }
+}
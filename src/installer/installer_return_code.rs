@@ -219,6 +219,32 @@ impl InstallerReturnCode {
         }
     }
 
+    /// Returns the 32 bits of the return code, as they appear in the exit code of a Windows
+    /// process.
+    ///
+    /// Negative return codes are reinterpreted as unsigned, so `-1` has the same bits as
+    /// [`u32::MAX`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::installer::InstallerReturnCode;
+    ///
+    /// # fn doctest() -> Option<()> {
+    /// assert_eq!(InstallerReturnCode::from_u32(3010)?.to_bits(), 3010);
+    /// assert_eq!(InstallerReturnCode::from_i32(-1)?.to_bits(), u32::MAX);
+    /// # Some(())
+    /// # }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn to_bits(self) -> u32 {
+        match self {
+            Self::Positive(n) => n.get(),
+            Self::Negative(n) => n.get().cast_unsigned(),
+        }
+    }
+
     /// Returns `true` if the return code is positive.
     ///
     /// # Examples
@@ -415,7 +441,7 @@ mod tests {
     ) {
         assert_eq!(
             str.parse::<InstallerReturnCode>()
-                .map_err(|err| err.kind().clone()),
+                .map_err(|err| *err.kind()),
             int_error_kind
         );
     }
@@ -0,0 +1,490 @@
+use alloc::vec::Vec;
+
+use super::{ExpectedReturnCodes, Installer, InstallerReturnCode, InstallerType, ReturnResponse};
+
+/// A return code with a well-known meaning for an installer technology.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct KnownReturnCode {
+    /// The return code.
+    pub return_code: InstallerReturnCode,
+
+    /// The name of the return code, such as `ERROR_SUCCESS_REBOOT_REQUIRED`.
+    pub name: &'static str,
+
+    /// The response the return code corresponds to.
+    pub return_response: ReturnResponse,
+
+    /// Whether the Windows Package Manager recognises the return code without it being listed
+    /// in the [expected return codes] of a manifest.
+    ///
+    /// [expected return codes]: Installer::expected_return_codes
+    pub implicit: bool,
+}
+
+impl KnownReturnCode {
+    const fn new(
+        return_code: i32,
+        name: &'static str,
+        return_response: ReturnResponse,
+        implicit: bool,
+    ) -> Self {
+        Self {
+            return_code: InstallerReturnCode::from_i32(return_code).unwrap(),
+            name,
+            return_response,
+            implicit,
+        }
+    }
+
+    /// Returns `true` if the return code means that the installer succeeded, such as when a
+    /// reboot is required to finish.
+    #[must_use]
+    #[inline]
+    pub const fn is_success(&self) -> bool {
+        matches!(
+            self.return_response,
+            ReturnResponse::RebootRequiredToFinish | ReturnResponse::RebootInitiated
+        )
+    }
+}
+
+/// See <https://learn.microsoft.com/windows/win32/msi/error-codes>.
+const MSI: [KnownReturnCode; 14] = [
+    KnownReturnCode::new(112, "ERROR_DISK_FULL", ReturnResponse::DiskFull, true),
+    KnownReturnCode::new(
+        1601,
+        "ERROR_INSTALL_SERVICE_FAILURE",
+        ReturnResponse::ContactSupport,
+        true,
+    ),
+    KnownReturnCode::new(
+        1602,
+        "ERROR_INSTALL_USEREXIT",
+        ReturnResponse::CancelledByUser,
+        true,
+    ),
+    KnownReturnCode::new(
+        1603,
+        "ERROR_INSTALL_FAILURE",
+        ReturnResponse::ContactSupport,
+        false,
+    ),
+    KnownReturnCode::new(
+        1618,
+        "ERROR_INSTALL_ALREADY_RUNNING",
+        ReturnResponse::InstallInProgress,
+        true,
+    ),
+    KnownReturnCode::new(
+        1623,
+        "ERROR_INSTALL_LANGUAGE_UNSUPPORTED",
+        ReturnResponse::SystemNotSupported,
+        true,
+    ),
+    KnownReturnCode::new(
+        1625,
+        "ERROR_INSTALL_PACKAGE_REJECTED",
+        ReturnResponse::BlockedByPolicy,
+        true,
+    ),
+    KnownReturnCode::new(
+        1633,
+        "ERROR_INSTALL_PLATFORM_UNSUPPORTED",
+        ReturnResponse::SystemNotSupported,
+        true,
+    ),
+    KnownReturnCode::new(
+        1638,
+        "ERROR_PRODUCT_VERSION",
+        ReturnResponse::AlreadyInstalled,
+        true,
+    ),
+    KnownReturnCode::new(
+        1639,
+        "ERROR_INVALID_COMMAND_LINE",
+        ReturnResponse::InvalidParameter,
+        true,
+    ),
+    KnownReturnCode::new(
+        1641,
+        "ERROR_SUCCESS_REBOOT_INITIATED",
+        ReturnResponse::RebootInitiated,
+        true,
+    ),
+    KnownReturnCode::new(
+        1643,
+        "ERROR_PATCH_PACKAGE_REJECTED",
+        ReturnResponse::BlockedByPolicy,
+        true,
+    ),
+    KnownReturnCode::new(
+        1644,
+        "ERROR_INSTALL_TRANSFORM_REJECTED",
+        ReturnResponse::BlockedByPolicy,
+        true,
+    ),
+    KnownReturnCode::new(
+        3010,
+        "ERROR_SUCCESS_REBOOT_REQUIRED",
+        ReturnResponse::RebootRequiredToFinish,
+        true,
+    ),
+];
+
+/// See <https://jrsoftware.org/ishelp/index.php?topic=setupexitcodes>.
+const INNO: [KnownReturnCode; 7] = [
+    KnownReturnCode::new(
+        1,
+        "Setup failed to initialize",
+        ReturnResponse::ContactSupport,
+        false,
+    ),
+    KnownReturnCode::new(
+        2,
+        "Cancelled before installation",
+        ReturnResponse::CancelledByUser,
+        true,
+    ),
+    KnownReturnCode::new(
+        3,
+        "Fatal error while preparing",
+        ReturnResponse::ContactSupport,
+        false,
+    ),
+    KnownReturnCode::new(
+        4,
+        "Fatal error during installation",
+        ReturnResponse::ContactSupport,
+        false,
+    ),
+    KnownReturnCode::new(
+        5,
+        "Cancelled during installation",
+        ReturnResponse::CancelledByUser,
+        true,
+    ),
+    KnownReturnCode::new(
+        7,
+        "Preparing to install failed",
+        ReturnResponse::ContactSupport,
+        false,
+    ),
+    KnownReturnCode::new(
+        8,
+        "Preparing to install failed, restart needed",
+        ReturnResponse::RebootRequiredForInstall,
+        true,
+    ),
+];
+
+/// See <https://nsis.sourceforge.io/Docs/AppendixD.html#errorlevels>.
+const NULLSOFT: [KnownReturnCode; 2] = [
+    KnownReturnCode::new(1, "Aborted by user", ReturnResponse::CancelledByUser, false),
+    KnownReturnCode::new(
+        2,
+        "Aborted by script",
+        ReturnResponse::ContactSupport,
+        false,
+    ),
+];
+
+/// Return codes that executable installer frameworks such as InstallShield and Advanced
+/// Installer commonly pass through from Windows Installer.
+const EXE: [KnownReturnCode; 4] = [
+    KnownReturnCode::new(
+        1602,
+        "ERROR_INSTALL_USEREXIT",
+        ReturnResponse::CancelledByUser,
+        false,
+    ),
+    KnownReturnCode::new(
+        1618,
+        "ERROR_INSTALL_ALREADY_RUNNING",
+        ReturnResponse::InstallInProgress,
+        false,
+    ),
+    KnownReturnCode::new(
+        1641,
+        "ERROR_SUCCESS_REBOOT_INITIATED",
+        ReturnResponse::RebootInitiated,
+        false,
+    ),
+    KnownReturnCode::new(
+        3010,
+        "ERROR_SUCCESS_REBOOT_REQUIRED",
+        ReturnResponse::RebootRequiredToFinish,
+        false,
+    ),
+];
+
+impl InstallerType {
+    /// Returns the return codes with a well-known meaning for this installer type.
+    ///
+    /// MSI, WiX and Burn installers share the [Windows Installer error codes]. Executable
+    /// installers have no fixed return codes, so the codes that common frameworks pass through
+    /// from Windows Installer are returned instead.
+    ///
+    /// [Windows Installer error codes]: https://learn.microsoft.com/windows/win32/msi/error-codes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::installer::{InstallerType, ReturnResponse};
+    ///
+    /// let known = InstallerType::Msi
+    ///     .known_return_codes()
+    ///     .iter()
+    ///     .find(|known| known.return_code.to_bits() == 3010)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(known.name, "ERROR_SUCCESS_REBOOT_REQUIRED");
+    /// assert_eq!(known.return_response, ReturnResponse::RebootRequiredToFinish);
+    /// ```
+    #[must_use]
+    pub const fn known_return_codes(self) -> &'static [KnownReturnCode] {
+        match self {
+            Self::Msi | Self::Wix | Self::Burn => &MSI,
+            Self::Inno => &INNO,
+            Self::Nullsoft => &NULLSOFT,
+            Self::Exe => &EXE,
+            _ => &[],
+        }
+    }
+}
+
+/// A return code in an installer that contradicts its [known meaning].
+///
+/// [known meaning]: InstallerType::known_return_codes
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ReturnCodeConflict<'installer> {
+    /// A success code that is known to be a failure.
+    FailureAsSuccess {
+        success_code: InstallerReturnCode,
+        known: &'static KnownReturnCode,
+    },
+
+    /// An expected return code with a different response than the known response.
+    ///
+    /// Expected return codes with a [custom] response are not considered to conflict.
+    ///
+    /// [custom]: ReturnResponse::Custom
+    ResponseMismatch {
+        expected: &'installer ExpectedReturnCodes,
+        known: &'static KnownReturnCode,
+    },
+}
+
+impl Installer {
+    /// Returns the installer type, using the nested installer type of archives.
+    pub(super) fn effective_type(&self) -> Option<InstallerType> {
+        match self.r#type? {
+            InstallerType::Zip => self.nested_installer_type.map(InstallerType::from),
+            installer_type => Some(installer_type),
+        }
+    }
+
+    fn known_return_codes(&self) -> &'static [KnownReturnCode] {
+        self.effective_type()
+            .map_or(&[], InstallerType::known_return_codes)
+    }
+
+    /// Returns the [expected return codes] that are well known for the installer type but are
+    /// not yet listed in this installer.
+    ///
+    /// Return codes that the Windows Package Manager recognises implicitly, and return codes
+    /// already listed as success codes or expected return codes, are not suggested.
+    ///
+    /// [expected return codes]: Installer::expected_return_codes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::installer::{Installer, InstallerType, ReturnResponse};
+    ///
+    /// let installer = Installer {
+    ///     r#type: Some(InstallerType::Nullsoft),
+    ///     ..Installer::default()
+    /// };
+    ///
+    /// let suggestions = installer.suggested_expected_return_codes();
+    /// assert_eq!(suggestions.len(), 2);
+    /// assert_eq!(suggestions[0].return_response, ReturnResponse::CancelledByUser);
+    /// ```
+    #[must_use]
+    pub fn suggested_expected_return_codes(&self) -> Vec<ExpectedReturnCodes> {
+        self.known_return_codes()
+            .iter()
+            .filter(|known| !known.implicit && !self.lists_return_code(known.return_code))
+            .map(|known| ExpectedReturnCodes {
+                installer_return_code: Some(known.return_code),
+                return_response: known.return_response,
+                return_response_url: None,
+            })
+            .collect()
+    }
+
+    /// Returns the success codes and expected return codes of this installer that contradict
+    /// the well-known return codes of the installer type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::installer::{
+    ///     Installer, InstallerReturnCode, InstallerType, ReturnCodeConflict,
+    /// };
+    ///
+    /// let installer = Installer {
+    ///     r#type: Some(InstallerType::Msi),
+    ///     success_codes: [InstallerReturnCode::from_u32(1603).unwrap()].into(),
+    ///     ..Installer::default()
+    /// };
+    ///
+    /// assert!(matches!(
+    ///     installer.return_code_conflicts().as_slice(),
+    ///     [ReturnCodeConflict::FailureAsSuccess { known, .. }] if known.name == "ERROR_INSTALL_FAILURE"
+    /// ));
+    /// ```
+    #[must_use]
+    pub fn return_code_conflicts(&self) -> Vec<ReturnCodeConflict<'_>> {
+        let known_return_codes = self.known_return_codes();
+        let find = |return_code: InstallerReturnCode| {
+            known_return_codes
+                .iter()
+                .find(|known| known.return_code.to_bits() == return_code.to_bits())
+        };
+
+        let failures_as_success = self.success_codes.iter().filter_map(|&success_code| {
+            find(success_code)
+                .filter(|known| !known.is_success())
+                .map(|known| ReturnCodeConflict::FailureAsSuccess {
+                    success_code,
+                    known,
+                })
+        });
+
+        let response_mismatches = self
+            .expected_return_codes
+            .iter()
+            .filter(|expected| expected.return_response != ReturnResponse::Custom)
+            .filter_map(|expected| {
+                find(expected.installer_return_code?)
+                    .filter(|known| known.return_response != expected.return_response)
+                    .map(|known| ReturnCodeConflict::ResponseMismatch { expected, known })
+            });
+
+        failures_as_success.chain(response_mismatches).collect()
+    }
+
+    fn lists_return_code(&self, return_code: InstallerReturnCode) -> bool {
+        let bits = return_code.to_bits();
+        self.success_codes.iter().any(|code| code.to_bits() == bits)
+            || self.expected_return_codes.iter().any(|expected| {
+                expected
+                    .installer_return_code
+                    .is_some_and(|code| code.to_bits() == bits)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::ReturnCodeConflict;
+    use crate::installer::{
+        ExpectedReturnCodes, Installer, InstallerReturnCode, InstallerType, NestedInstallerType,
+        ReturnResponse,
+    };
+
+    #[rstest]
+    #[case(InstallerType::Msi)]
+    #[case(InstallerType::Wix)]
+    #[case(InstallerType::Burn)]
+    #[case(InstallerType::Inno)]
+    #[case(InstallerType::Nullsoft)]
+    #[case(InstallerType::Exe)]
+    fn known_return_codes_are_unique(#[case] installer_type: InstallerType) {
+        let known_return_codes = installer_type.known_return_codes();
+
+        assert!(!known_return_codes.is_empty());
+        assert!(
+            known_return_codes
+                .windows(2)
+                .all(|pair| pair[0].return_code < pair[1].return_code)
+        );
+    }
+
+    #[test]
+    fn suggestions_skip_listed_and_implicit() {
+        let installer = Installer {
+            r#type: Some(InstallerType::Zip),
+            nested_installer_type: Some(NestedInstallerType::Inno),
+            success_codes: [InstallerReturnCode::from_u32(1).unwrap()].into(),
+            ..Installer::default()
+        };
+
+        let suggested = installer
+            .suggested_expected_return_codes()
+            .into_iter()
+            .filter_map(|expected| expected.installer_return_code)
+            .filter_map(InstallerReturnCode::to_u32)
+            .collect::<alloc::vec::Vec<_>>();
+
+        assert_eq!(suggested, [3, 4, 7]);
+    }
+
+    #[test]
+    fn no_suggestions_without_type() {
+        assert!(
+            Installer::default()
+                .suggested_expected_return_codes()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn conflicts() {
+        let installer = Installer {
+            r#type: Some(InstallerType::Msi),
+            success_codes: [
+                InstallerReturnCode::from_u32(1602).unwrap(),
+                InstallerReturnCode::from_u32(3010).unwrap(),
+            ]
+            .into(),
+            expected_return_codes: [
+                ExpectedReturnCodes {
+                    installer_return_code: InstallerReturnCode::from_u32(1618),
+                    return_response: ReturnResponse::PackageInUse,
+                    return_response_url: None,
+                },
+                ExpectedReturnCodes {
+                    installer_return_code: InstallerReturnCode::from_u32(1638),
+                    return_response: ReturnResponse::Custom,
+                    return_response_url: None,
+                },
+                ExpectedReturnCodes {
+                    installer_return_code: InstallerReturnCode::from_u32(112),
+                    return_response: ReturnResponse::DiskFull,
+                    return_response_url: None,
+                },
+            ]
+            .into(),
+            ..Installer::default()
+        };
+
+        let conflicts = installer.return_code_conflicts();
+        assert_eq!(conflicts.len(), 2);
+        assert!(matches!(
+            conflicts[0],
+            ReturnCodeConflict::FailureAsSuccess { success_code, known }
+                if success_code.to_u32() == Some(1602)
+                    && known.return_response == ReturnResponse::CancelledByUser
+        ));
+        assert!(matches!(
+            conflicts[1],
+            ReturnCodeConflict::ResponseMismatch { expected, known }
+                if expected.return_response == ReturnResponse::PackageInUse
+                    && known.return_response == ReturnResponse::InstallInProgress
+        ));
+    }
+}
@@ -0,0 +1,518 @@
+use alloc::{string::String, vec, vec::Vec};
+
+use compact_str::CompactString;
+use package_family_name::PackageFamilyName;
+use thiserror::Error;
+
+use super::{
+    AppsAndFeaturesEntry, Installer, InstallerType, PortableCommandAlias, RepairBehavior,
+    switches::RepairSwitch,
+};
+use crate::shared::url::DecodedUrl;
+
+/// A single step of a [`MaintenancePlan`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum MaintenanceStep {
+    /// Runs `msiexec.exe` with the arguments, such as `/x {ProductCode}`.
+    Msiexec { arguments: Vec<CompactString> },
+
+    /// Runs the `ModifyPath` command of the Apps and Features entry with the product code, with
+    /// the arguments appended.
+    ArpModify {
+        product_code: String,
+        arguments: Option<RepairSwitch>,
+    },
+
+    /// Runs the `UninstallString` command of the Apps and Features entry with the product code,
+    /// with the arguments appended.
+    ///
+    /// The `QuietUninstallString` should be preferred when no arguments are given and it is
+    /// present.
+    ArpUninstall {
+        product_code: String,
+        arguments: Option<RepairSwitch>,
+    },
+
+    /// Downloads and runs the installer with the arguments.
+    RunInstaller {
+        url: DecodedUrl,
+        arguments: RepairSwitch,
+    },
+
+    /// Re-registers the installed MSIX package with the package family name.
+    RegisterMsixPackage {
+        package_family_name: PackageFamilyName<'static>,
+    },
+
+    /// Removes the installed MSIX package with the package family name.
+    RemoveMsixPackage {
+        package_family_name: PackageFamilyName<'static>,
+    },
+
+    /// Removes the command aliases that link to a portable package.
+    RemovePortableLinks { aliases: Vec<PortableCommandAlias> },
+
+    /// Removes the directory of a portable package, which is named after the product code.
+    RemovePortablePackage { product_code: String },
+
+    /// Removes the Apps and Features entry with the product code.
+    RemoveArpEntry { product_code: String },
+}
+
+/// The steps to repair or uninstall an installed package, in the order they should be run.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct MaintenancePlan {
+    steps: Vec<MaintenanceStep>,
+}
+
+impl MaintenancePlan {
+    /// Returns the steps of the plan.
+    #[must_use]
+    #[inline]
+    pub fn steps(&self) -> &[MaintenanceStep] {
+        &self.steps
+    }
+}
+
+impl IntoIterator for MaintenancePlan {
+    type Item = MaintenanceStep;
+    type IntoIter = vec::IntoIter<MaintenanceStep>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.steps.into_iter()
+    }
+}
+
+impl From<Vec<MaintenanceStep>> for MaintenancePlan {
+    #[inline]
+    fn from(steps: Vec<MaintenanceStep>) -> Self {
+        Self { steps }
+    }
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum MaintenanceError {
+    #[error("The installer type of the package is not known")]
+    MissingInstallerType,
+    #[error("The package has no product code")]
+    MissingProductCode,
+    #[error("The package has no package family name")]
+    MissingPackageFamilyName,
+    #[error("The installer has no repair behavior")]
+    MissingRepairBehavior,
+    #[error("The installer has no repair switch")]
+    MissingRepairSwitch,
+    #[error("Repairing a {0} installer is not supported")]
+    RepairNotSupported(InstallerType),
+}
+
+impl Installer {
+    /// Returns the steps to repair the package installed by this installer, in the same way as
+    /// `winget repair`.
+    ///
+    /// MSI and WiX packages are repaired with `msiexec /fa` and MSIX packages are re-registered.
+    /// Other installers are repaired according to their [repair behavior], with their [repair
+    /// switch]. Portable packages cannot be repaired.
+    ///
+    /// The observed Apps and Features entry provides the installer type and product code of the
+    /// installed package, falling back to those of the installer.
+    ///
+    /// [repair behavior]: Installer::repair_behavior
+    /// [repair switch]: super::InstallerSwitches::repair
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the installer type is not known or cannot be repaired, or if the
+    /// installer lacks what its repair method requires.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::installer::{
+    ///     AppsAndFeaturesEntry, Installer, InstallerType, MaintenanceStep,
+    /// };
+    ///
+    /// let installer = Installer {
+    ///     r#type: Some(InstallerType::Msi),
+    ///     ..Installer::default()
+    /// };
+    /// let entry = AppsAndFeaturesEntry::builder()
+    ///     .product_code("{00000000-0000-0000-0000-000000000000}")
+    ///     .build();
+    ///
+    /// let plan = installer.repair_plan(&entry).unwrap();
+    /// assert!(matches!(
+    ///     plan.steps(),
+    ///     [MaintenanceStep::Msiexec { arguments }] if arguments[0] == "/fa"
+    /// ));
+    /// ```
+    pub fn repair_plan(
+        &self,
+        entry: &AppsAndFeaturesEntry,
+    ) -> Result<MaintenancePlan, MaintenanceError> {
+        let step = match self.maintenance_type(entry)? {
+            InstallerType::Msi | InstallerType::Wix => {
+                Self::msiexec("/fa", self.maintenance_product_code(entry)?)
+            }
+            InstallerType::Msix | InstallerType::Appx => MaintenanceStep::RegisterMsixPackage {
+                package_family_name: self.maintenance_package_family_name()?,
+            },
+            installer_type @ (InstallerType::Portable
+            | InstallerType::Zip
+            | InstallerType::Pwa
+            | InstallerType::Font) => {
+                return Err(MaintenanceError::RepairNotSupported(installer_type));
+            }
+            _ => {
+                let repair_behavior = self
+                    .repair_behavior
+                    .ok_or(MaintenanceError::MissingRepairBehavior)?;
+                let arguments = self
+                    .switches
+                    .repair()
+                    .cloned()
+                    .ok_or(MaintenanceError::MissingRepairSwitch)?;
+
+                match repair_behavior {
+                    RepairBehavior::Modify => MaintenanceStep::ArpModify {
+                        product_code: self.maintenance_product_code(entry)?,
+                        arguments: Some(arguments),
+                    },
+                    RepairBehavior::Uninstaller => MaintenanceStep::ArpUninstall {
+                        product_code: self.maintenance_product_code(entry)?,
+                        arguments: Some(arguments),
+                    },
+                    RepairBehavior::Installer => MaintenanceStep::RunInstaller {
+                        url: self.url.clone(),
+                        arguments,
+                    },
+                }
+            }
+        };
+
+        Ok(vec![step].into())
+    }
+
+    /// Returns the steps to uninstall the package installed by this installer, in the same way
+    /// as `winget uninstall`.
+    ///
+    /// MSI and WiX packages are uninstalled with `msiexec /x`, MSIX packages are removed by their
+    /// package family name, and portable packages have their command aliases, directory and
+    /// Apps and Features entry removed. Other installers run the uninstall command of their Apps
+    /// and Features entry.
+    ///
+    /// The observed Apps and Features entry provides the installer type and product code of the
+    /// installed package, falling back to those of the installer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the installer type is not known, or if the product code or package
+    /// family name needed to identify the installed package is missing.
+    pub fn uninstall_plan(
+        &self,
+        entry: &AppsAndFeaturesEntry,
+    ) -> Result<MaintenancePlan, MaintenanceError> {
+        let steps = match self.maintenance_type(entry)? {
+            InstallerType::Msi | InstallerType::Wix => {
+                vec![Self::msiexec("/x", self.maintenance_product_code(entry)?)]
+            }
+            InstallerType::Msix | InstallerType::Appx => vec![MaintenanceStep::RemoveMsixPackage {
+                package_family_name: self.maintenance_package_family_name()?,
+            }],
+            InstallerType::Portable => {
+                let product_code = self.maintenance_product_code(entry)?;
+                vec![
+                    MaintenanceStep::RemovePortableLinks {
+                        aliases: self.portable_aliases(),
+                    },
+                    MaintenanceStep::RemovePortablePackage {
+                        product_code: product_code.clone(),
+                    },
+                    MaintenanceStep::RemoveArpEntry { product_code },
+                ]
+            }
+            _ => vec![MaintenanceStep::ArpUninstall {
+                product_code: self.maintenance_product_code(entry)?,
+                arguments: None,
+            }],
+        };
+
+        Ok(steps.into())
+    }
+
+    fn maintenance_type(
+        &self,
+        entry: &AppsAndFeaturesEntry,
+    ) -> Result<InstallerType, MaintenanceError> {
+        entry
+            .installer_type()
+            .or_else(|| self.effective_type())
+            .ok_or(MaintenanceError::MissingInstallerType)
+    }
+
+    fn maintenance_product_code(
+        &self,
+        entry: &AppsAndFeaturesEntry,
+    ) -> Result<String, MaintenanceError> {
+        entry
+            .product_code()
+            .or(self.product_code.as_deref())
+            .map(String::from)
+            .ok_or(MaintenanceError::MissingProductCode)
+    }
+
+    fn maintenance_package_family_name(
+        &self,
+    ) -> Result<PackageFamilyName<'static>, MaintenanceError> {
+        self.package_family_name
+            .clone()
+            .ok_or(MaintenanceError::MissingPackageFamilyName)
+    }
+
+    fn msiexec(operation: &str, product_code: String) -> MaintenanceStep {
+        MaintenanceStep::Msiexec {
+            arguments: vec![operation.into(), product_code.into()],
+        }
+    }
+
+    /// Returns the command aliases of a portable package.
+    fn portable_aliases(&self) -> Vec<PortableCommandAlias> {
+        self.portable_commands()
+            .into_iter()
+            .map(|(alias, _)| alias)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use rstest::rstest;
+
+    use super::{MaintenanceError, MaintenanceStep};
+    use crate::installer::{
+        AppsAndFeaturesEntry, Command, Installer, InstallerSwitches, InstallerType,
+        NestedInstallerFiles, NestedInstallerType, PortableCommandAlias, RepairBehavior,
+    };
+
+    const PRODUCT_CODE: &str = "{5F9F1B4B-7E7B-4A7E-9C3B-0F0E5A6B7C8D}";
+
+    fn entry() -> AppsAndFeaturesEntry {
+        AppsAndFeaturesEntry::builder()
+            .product_code(PRODUCT_CODE)
+            .build()
+    }
+
+    fn exe(repair_behavior: RepairBehavior) -> Installer {
+        Installer {
+            r#type: Some(InstallerType::Exe),
+            repair_behavior: Some(repair_behavior),
+            switches: InstallerSwitches::builder()
+                .repair("/repair".parse().unwrap())
+                .build(),
+            ..Installer::default()
+        }
+    }
+
+    #[rstest]
+    #[case(InstallerType::Msi, "/fa")]
+    #[case(InstallerType::Wix, "/fa")]
+    fn msi_repair(#[case] installer_type: InstallerType, #[case] operation: &str) {
+        let installer = Installer {
+            r#type: Some(installer_type),
+            ..Installer::default()
+        };
+
+        assert_eq!(
+            installer.repair_plan(&entry()).unwrap().steps(),
+            [MaintenanceStep::Msiexec {
+                arguments: vec![operation.into(), PRODUCT_CODE.into()],
+            }]
+        );
+    }
+
+    #[test]
+    fn repair_behavior() {
+        assert!(matches!(
+            exe(RepairBehavior::Modify).repair_plan(&entry()).unwrap().steps(),
+            [MaintenanceStep::ArpModify { product_code, arguments: Some(arguments) }]
+                if product_code == PRODUCT_CODE && arguments.contains("/repair")
+        ));
+        assert!(matches!(
+            exe(RepairBehavior::Uninstaller)
+                .repair_plan(&entry())
+                .unwrap()
+                .steps(),
+            [MaintenanceStep::ArpUninstall {
+                arguments: Some(_),
+                ..
+            }]
+        ));
+        assert!(matches!(
+            exe(RepairBehavior::Installer)
+                .repair_plan(&AppsAndFeaturesEntry::default())
+                .unwrap()
+                .steps(),
+            [MaintenanceStep::RunInstaller { .. }]
+        ));
+    }
+
+    #[test]
+    fn repair_errors() {
+        let installer = Installer {
+            switches: InstallerSwitches::builder().build(),
+            ..exe(RepairBehavior::Modify)
+        };
+        assert_eq!(
+            installer.repair_plan(&entry()),
+            Err(MaintenanceError::MissingRepairSwitch)
+        );
+
+        let installer = Installer {
+            repair_behavior: None,
+            ..exe(RepairBehavior::Modify)
+        };
+        assert_eq!(
+            installer.repair_plan(&entry()),
+            Err(MaintenanceError::MissingRepairBehavior)
+        );
+
+        assert_eq!(
+            exe(RepairBehavior::Modify).repair_plan(&AppsAndFeaturesEntry::default()),
+            Err(MaintenanceError::MissingProductCode)
+        );
+
+        let installer = Installer {
+            r#type: Some(InstallerType::Portable),
+            ..Installer::default()
+        };
+        assert_eq!(
+            installer.repair_plan(&entry()),
+            Err(MaintenanceError::RepairNotSupported(
+                InstallerType::Portable
+            ))
+        );
+    }
+
+    #[test]
+    fn msix() {
+        let installer = Installer {
+            r#type: Some(InstallerType::Msix),
+            package_family_name: Some("Microsoft.WindowsTerminal_8wekyb3d8bbwe".parse().unwrap()),
+            ..Installer::default()
+        };
+
+        assert!(matches!(
+            installer
+                .uninstall_plan(&AppsAndFeaturesEntry::default())
+                .unwrap()
+                .steps(),
+            [MaintenanceStep::RemoveMsixPackage { .. }]
+        ));
+        assert!(matches!(
+            installer
+                .repair_plan(&AppsAndFeaturesEntry::default())
+                .unwrap()
+                .steps(),
+            [MaintenanceStep::RegisterMsixPackage { .. }]
+        ));
+    }
+
+    #[test]
+    fn observed_installer_type() {
+        let installer = Installer {
+            r#type: Some(InstallerType::Burn),
+            ..Installer::default()
+        };
+        let entry = AppsAndFeaturesEntry::builder()
+            .product_code(PRODUCT_CODE)
+            .installer_type(InstallerType::Msi)
+            .build();
+
+        assert!(matches!(
+            installer.uninstall_plan(&entry).unwrap().steps(),
+            [MaintenanceStep::Msiexec { arguments }] if arguments[0] == "/x"
+        ));
+        assert!(matches!(
+            installer.uninstall_plan(&self::entry()).unwrap().steps(),
+            [MaintenanceStep::ArpUninstall {
+                arguments: None,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn portable() {
+        let installer = Installer {
+            r#type: Some(InstallerType::Portable),
+            url: "https://example.com/download/tool-x64.exe".parse().unwrap(),
+            ..Installer::default()
+        };
+        let entry = AppsAndFeaturesEntry::builder()
+            .product_code("Contoso.Tool_Microsoft.Winget.Source_8wekyb3d8bbwe")
+            .build();
+
+        let plan = installer.uninstall_plan(&entry).unwrap();
+        assert_eq!(
+            plan.steps()[0],
+            MaintenanceStep::RemovePortableLinks {
+                aliases: vec![PortableCommandAlias::new("tool-x64").unwrap()],
+            }
+        );
+        assert!(matches!(
+            &plan.steps()[1..],
+            [
+                MaintenanceStep::RemovePortablePackage { product_code },
+                MaintenanceStep::RemoveArpEntry { .. },
+            ] if product_code.starts_with("Contoso.Tool")
+        ));
+
+        let installer = Installer {
+            commands: [Command::new("tool").unwrap()].into(),
+            ..installer
+        };
+        assert_eq!(
+            installer.uninstall_plan(&entry).unwrap().steps()[0],
+            MaintenanceStep::RemovePortableLinks {
+                aliases: vec![PortableCommandAlias::new("tool").unwrap()],
+            }
+        );
+    }
+
+    #[test]
+    fn nested_portable() {
+        let installer = Installer {
+            r#type: Some(InstallerType::Zip),
+            nested_installer_type: Some(NestedInstallerType::Portable),
+            nested_installer_files: [
+                NestedInstallerFiles {
+                    relative_file_path: "bin\\first.EXE".parse().unwrap(),
+                    portable_command_alias: None,
+                },
+                NestedInstallerFiles {
+                    relative_file_path: "bin\\second.exe".parse().unwrap(),
+                    portable_command_alias: Some(PortableCommandAlias::new("two").unwrap()),
+                },
+            ]
+            .into(),
+            product_code: Some("Contoso.Tools".into()),
+            ..Installer::default()
+        };
+
+        assert_eq!(
+            installer
+                .uninstall_plan(&AppsAndFeaturesEntry::default())
+                .unwrap()
+                .steps()[0],
+            MaintenanceStep::RemovePortableLinks {
+                aliases: vec![
+                    PortableCommandAlias::new("first").unwrap(),
+                    PortableCommandAlias::new("two").unwrap(),
+                ],
+            }
+        );
+    }
+}
@@ -8,16 +8,21 @@ mod channel;
 mod command;
 mod dependencies;
 mod elevation_requirement;
+mod exit_code;
 mod expected_return_codes;
 mod file_extension;
+mod hresult;
 mod install_modes;
 mod installation_metadata;
 mod installer_return_code;
 mod installer_type;
+mod known_return_codes;
+mod maintenance;
 mod market;
 mod minimum_os_version;
 mod nested;
 mod platform;
+mod portable;
 mod protocol;
 mod repair_behavior;
 mod return_response;
@@ -35,23 +40,35 @@ pub use authentication::Authentication;
 pub use capability::{Capability, CapabilityError, RestrictedCapability};
 pub use channel::{Channel, ChannelError};
 pub use command::{Command, CommandError};
-pub use dependencies::{Dependencies, PackageDependencies};
+pub use dependencies::{
+    Dependencies, DependencyChain, DependencyError, DependencyResolver, PackageDependencies,
+    Requirement, Resolution, ResolvedPackage,
+};
 pub use elevation_requirement::ElevationRequirement;
+pub use exit_code::{InstallContext, InstallOutcome};
 pub use expected_return_codes::ExpectedReturnCodes;
 pub use file_extension::{FileExtension, FileExtensionError};
+pub use hresult::{Facility, HResult, KnownHResult, Severity};
 pub use install_modes::InstallModes;
-pub use installation_metadata::InstallationMetadata;
+#[cfg(feature = "std")]
+pub use installation_metadata::MetadataIssue;
+pub use installation_metadata::{InstallationMetadata, MetadataFileType, MetadataFiles};
 pub use installer_return_code::{InstallerReturnCode, InstallerSuccessCode};
 pub use installer_type::InstallerType;
 use itertools::Itertools;
+pub use known_return_codes::{KnownReturnCode, ReturnCodeConflict};
+pub use maintenance::{MaintenanceError, MaintenancePlan, MaintenanceStep};
 pub use market::{Market, MarketError, Markets, MarketsError};
 pub use minimum_os_version::{MinimumOSVersion, MinimumOSVersionError};
-use nested::installer_type::NestedInstallerType;
 pub use nested::{
     PortableCommandAlias, PortableCommandAliasError, installer_files::NestedInstallerFiles,
+    installer_type::NestedInstallerType,
 };
 pub use package_family_name::PackageFamilyName;
 pub use platform::{Platform, PlatformParseError};
+pub use portable::{
+    AliasConflict, PortableArpEntry, PortableCommand, PortableLayout, PortableLayoutError,
+};
 pub use protocol::{Protocol, ProtocolError};
 pub use repair_behavior::RepairBehavior;
 pub use return_response::ReturnResponse;
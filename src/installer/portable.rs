@@ -0,0 +1,572 @@
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use thiserror::Error;
+
+use super::{Architecture, Installer, InstallerType, PortableCommandAlias, Scope};
+use crate::{PackageIdentifier, utils::RelativeDir};
+
+const USER_ROOT: &str = r"%LocalAppData%\Microsoft\WinGet";
+
+const MACHINE_ROOT: &str = r"WinGet";
+
+const UNINSTALL_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Uninstall";
+
+const UNINSTALL_KEY_32: &str = r"Software\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall";
+
+/// The files, command aliases and Apps and Features entry that the Windows Package Manager
+/// creates when installing a portable package.
+///
+/// Directories are expressed with environment variables, such as `%LocalAppData%`, so that they
+/// can be expanded for a particular machine with an [`Environment`].
+///
+/// [`Environment`]: crate::utils::Environment
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PortableLayout {
+    /// The scope the package is installed in.
+    pub scope: Scope,
+
+    /// The directory the portable files are placed in, which is named after the product code.
+    pub install_directory: String,
+
+    /// The directory the command aliases are linked in, or `None` if the package directory is
+    /// added to `PATH` instead.
+    pub links_directory: Option<String>,
+
+    /// The directories that are added to `PATH`.
+    pub path_entries: Vec<String>,
+
+    /// The command aliases of the package and the files they invoke.
+    pub commands: Vec<PortableCommand>,
+
+    /// The Apps and Features entry that is written for the package.
+    pub arp_entry: PortableArpEntry,
+}
+
+/// A command alias of a portable package.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PortableCommand {
+    /// The name the package is invoked by.
+    pub alias: PortableCommandAlias,
+
+    /// The full path of the portable file.
+    pub target: String,
+
+    /// The full path of the symbolic link to the portable file, or `None` if the file is found
+    /// through `PATH`.
+    pub link: Option<String>,
+}
+
+/// The values of the Apps and Features entry of a portable package.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PortableArpEntry {
+    /// The full path of the registry key, including its hive.
+    pub key: String,
+
+    /// The product code, which is the name of the registry key.
+    pub product_code: String,
+
+    pub install_location: String,
+
+    pub uninstall_string: String,
+
+    pub quiet_uninstall_string: String,
+
+    pub package_identifier: PackageIdentifier,
+
+    pub source_identifier: String,
+
+    pub installer_type: InstallerType,
+
+    /// Whether [`install_location`] is added to `PATH` rather than linked.
+    ///
+    /// [`install_location`]: PortableArpEntry::install_location
+    pub archive_binaries_depend_on_path: bool,
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum PortableLayoutError {
+    #[error("A portable layout requires a portable installer but the installer type is {0}")]
+    NotPortable(InstallerType),
+    #[error("A portable layout requires a portable installer but the installer has no type")]
+    MissingInstallerType,
+    #[error("The portable installer has no file to install")]
+    MissingFile,
+}
+
+/// A command alias claimed by more than one package.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AliasConflict<'a> {
+    pub alias: &'a PortableCommandAlias,
+
+    /// The product codes of the packages that claim the alias.
+    pub product_codes: Vec<&'a str>,
+}
+
+impl fmt::Display for AliasConflict<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is claimed by ", self.alias)?;
+        for (index, product_code) in self.product_codes.iter().enumerate() {
+            if index != 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(product_code)?;
+        }
+        Ok(())
+    }
+}
+
+impl PortableLayout {
+    /// Returns the command aliases that are claimed by more than one of the given packages.
+    ///
+    /// Aliases are compared case-insensitively, as command names are on Windows. Aliases in
+    /// different scopes are also reported, as both the user and machine links directories are on
+    /// `PATH` and one would shadow the other.
+    #[must_use]
+    pub fn alias_conflicts<'a, I>(layouts: I) -> Vec<AliasConflict<'a>>
+    where
+        I: IntoIterator<Item = &'a Self>,
+    {
+        let mut claims = BTreeMap::<String, AliasConflict<'a>>::new();
+
+        for layout in layouts {
+            for command in &layout.commands {
+                let product_code = layout.arp_entry.product_code.as_str();
+                let claim = claims
+                    .entry(command.alias.as_str().to_uppercase())
+                    .or_insert_with(|| AliasConflict {
+                        alias: &command.alias,
+                        product_codes: Vec::new(),
+                    });
+                if !claim.product_codes.contains(&product_code) {
+                    claim.product_codes.push(product_code);
+                }
+            }
+        }
+
+        claims
+            .into_values()
+            .filter(|claim| claim.product_codes.len() > 1)
+            .collect()
+    }
+}
+
+impl Installer {
+    /// Returns the layout the Windows Package Manager creates when installing this portable
+    /// installer, or the portable files of this archive.
+    ///
+    /// The package is placed in a directory named after its product code,
+    /// `{PackageIdentifier}_{SourceIdentifier}`, under `%LocalAppData%\Microsoft\WinGet\Packages`
+    /// for the user scope or `%ProgramFiles%\WinGet\Packages` for the machine scope, or
+    /// `%ProgramFiles(x86)%\WinGet\Packages` for x86 machine installers. The user scope is assumed
+    /// if the installer has no scope. Each command alias is linked from the `Links` directory of
+    /// the scope, `%LocalAppData%\Microsoft\WinGet\Links` or `%ProgramFiles%\WinGet\Links`,
+    /// unless [`archive_binaries_depend_on_path`] is set, in which case the directories of the
+    /// portable files are added to `PATH` instead.
+    ///
+    /// [`archive_binaries_depend_on_path`]: Installer::archive_binaries_depend_on_path
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the installer is not a portable installer or an archive of portable
+    /// files, or if it has no file to install.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::{
+    ///     PackageIdentifier,
+    ///     installer::{Installer, InstallerType},
+    /// };
+    ///
+    /// let installer = Installer {
+    ///     r#type: Some(InstallerType::Portable),
+    ///     url: "https://example.com/tool.exe".parse().unwrap(),
+    ///     ..Installer::default()
+    /// };
+    /// let package_identifier = "Contoso.Tool".parse::<PackageIdentifier>().unwrap();
+    ///
+    /// let layout = installer
+    ///     .portable_layout(&package_identifier, "winget")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     layout.commands[0].link.as_deref(),
+    ///     Some(r"%LocalAppData%\Microsoft\WinGet\Links\tool.exe")
+    /// );
+    /// assert_eq!(
+    ///     layout.commands[0].target,
+    ///     r"%LocalAppData%\Microsoft\WinGet\Packages\Contoso.Tool_winget\tool.exe"
+    /// );
+    /// ```
+    pub fn portable_layout(
+        &self,
+        package_identifier: &PackageIdentifier,
+        source_identifier: &str,
+    ) -> Result<PortableLayout, PortableLayoutError> {
+        match self.effective_type() {
+            Some(InstallerType::Portable) => {}
+            Some(installer_type) => return Err(PortableLayoutError::NotPortable(installer_type)),
+            None => return Err(PortableLayoutError::MissingInstallerType),
+        }
+
+        let portable_commands = self.portable_commands();
+        if portable_commands.is_empty() {
+            return Err(PortableLayoutError::MissingFile);
+        }
+
+        let scope = self.scope.unwrap_or(Scope::User);
+        let (packages_root, links_root) = match scope {
+            Scope::User => (USER_ROOT.to_string(), USER_ROOT.to_string()),
+            Scope::Machine => {
+                // Machine links are shared by all architectures, so only the packages of x86
+                // installers are placed under the 32-bit Program Files directory
+                let links_root = format!(r"{}\{MACHINE_ROOT}", RelativeDir::PROGRAM_FILES_64);
+                let packages_root = if self.architecture == Architecture::X86 {
+                    format!(r"{}\{MACHINE_ROOT}", RelativeDir::PROGRAM_FILES_32)
+                } else {
+                    links_root.clone()
+                };
+                (packages_root, links_root)
+            }
+        };
+
+        let product_code = format!("{package_identifier}_{source_identifier}");
+        let install_directory = format!(r"{packages_root}\Packages\{product_code}");
+        let depends_on_path =
+            self.r#type == Some(InstallerType::Zip) && self.archive_binaries_depend_on_path;
+        let links_directory = (!depends_on_path).then(|| format!(r"{links_root}\Links"));
+
+        let commands = portable_commands
+            .into_iter()
+            .map(|(alias, target)| PortableCommand {
+                link: links_directory
+                    .as_ref()
+                    .map(|links| format!(r"{links}\{alias}.exe")),
+                target: format!(r"{install_directory}\{target}"),
+                alias,
+            })
+            .collect::<Vec<_>>();
+
+        let path_entries = match &links_directory {
+            Some(links) => Vec::from([links.clone()]),
+            None => {
+                let mut directories = Vec::<String>::new();
+                for command in &commands {
+                    let directory = command
+                        .target
+                        .rsplit_once('\\')
+                        .map_or(install_directory.as_str(), |(directory, _)| directory);
+                    if !directories
+                        .iter()
+                        .any(|existing| existing.eq_ignore_ascii_case(directory))
+                    {
+                        directories.push(directory.to_string());
+                    }
+                }
+                directories
+            }
+        };
+
+        let key = match scope {
+            Scope::User => format!(r"HKEY_CURRENT_USER\{UNINSTALL_KEY}\{product_code}"),
+            Scope::Machine if self.architecture == Architecture::X86 => {
+                format!(r"HKEY_LOCAL_MACHINE\{UNINSTALL_KEY_32}\{product_code}")
+            }
+            Scope::Machine => format!(r"HKEY_LOCAL_MACHINE\{UNINSTALL_KEY}\{product_code}"),
+        };
+        let uninstall_string = format!(r#"winget uninstall --product-code "{product_code}""#);
+
+        Ok(PortableLayout {
+            scope,
+            links_directory,
+            path_entries,
+            commands,
+            arp_entry: PortableArpEntry {
+                key,
+                quiet_uninstall_string: format!("{uninstall_string} --silent"),
+                uninstall_string,
+                product_code,
+                install_location: install_directory.clone(),
+                package_identifier: package_identifier.clone(),
+                source_identifier: source_identifier.to_string(),
+                installer_type: InstallerType::Portable,
+                archive_binaries_depend_on_path: depends_on_path,
+            },
+            install_directory,
+        })
+    }
+
+    /// Returns the command aliases of a portable package and the paths of the files they invoke,
+    /// relative to the package directory.
+    ///
+    /// A portable package is aliased by its first command, which its executable is renamed to, or
+    /// by the file name of its executable.
+    /// Each portable file in an archive is aliased by its portable command alias, or by its file
+    /// name.
+    pub(super) fn portable_commands(&self) -> Vec<(PortableCommandAlias, String)> {
+        if self.r#type == Some(InstallerType::Zip) {
+            return self
+                .nested_installer_files
+                .iter()
+                .filter_map(|file| {
+                    let alias = file.portable_command_alias.clone().or_else(|| {
+                        PortableCommandAlias::new(executable_stem(file.relative_file_path.as_str()))
+                            .ok()
+                    })?;
+                    Some((alias, file.relative_file_path.to_string()))
+                })
+                .collect();
+        }
+
+        // When the package has commands, the executable is renamed after the first of them
+        if let Some(command) = self.commands.first() {
+            return PortableCommandAlias::new(command.as_str())
+                .ok()
+                .map(|alias| {
+                    let target = format!("{alias}.exe");
+                    (alias, target)
+                })
+                .into_iter()
+                .collect();
+        }
+
+        self.url
+            .path_segments()
+            .and_then(Iterator::last)
+            .filter(|file_name| !file_name.is_empty())
+            .and_then(|file_name| {
+                let alias = PortableCommandAlias::new(executable_stem(file_name)).ok()?;
+                Some((alias, file_name.to_string()))
+            })
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Returns the file name of a path without its `.exe` extension.
+fn executable_stem(path: &str) -> &str {
+    let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    file_name
+        .len()
+        .checked_sub(".exe".len())
+        .filter(|&index| {
+            file_name.is_char_boundary(index) && file_name[index..].eq_ignore_ascii_case(".exe")
+        })
+        .map_or(file_name, |index| &file_name[..index])
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use rstest::rstest;
+
+    use super::{AliasConflict, PortableLayout, PortableLayoutError};
+    use crate::{
+        PackageIdentifier,
+        installer::{
+            Architecture, Command, Installer, InstallerType, NestedInstallerFiles,
+            NestedInstallerType, PortableCommandAlias, Scope,
+        },
+    };
+
+    fn portable() -> Installer {
+        Installer {
+            r#type: Some(InstallerType::Portable),
+            url: "https://example.com/download/tool-x64.exe".parse().unwrap(),
+            ..Installer::default()
+        }
+    }
+
+    fn archive(archive_binaries_depend_on_path: bool) -> Installer {
+        Installer {
+            r#type: Some(InstallerType::Zip),
+            nested_installer_type: Some(NestedInstallerType::Portable),
+            nested_installer_files: [
+                NestedInstallerFiles {
+                    relative_file_path: r"bin\first.EXE".parse().unwrap(),
+                    portable_command_alias: None,
+                },
+                NestedInstallerFiles {
+                    relative_file_path: r"bin\second.exe".parse().unwrap(),
+                    portable_command_alias: Some(PortableCommandAlias::new("two").unwrap()),
+                },
+            ]
+            .into(),
+            archive_binaries_depend_on_path,
+            ..Installer::default()
+        }
+    }
+
+    fn layout(installer: &Installer, package_identifier: &str) -> PortableLayout {
+        installer
+            .portable_layout(&package_identifier.parse().unwrap(), "winget")
+            .unwrap()
+    }
+
+    #[test]
+    fn user_portable() {
+        let installer = Installer {
+            commands: [Command::new("tool").unwrap()].into(),
+            ..portable()
+        };
+
+        let layout = layout(&installer, "Contoso.Tool");
+
+        assert_eq!(layout.scope, Scope::User);
+        assert_eq!(
+            layout.install_directory,
+            r"%LocalAppData%\Microsoft\WinGet\Packages\Contoso.Tool_winget"
+        );
+        assert_eq!(
+            layout.path_entries,
+            vec![r"%LocalAppData%\Microsoft\WinGet\Links"]
+        );
+        assert_eq!(layout.commands.len(), 1);
+        assert_eq!(layout.commands[0].alias.as_str(), "tool");
+        assert_eq!(
+            layout.commands[0].target,
+            r"%LocalAppData%\Microsoft\WinGet\Packages\Contoso.Tool_winget\tool.exe"
+        );
+        assert_eq!(
+            layout.commands[0].link.as_deref(),
+            Some(r"%LocalAppData%\Microsoft\WinGet\Links\tool.exe")
+        );
+        assert_eq!(
+            layout.arp_entry.key,
+            r"HKEY_CURRENT_USER\Software\Microsoft\Windows\CurrentVersion\Uninstall\Contoso.Tool_winget"
+        );
+        assert_eq!(
+            layout.arp_entry.uninstall_string,
+            r#"winget uninstall --product-code "Contoso.Tool_winget""#
+        );
+        assert_eq!(layout.arp_entry.installer_type, InstallerType::Portable);
+    }
+
+    #[rstest]
+    #[case(
+        Architecture::X64,
+        r"%ProgramFiles%\WinGet\Packages\Contoso.Tool_winget\tool-x64.exe",
+        r"HKEY_LOCAL_MACHINE\Software\Microsoft\Windows\CurrentVersion\Uninstall\Contoso.Tool_winget"
+    )]
+    #[case(
+        Architecture::X86,
+        r"%ProgramFiles(x86)%\WinGet\Packages\Contoso.Tool_winget\tool-x64.exe",
+        r"HKEY_LOCAL_MACHINE\Software\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall\Contoso.Tool_winget"
+    )]
+    fn machine_portable(
+        #[case] architecture: Architecture,
+        #[case] target: &str,
+        #[case] key: &str,
+    ) {
+        let installer = Installer {
+            scope: Some(Scope::Machine),
+            architecture,
+            ..portable()
+        };
+
+        let layout = layout(&installer, "Contoso.Tool");
+
+        assert_eq!(
+            layout.links_directory.as_deref(),
+            Some(r"%ProgramFiles%\WinGet\Links")
+        );
+        assert_eq!(
+            layout.commands[0].link.as_deref(),
+            Some(r"%ProgramFiles%\WinGet\Links\tool-x64.exe")
+        );
+        assert_eq!(layout.commands[0].target, target);
+        assert_eq!(layout.commands[0].alias.as_str(), "tool-x64");
+        assert_eq!(layout.arp_entry.key, key);
+    }
+
+    #[test]
+    fn linked_archive() {
+        let layout = layout(&archive(false), "Contoso.Tools");
+
+        assert_eq!(
+            layout
+                .commands
+                .iter()
+                .map(|command| (
+                    command.alias.as_str(),
+                    command.target.as_str(),
+                    command.link.as_deref()
+                ))
+                .collect::<vec::Vec<_>>(),
+            vec![
+                (
+                    "first",
+                    r"%LocalAppData%\Microsoft\WinGet\Packages\Contoso.Tools_winget\bin\first.EXE",
+                    Some(r"%LocalAppData%\Microsoft\WinGet\Links\first.exe")
+                ),
+                (
+                    "two",
+                    r"%LocalAppData%\Microsoft\WinGet\Packages\Contoso.Tools_winget\bin\second.exe",
+                    Some(r"%LocalAppData%\Microsoft\WinGet\Links\two.exe")
+                ),
+            ]
+        );
+        assert!(!layout.arp_entry.archive_binaries_depend_on_path);
+    }
+
+    #[test]
+    fn archive_depends_on_path() {
+        let layout = layout(&archive(true), "Contoso.Tools");
+
+        assert_eq!(layout.links_directory, None);
+        assert!(layout.commands.iter().all(|command| command.link.is_none()));
+        assert_eq!(
+            layout.path_entries,
+            vec![r"%LocalAppData%\Microsoft\WinGet\Packages\Contoso.Tools_winget\bin"]
+        );
+        assert!(layout.arp_entry.archive_binaries_depend_on_path);
+    }
+
+    #[test]
+    fn not_portable() {
+        let package_identifier = "Contoso.Tool".parse::<PackageIdentifier>().unwrap();
+        let installer = Installer {
+            r#type: Some(InstallerType::Zip),
+            nested_installer_type: Some(NestedInstallerType::Msi),
+            ..Installer::default()
+        };
+
+        assert_eq!(
+            installer.portable_layout(&package_identifier, "winget"),
+            Err(PortableLayoutError::NotPortable(InstallerType::Msi))
+        );
+        assert_eq!(
+            Installer::default().portable_layout(&package_identifier, "winget"),
+            Err(PortableLayoutError::MissingInstallerType)
+        );
+    }
+
+    #[test]
+    fn alias_conflicts() {
+        let tool = layout(
+            &Installer {
+                commands: [Command::new("First").unwrap()].into(),
+                ..portable()
+            },
+            "Contoso.Tool",
+        );
+        let tools = layout(&archive(false), "Contoso.Tools");
+        let other = layout(&portable(), "Fabrikam.Tool");
+
+        assert_eq!(
+            PortableLayout::alias_conflicts([&tool, &tools, &other, &tools]),
+            vec![AliasConflict {
+                alias: &tool.commands[0].alias,
+                product_codes: vec!["Contoso.Tool_winget", "Contoso.Tools_winget"],
+            }]
+        );
+        assert_eq!(PortableLayout::alias_conflicts([&tool, &other]), vec![]);
+    }
+}
@@ -10,6 +10,10 @@ pub use package_family_name;
 pub use sha2;
 pub use shared::*;
 
+pub mod analysis;
+#[cfg(feature = "fetch")]
+pub mod fetch;
+pub mod installed;
 pub mod installer;
 pub mod locale;
 mod shared;
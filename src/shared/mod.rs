@@ -5,6 +5,7 @@ mod manifest_type;
 mod manifest_version;
 mod package_identifier;
 mod package_version;
+mod relative_path;
 mod sha_256;
 pub mod url;
 mod version;
@@ -16,7 +17,8 @@ pub use manifest_type::{ManifestType, ManifestTypeWithLocale};
 pub use manifest_version::ManifestVersion;
 pub use package_identifier::{PackageIdentifier, PackageIdentifierError};
 pub use package_version::{PackageVersion, PackageVersionError};
-pub use sha_256::Sha256String;
-pub use version::Version;
+pub use relative_path::{RelativePath, RelativePathError};
+pub use sha_256::{Sha256Hasher, Sha256String, Sha256StringError};
+pub use version::{Version, VersionInterval, VersionRange, VersionRangeError};
 
 pub const DISALLOWED_CHARACTERS: [char; 9] = ['\\', '/', ':', '*', '?', '\"', '<', '>', '|'];
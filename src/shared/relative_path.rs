@@ -0,0 +1,417 @@
+use alloc::vec::Vec;
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use compact_str::CompactString;
+use thiserror::Error;
+
+const SEPARATOR: char = '\\';
+
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+const INVALID_CHARACTERS: [char; 7] = ['<', '>', ':', '"', '|', '?', '*'];
+
+/// A relative path with Windows semantics, such as a file within an archive or an install
+/// location.
+///
+/// The path behaves the same regardless of the host operating system or whether the `std`
+/// feature is enabled:
+///
+/// - Both `\` and `/` are separators, and the path is stored with `\`.
+/// - Empty and `.` components are removed, and `..` components are resolved against the
+///   preceding component.
+/// - Paths are compared case-insensitively, like paths on NTFS.
+///
+/// # Examples
+///
+/// ```
+/// use winget_types::RelativePath;
+///
+/// # fn main() -> Result<(), winget_types::RelativePathError> {
+/// let path = "bin/./tools/../App.exe".parse::<RelativePath>()?;
+///
+/// assert_eq!(path.as_str(), r"bin\App.exe");
+/// assert_eq!(path, r"BIN\app.EXE".parse::<RelativePath>()?);
+/// assert_eq!(path.file_name(), "App.exe");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "CompactString"))]
+#[repr(transparent)]
+pub struct RelativePath(CompactString);
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum RelativePathError {
+    #[error("Relative path must not be empty")]
+    Empty,
+    #[error(
+        "Relative path must not have more than {} characters but has {_0}",
+        RelativePath::MAX_CHAR_LENGTH
+    )]
+    TooLong(usize),
+    #[error("Relative path must not be absolute")]
+    Absolute,
+    #[error("Relative path must not refer to a location outside of its base directory")]
+    Escapes,
+    #[error("Relative path must not contain `{_0}`")]
+    InvalidCharacter(char),
+    #[error("`{_0}` is a reserved name on Windows")]
+    ReservedName(CompactString),
+    #[error("Path component `{_0}` must not end with a period or space")]
+    TrailingPeriodOrSpace(CompactString),
+}
+
+impl RelativePath {
+    pub const MAX_CHAR_LENGTH: usize = 512;
+
+    /// Creates a new `RelativePath`, normalizing its separators and `.` and `..` components.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the path is empty, too long, absolute, refers to a location outside
+    /// its base directory, or has a component that is not a valid Windows file name.
+    pub fn new<T: AsRef<str>>(path: T) -> Result<Self, RelativePathError> {
+        let path = path.as_ref();
+
+        let char_count = path.chars().count();
+        if char_count > Self::MAX_CHAR_LENGTH {
+            return Err(RelativePathError::TooLong(char_count));
+        }
+
+        if path.starts_with(['\\', '/']) || path.as_bytes().get(1) == Some(&b':') {
+            return Err(RelativePathError::Absolute);
+        }
+
+        let mut components = Vec::new();
+        for component in path.split(['\\', '/']) {
+            match component {
+                "" | "." => {}
+                ".." => {
+                    components.pop().ok_or(RelativePathError::Escapes)?;
+                }
+                component => {
+                    validate_component(component)?;
+                    components.push(component);
+                }
+            }
+        }
+
+        if components.is_empty() {
+            return Err(RelativePathError::Empty);
+        }
+
+        let mut normalized = CompactString::with_capacity(path.len());
+        for (index, component) in components.into_iter().enumerate() {
+            if index != 0 {
+                normalized.push(SEPARATOR);
+            }
+            normalized.push_str(component);
+        }
+
+        Ok(Self(normalized))
+    }
+
+    /// Extracts a string slice containing the entire `RelativePath`, using `\` as the separator.
+    #[must_use]
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Returns an iterator over the components of the path.
+    pub fn components(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.0.split(SEPARATOR)
+    }
+
+    /// Returns the last component of the path.
+    #[must_use]
+    pub fn file_name(&self) -> &str {
+        self.0
+            .rsplit_once(SEPARATOR)
+            .map_or(self.as_str(), |(_, file_name)| file_name)
+    }
+
+    /// Returns the file name without its extension.
+    #[must_use]
+    pub fn file_stem(&self) -> &str {
+        let file_name = self.file_name();
+        file_name
+            .rsplit_once('.')
+            .filter(|(stem, _)| !stem.is_empty())
+            .map_or(file_name, |(stem, _)| stem)
+    }
+
+    /// Returns the extension of the file name, if any.
+    #[must_use]
+    pub fn extension(&self) -> Option<&str> {
+        self.file_name()
+            .rsplit_once('.')
+            .filter(|(stem, _)| !stem.is_empty())
+            .map(|(_, extension)| extension)
+    }
+
+    /// Returns the path without its last component, or `None` if it only has one component.
+    #[must_use]
+    pub fn parent(&self) -> Option<Self> {
+        self.0
+            .rsplit_once(SEPARATOR)
+            .map(|(parent, _)| Self(parent.into()))
+    }
+
+    /// Creates a new path by appending a path to this one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the joined path is too long or refers to a location outside of its
+    /// base directory.
+    pub fn join<T: AsRef<str>>(&self, path: T) -> Result<Self, RelativePathError> {
+        let path = path.as_ref();
+        if path.starts_with(['\\', '/']) || path.as_bytes().get(1) == Some(&b':') {
+            return Err(RelativePathError::Absolute);
+        }
+
+        let mut joined = CompactString::with_capacity(self.0.len() + 1 + path.len());
+        joined.push_str(&self.0);
+        joined.push(SEPARATOR);
+        joined.push_str(path);
+
+        Self::new(joined)
+    }
+
+    /// Returns `true` if the path starts with the components of the base path.
+    #[must_use]
+    pub fn starts_with(&self, base: &Self) -> bool {
+        let mut components = self.components();
+        base.components().all(|base| {
+            components
+                .next()
+                .is_some_and(|component| eq_ignore_case(component, base))
+        })
+    }
+
+    fn folded(&self) -> impl Iterator<Item = char> + '_ {
+        self.0.chars().map(upcase)
+    }
+}
+
+fn validate_component(component: &str) -> Result<(), RelativePathError> {
+    if let Some(invalid) = component
+        .chars()
+        .find(|char| INVALID_CHARACTERS.contains(char) || char.is_control())
+    {
+        return Err(RelativePathError::InvalidCharacter(invalid));
+    }
+
+    if component.ends_with(['.', ' ']) {
+        return Err(RelativePathError::TrailingPeriodOrSpace(component.into()));
+    }
+
+    let stem = component
+        .split_once('.')
+        .map_or(component, |(stem, _)| stem)
+        .trim_end();
+    if RESERVED_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(stem))
+    {
+        return Err(RelativePathError::ReservedName(component.into()));
+    }
+
+    Ok(())
+}
+
+/// Maps a character to its uppercase form if that is a single character, like the one-to-one
+/// upcase table that NTFS and Windows compare paths with.
+///
+/// Characters whose uppercase form expands to several characters, such as `ß`, are unchanged, so
+/// `straße` and `STRASSE` are different names.
+fn upcase(char: char) -> char {
+    let mut upper = char.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(upper), None) => upper,
+        _ => char,
+    }
+}
+
+fn eq_ignore_case(left: &str, right: &str) -> bool {
+    left.chars().map(upcase).eq(right.chars().map(upcase))
+}
+
+impl PartialEq for RelativePath {
+    fn eq(&self, other: &Self) -> bool {
+        eq_ignore_case(self.as_str(), other.as_str())
+    }
+}
+
+impl Eq for RelativePath {}
+
+impl PartialOrd for RelativePath {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RelativePath {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.folded().cmp(other.folded())
+    }
+}
+
+impl Hash for RelativePath {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for char in self.folded() {
+            char.hash(state);
+        }
+    }
+}
+
+impl AsRef<str> for RelativePath {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for RelativePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for RelativePath {
+    type Err = RelativePathError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<CompactString> for RelativePath {
+    type Error = RelativePathError;
+
+    #[inline]
+    fn try_from(value: CompactString) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{RelativePath, RelativePathError};
+
+    #[rstest]
+    #[case("app.exe", "app.exe")]
+    #[case("bin/app.exe", r"bin\app.exe")]
+    #[case(r"bin\\tools/./app.exe", r"bin\tools\app.exe")]
+    #[case(r"bin\tools\..\app.exe", r"bin\app.exe")]
+    #[case(r".\bin\", "bin")]
+    #[case("Console.exe", "Console.exe")]
+    fn normalize(#[case] path: &str, #[case] expected: &str) {
+        assert_eq!(RelativePath::new(path).unwrap().as_str(), expected);
+    }
+
+    #[rstest]
+    #[case("", RelativePathError::Empty)]
+    #[case(r".\", RelativePathError::Empty)]
+    #[case(r"\bin\app.exe", RelativePathError::Absolute)]
+    #[case("/bin/app.exe", RelativePathError::Absolute)]
+    #[case(r"C:\app.exe", RelativePathError::Absolute)]
+    #[case("C:app.exe", RelativePathError::Absolute)]
+    #[case(r"\\server\share\app.exe", RelativePathError::Absolute)]
+    #[case(r"..\app.exe", RelativePathError::Escapes)]
+    #[case(r"bin\..\..\app.exe", RelativePathError::Escapes)]
+    #[case("bin/app?.exe", RelativePathError::InvalidCharacter('?'))]
+    #[case("bin\0", RelativePathError::InvalidCharacter('\0'))]
+    #[case(r"CON", RelativePathError::ReservedName("CON".into()))]
+    #[case(r"bin\nul.txt", RelativePathError::ReservedName("nul.txt".into()))]
+    #[case(r"bin.\app.exe", RelativePathError::TrailingPeriodOrSpace("bin.".into()))]
+    #[case("app.exe ", RelativePathError::TrailingPeriodOrSpace("app.exe ".into()))]
+    fn invalid(#[case] path: &str, #[case] expected: RelativePathError) {
+        assert_eq!(RelativePath::new(path), Err(expected));
+    }
+
+    #[test]
+    fn too_long() {
+        let path = "a".repeat(RelativePath::MAX_CHAR_LENGTH + 1);
+
+        assert_eq!(
+            RelativePath::new(path),
+            Err(RelativePathError::TooLong(
+                RelativePath::MAX_CHAR_LENGTH + 1
+            ))
+        );
+    }
+
+    #[test]
+    fn case_insensitive() {
+        use core::hash::BuildHasher;
+
+        use rustc_hash::FxBuildHasher;
+
+        let lower = RelativePath::new(r"bin\café.exe").unwrap();
+        let upper = RelativePath::new("BIN/CAFÉ.EXE").unwrap();
+
+        assert_eq!(lower, upper);
+        assert_eq!(lower.cmp(&upper), core::cmp::Ordering::Equal);
+        assert_eq!(
+            FxBuildHasher.hash_one(&lower),
+            FxBuildHasher.hash_one(&upper)
+        );
+    }
+
+    #[test]
+    fn multi_char_uppercase_is_not_folded() {
+        let lower = RelativePath::new(r"bin\straße.exe").unwrap();
+        let upper = RelativePath::new("BIN/STRASSE.EXE").unwrap();
+
+        assert_ne!(lower, upper);
+        assert_ne!(lower.cmp(&upper), core::cmp::Ordering::Equal);
+        assert!(
+            !RelativePath::new(r"straße\app.exe")
+                .unwrap()
+                .starts_with(&RelativePath::new("STRASSE").unwrap())
+        );
+    }
+
+    #[test]
+    fn components() {
+        let path = RelativePath::new(r"bin\tools\App.tar.gz").unwrap();
+
+        assert_eq!(path.file_name(), "App.tar.gz");
+        assert_eq!(path.file_stem(), "App.tar");
+        assert_eq!(path.extension(), Some("gz"));
+        assert_eq!(path.parent(), RelativePath::new(r"bin\tools").ok());
+        assert!(path.starts_with(&RelativePath::new("BIN").unwrap()));
+        assert!(!path.starts_with(&RelativePath::new("bi").unwrap()));
+        assert_eq!(
+            path.parent().unwrap().join("other.exe"),
+            RelativePath::new(r"bin\tools\other.exe")
+        );
+        assert_eq!(path.join(r"..\..\..\.."), Err(RelativePathError::Escapes));
+        assert_eq!(RelativePath::new(".gitignore").unwrap().extension(), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        assert_eq!(
+            serde_yaml::from_str::<RelativePath>("bin/app.exe")
+                .unwrap()
+                .as_str(),
+            r"bin\app.exe"
+        );
+        assert!(serde_yaml::from_str::<RelativePath>("../app.exe").is_err());
+    }
+}
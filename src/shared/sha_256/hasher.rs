@@ -0,0 +1,392 @@
+use core::fmt;
+
+use sha2::{Digest, Sha256};
+
+use super::Sha256String;
+
+/// The size of the buffer that readers are hashed through.
+#[cfg(feature = "std")]
+const BUFFER_SIZE: usize = 1 << 16;
+
+/// An incremental Sha256 hasher that produces a [`Sha256String`].
+///
+/// Data can be hashed in chunks as it arrives, such as while an installer is being downloaded.
+/// With the `std` feature, the hasher also implements `Write`, and can hash readers while
+/// reporting progress and optionally copying the data to a writer, such as a file, in the same
+/// pass. Async readers are supported with the `tokio` and `futures` features.
+///
+/// # Examples
+///
+/// ```
+/// use winget_types::{Sha256Hasher, Sha256String};
+///
+/// let mut hasher = Sha256Hasher::new();
+/// hasher.update(b"a");
+/// hasher.update(b"bc");
+///
+/// assert_eq!(hasher.bytes_hashed(), 3);
+/// assert_eq!(
+///     hasher.finalize().to_string(),
+///     "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD"
+/// );
+/// ```
+#[derive(Clone, Default)]
+pub struct Sha256Hasher {
+    hasher: Sha256,
+    bytes_hashed: u64,
+}
+
+impl Sha256Hasher {
+    /// Creates a new `Sha256Hasher`.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hashes a chunk of data.
+    pub fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+        self.bytes_hashed += data.len() as u64;
+    }
+
+    /// Returns the number of bytes that have been hashed so far.
+    #[must_use]
+    #[inline]
+    pub const fn bytes_hashed(&self) -> u64 {
+        self.bytes_hashed
+    }
+
+    /// Consumes the hasher, returning the hash of all the data that was hashed.
+    #[must_use]
+    pub fn finalize(self) -> Sha256String {
+        Sha256String::from_digest(&self.hasher.finalize())
+    }
+
+    /// Hashes all the data from a reader until EOF, returning the number of bytes read.
+    ///
+    /// `progress` is called after each chunk with the total number of bytes hashed so far.
+    ///
+    /// # Errors
+    ///
+    /// Returns the propagated `Err` from [`io::read`]. Reads that are [interrupted] are retried.
+    ///
+    /// [`io::read`]: std::io::Read::read
+    /// [interrupted]: std::io::ErrorKind::Interrupted
+    #[cfg(feature = "std")]
+    pub fn update_from_reader<R, F>(&mut self, reader: R, progress: F) -> std::io::Result<u64>
+    where
+        R: std::io::Read,
+        F: FnMut(u64),
+    {
+        self.tee_reader(reader, std::io::sink(), progress)
+    }
+
+    /// Hashes all the data from a reader until EOF while writing it to `writer`, returning the
+    /// number of bytes read.
+    ///
+    /// This allows a download to be saved to a file and hashed in a single pass. `progress` is
+    /// called after each chunk with the total number of bytes hashed so far.
+    ///
+    /// # Errors
+    ///
+    /// Returns the propagated `Err` from [`io::read`] or [`io::write_all`]. Reads that are
+    /// [interrupted] are retried.
+    ///
+    /// [`io::read`]: std::io::Read::read
+    /// [`io::write_all`]: std::io::Write::write_all
+    /// [interrupted]: std::io::ErrorKind::Interrupted
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::{fs::File, io};
+    ///
+    /// use winget_types::Sha256Hasher;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let download = File::open("download.part")?;
+    ///     let installer = File::create("installer.exe")?;
+    ///
+    ///     let mut hasher = Sha256Hasher::new();
+    ///     hasher.tee_reader(download, installer, |bytes| println!("{bytes} bytes"))?;
+    ///
+    ///     println!("Installer SHA256 hash: {}", hasher.finalize());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn tee_reader<R, W, F>(
+        &mut self,
+        mut reader: R,
+        mut writer: W,
+        mut progress: F,
+    ) -> std::io::Result<u64>
+    where
+        R: std::io::Read,
+        W: std::io::Write,
+        F: FnMut(u64),
+    {
+        let mut buffer = alloc::vec![0; BUFFER_SIZE];
+        let start = self.bytes_hashed;
+
+        loop {
+            let count = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(count) => count,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            writer.write_all(&buffer[..count])?;
+            self.update(&buffer[..count]);
+            progress(self.bytes_hashed);
+        }
+
+        writer.flush()?;
+        Ok(self.bytes_hashed - start)
+    }
+
+    /// Hashes all the data from a [Tokio async reader] until EOF, returning the number of bytes
+    /// read.
+    ///
+    /// `progress` is called after each chunk with the total number of bytes hashed so far.
+    ///
+    /// [Tokio async reader]: tokio::io::AsyncRead
+    ///
+    /// # Errors
+    ///
+    /// Returns the propagated `Err` from reading.
+    #[cfg(feature = "tokio")]
+    pub async fn update_from_tokio_reader<R, F>(
+        &mut self,
+        reader: R,
+        progress: F,
+    ) -> std::io::Result<u64>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+        F: FnMut(u64),
+    {
+        self.tee_tokio_reader(reader, tokio::io::sink(), progress)
+            .await
+    }
+
+    /// Hashes all the data from a [Tokio async reader] until EOF while writing it to `writer`,
+    /// returning the number of bytes read.
+    ///
+    /// `progress` is called after each chunk with the total number of bytes hashed so far.
+    ///
+    /// [Tokio async reader]: tokio::io::AsyncRead
+    ///
+    /// # Errors
+    ///
+    /// Returns the propagated `Err` from reading or writing.
+    #[cfg(feature = "tokio")]
+    pub async fn tee_tokio_reader<R, W, F>(
+        &mut self,
+        mut reader: R,
+        mut writer: W,
+        mut progress: F,
+    ) -> std::io::Result<u64>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+        W: tokio::io::AsyncWrite + Unpin,
+        F: FnMut(u64),
+    {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let mut buffer = alloc::vec![0; BUFFER_SIZE];
+        let start = self.bytes_hashed;
+
+        loop {
+            let count = match reader.read(&mut buffer).await {
+                Ok(0) => break,
+                Ok(count) => count,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            writer.write_all(&buffer[..count]).await?;
+            self.update(&buffer[..count]);
+            progress(self.bytes_hashed);
+        }
+
+        writer.flush().await?;
+        Ok(self.bytes_hashed - start)
+    }
+
+    /// Hashes all the data from a [futures async reader] until EOF, returning the number of
+    /// bytes read.
+    ///
+    /// `progress` is called after each chunk with the total number of bytes hashed so far.
+    ///
+    /// [futures async reader]: futures_util::io::AsyncRead
+    ///
+    /// # Errors
+    ///
+    /// Returns the propagated `Err` from reading.
+    #[cfg(feature = "futures")]
+    pub async fn update_from_async_reader<R, F>(
+        &mut self,
+        reader: R,
+        progress: F,
+    ) -> std::io::Result<u64>
+    where
+        R: futures_util::io::AsyncRead + Unpin,
+        F: FnMut(u64),
+    {
+        self.tee_async_reader(reader, futures_util::io::sink(), progress)
+            .await
+    }
+
+    /// Hashes all the data from a [futures async reader] until EOF while writing it to
+    /// `writer`, returning the number of bytes read.
+    ///
+    /// `progress` is called after each chunk with the total number of bytes hashed so far.
+    ///
+    /// [futures async reader]: futures_util::io::AsyncRead
+    ///
+    /// # Errors
+    ///
+    /// Returns the propagated `Err` from reading or writing.
+    #[cfg(feature = "futures")]
+    pub async fn tee_async_reader<R, W, F>(
+        &mut self,
+        mut reader: R,
+        mut writer: W,
+        mut progress: F,
+    ) -> std::io::Result<u64>
+    where
+        R: futures_util::io::AsyncRead + Unpin,
+        W: futures_util::io::AsyncWrite + Unpin,
+        F: FnMut(u64),
+    {
+        use futures_util::io::{AsyncReadExt, AsyncWriteExt};
+
+        let mut buffer = alloc::vec![0; BUFFER_SIZE];
+        let start = self.bytes_hashed;
+
+        loop {
+            let count = match reader.read(&mut buffer).await {
+                Ok(0) => break,
+                Ok(count) => count,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            writer.write_all(&buffer[..count]).await?;
+            self.update(&buffer[..count]);
+            progress(self.bytes_hashed);
+        }
+
+        writer.flush().await?;
+        Ok(self.bytes_hashed - start)
+    }
+}
+
+impl fmt::Debug for Sha256Hasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sha256Hasher")
+            .field("bytes_hashed", &self.bytes_hashed)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for Sha256Hasher {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::Sha256Hasher;
+
+    const ABC_SHA256: &str = "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD";
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            Sha256Hasher::new().finalize().to_string(),
+            "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855"
+        );
+    }
+
+    #[test]
+    fn incremental() {
+        let mut hasher = Sha256Hasher::new();
+        for byte in b"abc" {
+            hasher.update(&[*byte]);
+        }
+
+        assert_eq!(hasher.bytes_hashed(), 3);
+        assert_eq!(hasher.finalize().to_string(), ABC_SHA256);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn tee_reader() {
+        use alloc::vec::Vec;
+
+        let data = alloc::vec![0x5A; super::BUFFER_SIZE * 2 + 1];
+        let mut copy = Vec::new();
+        let mut progress = Vec::new();
+
+        let mut hasher = Sha256Hasher::new();
+        let count = hasher
+            .tee_reader(data.as_slice(), &mut copy, |bytes| progress.push(bytes))
+            .unwrap();
+
+        assert_eq!(count, data.len() as u64);
+        assert_eq!(copy, data);
+        assert_eq!(
+            progress,
+            [
+                super::BUFFER_SIZE as u64,
+                super::BUFFER_SIZE as u64 * 2,
+                data.len() as u64
+            ]
+        );
+        assert_eq!(
+            hasher.finalize(),
+            crate::Sha256String::hash_from_reader(data.as_slice()).unwrap()
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn tokio_reader() {
+        let mut hasher = Sha256Hasher::new();
+        let mut copy = alloc::vec::Vec::new();
+
+        let count = hasher
+            .tee_tokio_reader(&b"abc"[..], &mut copy, |_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(count, 3);
+        assert_eq!(copy, b"abc");
+        assert_eq!(hasher.finalize().to_string(), ABC_SHA256);
+    }
+
+    #[cfg(feature = "futures")]
+    #[tokio::test]
+    async fn futures_reader() {
+        let mut hasher = Sha256Hasher::new();
+        hasher.update(b"a");
+
+        let count = hasher
+            .update_from_async_reader(&b"bc"[..], |_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(count, 2);
+        assert_eq!(hasher.bytes_hashed(), 3);
+        assert_eq!(hasher.finalize().to_string(), ABC_SHA256);
+    }
+}
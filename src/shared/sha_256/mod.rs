@@ -0,0 +1,305 @@
+mod hasher;
+
+use core::{fmt, str::FromStr};
+
+use compact_str::CompactString;
+pub use hasher::Sha256Hasher;
+use sha2::{Sha256, digest::Output};
+use thiserror::Error;
+
+const SHA256_BYTES: usize = 256 / u8::BITS as usize;
+
+// 256 bits / 4 bits per hex character
+const SHA256_LEN: usize = SHA256_BYTES * 2;
+
+/// A Sha256 hash, such as the hash of an installer.
+///
+/// The hash is stored as its 32 raw bytes. It is parsed from exactly 64 hexadecimal characters in
+/// any case, and is always displayed in uppercase.
+///
+/// # Examples
+///
+/// ```
+/// use winget_types::Sha256String;
+///
+/// # fn main() -> Result<(), winget_types::Sha256StringError> {
+/// let hash = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+///     .parse::<Sha256String>()?;
+///
+/// assert_eq!(
+///     hash.to_string(),
+///     "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD"
+/// );
+/// assert_eq!(hash.as_bytes()[..2], [0xBA, 0x78]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "CompactString"))]
+pub struct Sha256String([u8; SHA256_BYTES]);
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum Sha256StringError {
+    #[error("Sha256 hash must have {SHA256_LEN} hexadecimal characters but has {_0}")]
+    InvalidLength(usize),
+    #[error("Sha256 hash contains non-hexadecimal character `{_0}`")]
+    InvalidCharacter(char),
+}
+
+impl Sha256String {
+    /// Creates a `Sha256String` from a Sha256 digest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::{sha2::{Digest, Sha256}, Sha256String};
+    ///
+    /// // Digest some data manually
+    /// let sha256_digest = Sha256::digest("abc");
+    ///
+    /// assert_eq!(
+    ///     Sha256String::from_digest(&sha256_digest).to_string(),
+    ///     "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn from_digest(digest: &Output<Sha256>) -> Self {
+        let mut bytes = [0; SHA256_BYTES];
+        bytes.copy_from_slice(digest);
+        Self(bytes)
+    }
+
+    /// Creates a `Sha256String` by hashing data from a reader.
+    ///
+    /// This will repeatedly read the data into a buffer of 64 KiB. Use a [`Sha256Hasher`] to
+    /// report progress or to copy the data elsewhere while hashing.
+    ///
+    /// # Errors
+    ///
+    /// Returns the propagated `Err` from [`io::read`].
+    ///
+    /// # Examples
+    ///
+    /// [`File`]s implement `Read`:
+    ///
+    /// [`File`]: std::fs::File
+    /// [`io::read`]: std::io::Read::read
+    ///
+    /// ```no_run
+    /// use std::io;
+    /// use std::fs::File;
+    ///
+    /// use winget_types::Sha256String;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut f = File::open("foo.txt")?;
+    ///
+    ///     let sha256_string = Sha256String::hash_from_reader(f)?;
+    ///
+    ///     println!("File SHA256 hash: {sha256_string}");
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn hash_from_reader<R: std::io::Read>(reader: R) -> std::io::Result<Self> {
+        let mut hasher = Sha256Hasher::new();
+        hasher.update_from_reader(reader, |_| {})?;
+        Ok(hasher.finalize())
+    }
+
+    /// Returns `true` if the data from a reader hashes to this `Sha256String`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the propagated `Err` from [`io::read`].
+    ///
+    /// [`io::read`]: std::io::Read::read
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::Sha256String;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let hash = "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD"
+    ///     .parse::<Sha256String>()?;
+    ///
+    /// assert!(hash.verify(&b"abc"[..])?);
+    /// assert!(!hash.verify(&b"abd"[..])?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn verify<R: std::io::Read>(&self, reader: R) -> std::io::Result<bool> {
+        Self::hash_from_reader(reader).map(|hash| hash == *self)
+    }
+
+    /// Returns the 32 raw bytes of the hash.
+    #[must_use]
+    #[inline]
+    pub const fn as_bytes(&self) -> &[u8; SHA256_BYTES] {
+        &self.0
+    }
+}
+
+impl From<[u8; SHA256_BYTES]> for Sha256String {
+    #[inline]
+    fn from(bytes: [u8; SHA256_BYTES]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Sha256String> for [u8; SHA256_BYTES] {
+    #[inline]
+    fn from(hash: Sha256String) -> Self {
+        hash.0
+    }
+}
+
+impl AsRef<[u8]> for Sha256String {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Sha256String {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Sha256String")
+            .field(&format_args!("{self}"))
+            .finish()
+    }
+}
+
+impl fmt::Display for Sha256String {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut encode_buf = [0; SHA256_LEN];
+
+        f.pad(
+            base16ct::upper::encode_str(&self.0, &mut encode_buf)
+                .unwrap_or_else(|_| unreachable!("Sha256 hashes should always be 32 bytes long")),
+        )
+    }
+}
+
+impl FromStr for Sha256String {
+    type Err = Sha256StringError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(invalid) = s.chars().find(|char| !char.is_ascii_hexdigit()) {
+            return Err(Sha256StringError::InvalidCharacter(invalid));
+        }
+
+        if s.len() != SHA256_LEN {
+            return Err(Sha256StringError::InvalidLength(s.len()));
+        }
+
+        let mut bytes = [0; SHA256_BYTES];
+        for (byte, pair) in bytes.iter_mut().zip(s.as_bytes().chunks_exact(2)) {
+            *byte = (hex_value(pair[0]) << 4) | hex_value(pair[1]);
+        }
+
+        Ok(Self(bytes))
+    }
+}
+
+const fn hex_value(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        _ => digit - b'A' + 10,
+    }
+}
+
+impl TryFrom<CompactString> for Sha256String {
+    type Error = Sha256StringError;
+
+    #[inline]
+    fn try_from(value: CompactString) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Sha256String {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use rstest::rstest;
+
+    use super::{Sha256String, Sha256StringError};
+
+    const ABC: &str = "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD";
+
+    #[rstest]
+    #[case(ABC)]
+    #[case("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")]
+    #[case("Ba7816Bf8f01CfEa414140De5dAe2223B00361A396177A9cB410Ff61F20015aD")]
+    fn parse_case_insensitive(#[case] hash: &str) {
+        let hash = hash.parse::<Sha256String>().unwrap();
+
+        assert_eq!(hash.to_string(), ABC);
+        assert_eq!(hash, ABC.parse().unwrap());
+        assert_eq!(hash.as_bytes()[0], 0xBA);
+        assert_eq!(hash.as_bytes()[31], 0xAD);
+    }
+
+    #[rstest]
+    #[case("", Sha256StringError::InvalidLength(0))]
+    #[case(&ABC[1..], Sha256StringError::InvalidLength(63))]
+    #[case(
+        "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD0",
+        Sha256StringError::InvalidLength(65)
+    )]
+    #[case(
+        "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AG",
+        Sha256StringError::InvalidCharacter('G')
+    )]
+    #[case(
+        " BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015A",
+        Sha256StringError::InvalidCharacter(' ')
+    )]
+    #[case(
+        "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F2001éA",
+        Sha256StringError::InvalidCharacter('é')
+    )]
+    fn parse_invalid(#[case] hash: &str, #[case] expected: Sha256StringError) {
+        assert_eq!(hash.parse::<Sha256String>(), Err(expected));
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let bytes = core::array::from_fn::<u8, 32, _>(|index| index as u8);
+        let hash = Sha256String::from(bytes);
+
+        assert_eq!(<[u8; 32]>::from(hash), bytes);
+        assert_eq!(
+            hash.to_string(),
+            "000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F"
+        );
+    }
+
+    #[test]
+    fn default() {
+        assert_eq!(Sha256String::default().to_string(), "0".repeat(64));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let hash = serde_yaml::from_str::<Sha256String>(&ABC.to_lowercase()).unwrap();
+
+        assert_eq!(serde_yaml::to_string(&hash).unwrap().trim_end(), ABC);
+        assert!(serde_yaml::from_str::<Sha256String>("ABC").is_err());
+    }
+}
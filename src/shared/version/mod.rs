@@ -1,4 +1,5 @@
 mod part;
+mod range;
 
 use alloc::{borrow::Cow, string::String};
 use core::{
@@ -12,6 +13,7 @@ use core::{
 use compact_str::CompactString;
 use itertools::{EitherOrBoth, Itertools};
 use part::VersionPart;
+pub use range::{VersionInterval, VersionRange, VersionRangeError};
 use smallvec::SmallVec;
 
 #[derive(Clone, Debug, Default, Eq)]
@@ -408,7 +410,7 @@ mod tests {
     #[case("0.0.2", &["0.0.1", "0.0.3", "0.2.0"], "0.0.3")]
     #[case("999.999.999", &["999.999.998", "1000.0.0"], "999.999.998")]
     fn closest_version(#[case] version: &str, #[case] versions: &[&str], #[case] expected: &str) {
-        let versions = versions.into_iter().map(Version::new).collect::<Vec<_>>();
+        let versions = versions.iter().map(Version::new).collect::<Vec<_>>();
         assert_eq!(
             Version::new(version).closest(&versions),
             Some(&Version::new(expected))
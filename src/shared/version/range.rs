@@ -0,0 +1,419 @@
+use alloc::vec::Vec;
+use core::{fmt, ops::Bound, str::FromStr};
+
+use compact_str::CompactString;
+use thiserror::Error;
+
+use super::Version;
+
+/// A continuous interval of versions between two bounds.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct VersionInterval {
+    pub start: Bound<Version>,
+    pub end: Bound<Version>,
+}
+
+impl VersionInterval {
+    /// Returns `true` if the version is within this interval.
+    #[must_use]
+    pub fn contains(&self, version: &Version) -> bool {
+        (match &self.start {
+            Bound::Included(start) => version >= start,
+            Bound::Excluded(start) => version > start,
+            Bound::Unbounded => true,
+        }) && (match &self.end {
+            Bound::Included(end) => version <= end,
+            Bound::Excluded(end) => version < end,
+            Bound::Unbounded => true,
+        })
+    }
+
+    /// Returns `true` if no version can be within this interval.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        match (&self.start, &self.end) {
+            (Bound::Included(start), Bound::Included(end)) => start > end,
+            (
+                Bound::Included(start) | Bound::Excluded(start),
+                Bound::Included(end) | Bound::Excluded(end),
+            ) => start >= end,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for VersionInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let (Bound::Included(start), Bound::Included(end)) = (&self.start, &self.end) {
+            if start == end {
+                return write!(f, "[{start}]");
+            }
+        }
+
+        match &self.start {
+            Bound::Included(start) => write!(f, "[{start}")?,
+            Bound::Excluded(start) => write!(f, "({start}")?,
+            Bound::Unbounded => f.write_str("(")?,
+        }
+        f.write_str(",")?;
+        match &self.end {
+            Bound::Included(end) => write!(f, "{end}]"),
+            Bound::Excluded(end) => write!(f, "{end})"),
+            Bound::Unbounded => f.write_str(")"),
+        }
+    }
+}
+
+/// A set of versions made of a union of [intervals].
+///
+/// A range is written in interval notation, where `[` and `]` include a bound and `(` and `)`
+/// exclude it, such as `[1.0,2.0)`. A missing bound is unbounded, such as `(,2.0)`, and `[1.0]`
+/// matches exactly `1.0`. A version on its own, such as `1.0`, is a minimum version. Intervals are
+/// joined into a union with `||`.
+///
+/// Versions are compared with the ordering of [`Version`], so `unknown` is below every lower bound
+/// and `latest` is above every upper bound.
+///
+/// [intervals]: VersionInterval
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "CompactString"))]
+pub struct VersionRange {
+    intervals: Vec<VersionInterval>,
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum VersionRangeError {
+    #[error("Version range must not be empty")]
+    Empty,
+    #[error("Version interval `{_0}` must start with `[` or `(` and end with `]` or `)`")]
+    UnclosedInterval(CompactString),
+    #[error("Version interval `{_0}` must have at most two versions separated by `,`")]
+    TooManyVersions(CompactString),
+    #[error("Version interval `{_0}` does not contain any versions")]
+    EmptyInterval(CompactString),
+}
+
+impl VersionRange {
+    const UNION: &'static str = "||";
+
+    /// Creates a range that matches every version.
+    #[must_use]
+    pub fn any() -> Self {
+        Self::from(VersionInterval {
+            start: Bound::Unbounded,
+            end: Bound::Unbounded,
+        })
+    }
+
+    /// Creates a range that matches exactly one version.
+    #[must_use]
+    pub fn exact(version: Version) -> Self {
+        Self::from(VersionInterval {
+            start: Bound::Included(version.clone()),
+            end: Bound::Included(version),
+        })
+    }
+
+    /// Creates a range that matches a version and every version above it.
+    #[must_use]
+    pub fn at_least(version: Version) -> Self {
+        Self::from(VersionInterval {
+            start: Bound::Included(version),
+            end: Bound::Unbounded,
+        })
+    }
+
+    /// Returns the intervals of this range.
+    #[must_use]
+    #[inline]
+    pub fn intervals(&self) -> &[VersionInterval] {
+        &self.intervals
+    }
+
+    /// Returns a range that matches the versions of this range and the versions of the other
+    /// range.
+    #[must_use]
+    pub fn union(mut self, other: Self) -> Self {
+        for interval in other.intervals {
+            if !self.intervals.contains(&interval) {
+                self.intervals.push(interval);
+            }
+        }
+        self
+    }
+
+    /// Returns `true` if the version is within any interval of this range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::{Version, VersionRange};
+    ///
+    /// let range = "[1.0,2.0) || [3.0,)".parse::<VersionRange>().unwrap();
+    ///
+    /// assert!(range.contains(&Version::new("1.5")));
+    /// assert!(!range.contains(&Version::new("2.0")));
+    /// assert!(range.contains(&Version::new("latest")));
+    /// assert!(!range.contains(&Version::new("unknown")));
+    /// ```
+    #[must_use]
+    pub fn contains(&self, version: &Version) -> bool {
+        self.intervals
+            .iter()
+            .any(|interval| interval.contains(version))
+    }
+
+    /// Returns the highest of the versions that are within this range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::{Version, VersionRange};
+    ///
+    /// let range = "[1.0,2.0)".parse::<VersionRange>().unwrap();
+    /// let versions = [Version::new("0.9"), Version::new("1.4"), Version::new("2.0")];
+    ///
+    /// assert_eq!(range.best_match(&versions).map(Version::as_str), Some("1.4"));
+    /// ```
+    pub fn best_match<'iter, I, T>(&self, versions: I) -> Option<&'iter T>
+    where
+        I: IntoIterator<Item = &'iter T>,
+        &'iter T: Into<&'iter Version>,
+    {
+        versions
+            .into_iter()
+            .filter(|&version| self.contains(version.into()))
+            .max_by(|&a, &b| Into::<&Version>::into(a).cmp(b.into()))
+    }
+
+    /// Finds the closest version to a target version from the versions that are within this
+    /// range.
+    ///
+    /// See [`Version::closest`] for how the closest version is chosen.
+    pub fn closest<'iter, I, T>(&self, target: &Version, versions: I) -> Option<&'iter T>
+    where
+        I: IntoIterator<Item = &'iter T>,
+        &'iter T: Into<&'iter Version>,
+    {
+        target.closest(
+            versions
+                .into_iter()
+                .filter(|&version| self.contains(version.into())),
+        )
+    }
+
+    fn parse_interval(interval: &str) -> Result<VersionInterval, VersionRangeError> {
+        let Some(inner) = interval.strip_prefix(['[', '(']) else {
+            return Ok(VersionInterval {
+                start: Bound::Included(Version::new(interval)),
+                end: Bound::Unbounded,
+            });
+        };
+        let inner = inner
+            .strip_suffix([']', ')'])
+            .ok_or_else(|| VersionRangeError::UnclosedInterval(interval.into()))?;
+
+        let bound = |version: &str, inclusive: bool| {
+            let version = version.trim();
+            if version.is_empty() {
+                Bound::Unbounded
+            } else if inclusive {
+                Bound::Included(Version::new(version))
+            } else {
+                Bound::Excluded(Version::new(version))
+            }
+        };
+
+        let interval_range = match inner.split_once(',') {
+            Some((_, end)) if end.contains(',') => {
+                return Err(VersionRangeError::TooManyVersions(interval.into()));
+            }
+            Some((start, end)) => VersionInterval {
+                start: bound(start, interval.starts_with('[')),
+                end: bound(end, interval.ends_with(']')),
+            },
+            None if interval.starts_with('[')
+                && interval.ends_with(']')
+                && !inner.trim().is_empty() =>
+            {
+                let version = bound(inner, true);
+                VersionInterval {
+                    start: version.clone(),
+                    end: version,
+                }
+            }
+            None => return Err(VersionRangeError::EmptyInterval(interval.into())),
+        };
+
+        if interval_range.is_empty() {
+            return Err(VersionRangeError::EmptyInterval(interval.into()));
+        }
+
+        Ok(interval_range)
+    }
+}
+
+impl Default for VersionRange {
+    fn default() -> Self {
+        Self::any()
+    }
+}
+
+impl From<VersionInterval> for VersionRange {
+    fn from(interval: VersionInterval) -> Self {
+        Self {
+            intervals: alloc::vec![interval],
+        }
+    }
+}
+
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, interval) in self.intervals.iter().enumerate() {
+            if index > 0 {
+                write!(f, " {} ", Self::UNION)?;
+            }
+            interval.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for VersionRange {
+    type Err = VersionRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let intervals = s
+            .split(Self::UNION)
+            .map(str::trim)
+            .map(|interval| {
+                if interval.is_empty() {
+                    Err(VersionRangeError::Empty)
+                } else {
+                    Self::parse_interval(interval)
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { intervals })
+    }
+}
+
+impl TryFrom<CompactString> for VersionRange {
+    type Error = VersionRangeError;
+
+    #[inline]
+    fn try_from(value: CompactString) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for VersionRange {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use rstest::rstest;
+
+    use super::{VersionRange, VersionRangeError};
+    use crate::Version;
+
+    #[rstest]
+    #[case("[1.0,2.0)", "1.0", true)]
+    #[case("[1.0,2.0)", "1.9.9", true)]
+    #[case("[1.0,2.0)", "2.0", false)]
+    #[case("(1.0,2.0]", "1.0", false)]
+    #[case("(1.0,2.0]", "2.0.0", true)]
+    #[case("(,2.0]", "0.1", true)]
+    #[case("(,2.0]", "unknown", true)]
+    #[case("[1.0,)", "latest", true)]
+    #[case("[1.0,)", "unknown", false)]
+    #[case("[1.0,latest)", "latest", false)]
+    #[case("[1.2]", "1.2.0", true)]
+    #[case("[1.2]", "1.2.1", false)]
+    #[case("1.2", "1.3", true)]
+    #[case("1.2", "1.1", false)]
+    #[case("[1.0,1.5] || [2.0,3.0)", "1.7", false)]
+    #[case("[1.0,1.5] || [2.0,3.0)", "2.5", true)]
+    fn contains(#[case] range: &str, #[case] version: &str, #[case] expected: bool) {
+        assert_eq!(
+            range
+                .parse::<VersionRange>()
+                .unwrap()
+                .contains(&Version::new(version)),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("[1.0,2.0)", "[1.0,2.0)")]
+    #[case("( , 2.0 ]", "(,2.0]")]
+    #[case("[1.2]", "[1.2]")]
+    #[case("1.2", "[1.2,)")]
+    #[case("[1.0,1.5]||(2.0,)", "[1.0,1.5] || (2.0,)")]
+    fn display(#[case] range: &str, #[case] expected: &str) {
+        assert_eq!(range.parse::<VersionRange>().unwrap().to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("", VersionRangeError::Empty)]
+    #[case("[1.0,2.0) ||", VersionRangeError::Empty)]
+    #[case("[1.0,2.0", VersionRangeError::UnclosedInterval("[1.0,2.0".into()))]
+    #[case("[1.0,1.5,2.0]", VersionRangeError::TooManyVersions("[1.0,1.5,2.0]".into()))]
+    #[case("(1.0)", VersionRangeError::EmptyInterval("(1.0)".into()))]
+    #[case("[]", VersionRangeError::EmptyInterval("[]".into()))]
+    #[case("[2.0,1.0]", VersionRangeError::EmptyInterval("[2.0,1.0]".into()))]
+    #[case("[1.0,1.0)", VersionRangeError::EmptyInterval("[1.0,1.0)".into()))]
+    fn invalid(#[case] range: &str, #[case] expected: VersionRangeError) {
+        assert_eq!(range.parse::<VersionRange>(), Err(expected));
+    }
+
+    #[test]
+    fn union() {
+        let range = VersionRange::exact(Version::new("1.0"))
+            .union(VersionRange::at_least(Version::new("2.0")));
+
+        assert_eq!(range.to_string(), "[1.0] || [2.0,)");
+        assert!(range.contains(&Version::new("3.0")));
+        assert!(!range.contains(&Version::new("1.5")));
+    }
+
+    #[test]
+    fn best_match() {
+        let range = "[1.0,2.0)".parse::<VersionRange>().unwrap();
+        let versions = [
+            Version::new("2.0"),
+            Version::new("1.2"),
+            Version::new("1.10"),
+            Version::new("0.9"),
+        ];
+
+        assert_eq!(
+            range.best_match(&versions).map(Version::as_str),
+            Some("1.10")
+        );
+        assert_eq!(
+            range
+                .closest(&Version::new("1.3"), &versions)
+                .map(Version::as_str),
+            Some("1.2")
+        );
+        assert_eq!(
+            "[3.0,)"
+                .parse::<VersionRange>()
+                .unwrap()
+                .best_match(&versions),
+            None
+        );
+    }
+}
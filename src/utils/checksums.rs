@@ -0,0 +1,569 @@
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::str::FromStr;
+
+use compact_str::CompactString;
+use percent_encoding::percent_decode_str;
+use thiserror::Error;
+
+use crate::{Sha256String, Sha256StringError, installer::Installer, url::DecodedUrl};
+
+const BSD_TAGS: [&str; 3] = ["SHA256", "SHA2-256", "SHA-256"];
+
+const SIDECAR_EXTENSIONS: [&str; 4] = [".sha256sum", ".sha256.txt", ".sha256", ".sha256sums"];
+
+/// Sha256 hashes published by a project for its files, such as in a `SHA256SUMS` file.
+///
+/// The following formats are recognised:
+///
+/// - GNU coreutils, as written by `sha256sum`: `<hash>  <file>`, or `<hash> *<file>` for binary
+///   mode.
+/// - BSD, as written by `sha256sum --tag`, `shasum` and `openssl`: `SHA256 (<file>) = <hash>`.
+/// - Sidecar files that contain only the hash of a single file, such as `app.exe.sha256`.
+///
+/// Files are listed by the path they were hashed with, and matched by its final component, so
+/// `dist/app.exe` matches an installer URL ending in `app.exe`.
+///
+/// # Examples
+///
+/// ```
+/// use winget_types::utils::Checksums;
+///
+/// # fn main() -> Result<(), winget_types::utils::ChecksumsError> {
+/// let checksums = "\
+/// ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  app-x64.exe
+/// SHA256 (app-arm64.exe) = e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855
+/// "
+/// .parse::<Checksums>()?;
+///
+/// assert_eq!(checksums.len(), 2);
+/// assert_eq!(
+///     checksums.get("app-x64.exe").unwrap().to_string(),
+///     "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Checksums(Vec<(CompactString, Sha256String)>);
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum ChecksumsError {
+    #[error("Line {line} is not a GNU or BSD style checksum")]
+    InvalidLine { line: usize },
+    #[error("Line {line} has an invalid Sha256 hash: {source}")]
+    InvalidHash {
+        line: usize,
+        source: Sha256StringError,
+    },
+    #[error("`{_0}` is listed more than once with different hashes")]
+    ConflictingHashes(CompactString),
+    #[error("Sidecar file does not contain a Sha256 hash")]
+    EmptySidecar,
+}
+
+impl Checksums {
+    /// Extracts checksums from free-form text, such as release notes.
+    ///
+    /// Unlike [parsing], lines that are not checksums are ignored, and Markdown code spans, code
+    /// fences and list markers around checksums are allowed.
+    ///
+    /// [parsing]: Checksums::from_str
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the same file is listed with different hashes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::utils::Checksums;
+    ///
+    /// let notes = "\
+    /// ## Checksums
+    ///
+    /// - `ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  app.msi`
+    /// ";
+    ///
+    /// let checksums = Checksums::extract(notes).unwrap();
+    /// assert!(checksums.get("app.msi").is_some());
+    /// ```
+    pub fn extract(text: &str) -> Result<Self, ChecksumsError> {
+        let mut checksums = Self::default();
+
+        for line in text.lines() {
+            let line = line
+                .trim()
+                .trim_start_matches(['-', '*', '+'])
+                .trim()
+                .trim_matches('`')
+                .trim();
+
+            if let Some(Ok((path, hash))) = parse_line(line) {
+                checksums.insert(path, hash)?;
+            }
+        }
+
+        Ok(checksums)
+    }
+
+    /// Parses a sidecar file that holds the hash of a single file, such as `app.exe.sha256`.
+    ///
+    /// The file the hash is for is named after the sidecar without its `.sha256`, `.sha256sum`
+    /// or `.sha256.txt` extension. If the sidecar is in GNU or BSD format, the file name within
+    /// it is used instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the sidecar does not start with a valid Sha256 hash.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use winget_types::utils::Checksums;
+    ///
+    /// let checksums = Checksums::from_sidecar(
+    ///     "app.exe.sha256",
+    ///     "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD\n",
+    /// )
+    /// .unwrap();
+    ///
+    /// assert!(checksums.get("app.exe").is_some());
+    /// ```
+    pub fn from_sidecar(sidecar_name: &str, contents: &str) -> Result<Self, ChecksumsError> {
+        let line = contents
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .ok_or(ChecksumsError::EmptySidecar)?;
+
+        let (path, hash) = match parse_line(line) {
+            Some(Ok((path, hash))) => (path, hash),
+            Some(Err(source)) => return Err(ChecksumsError::InvalidHash { line: 1, source }),
+            None => {
+                let hash = line
+                    .parse::<Sha256String>()
+                    .map_err(|source| ChecksumsError::InvalidHash { line: 1, source })?;
+                let sidecar_name = file_name(sidecar_name);
+                let file_name = SIDECAR_EXTENSIONS
+                    .iter()
+                    .find_map(|extension| strip_suffix_ignore_case(sidecar_name, extension))
+                    .unwrap_or(sidecar_name);
+                (Cow::Borrowed(file_name), hash)
+            }
+        };
+
+        Ok(Self(Vec::from([(path.into(), hash)])))
+    }
+
+    /// Returns the hash of a file by its name, or by its path as listed.
+    ///
+    /// An exact match is preferred over a case-insensitive one. If files in different
+    /// directories share the name and have different hashes, the name is ambiguous and `None` is
+    /// returned.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Sha256String> {
+        if let Some((_, hash)) = self.iter().find(|&(path, _)| path == name) {
+            return Some(hash);
+        }
+
+        let name = file_name(name);
+        self.get_unambiguous(|file_name| file_name == name)
+            .or_else(|| self.get_unambiguous(|file_name| file_name.eq_ignore_ascii_case(name)))
+            .flatten()
+    }
+
+    /// Returns the hash of the files whose names match, `Some(None)` if they have different
+    /// hashes, or `None` if no file matches.
+    fn get_unambiguous<F>(&self, mut matches: F) -> Option<Option<&Sha256String>>
+    where
+        F: FnMut(&str) -> bool,
+    {
+        let mut hashes = self
+            .iter()
+            .filter(|&(path, _)| matches(file_name(path)))
+            .map(|(_, hash)| hash);
+
+        let first = hashes.next()?;
+        Some(hashes.all(|hash| hash == first).then_some(first))
+    }
+
+    /// Returns the hash of the file that a URL downloads, by the last segment of its path.
+    #[must_use]
+    pub fn get_for_url(&self, url: &DecodedUrl) -> Option<&Sha256String> {
+        let segment = url.path_segments()?.next_back()?;
+        self.get(&percent_decode_str(segment).decode_utf8_lossy())
+    }
+
+    /// Sets the [`sha_256`] of an installer from the hash of its URL's file, returning `true` if
+    /// a hash was found.
+    ///
+    /// The hash is taken from the publisher. It can be cross-checked against the downloaded
+    /// installer with `Sha256String::verify`.
+    ///
+    /// [`sha_256`]: Installer::sha_256
+    pub fn apply(&self, installer: &mut Installer) -> bool {
+        let Some(hash) = self.get_for_url(&installer.url) else {
+            return false;
+        };
+        installer.sha_256 = *hash;
+        true
+    }
+
+    /// Returns an iterator over the file paths and their hashes, in the order they were listed.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, &Sha256String)> {
+        self.0
+            .iter()
+            .map(|(file_name, hash)| (file_name.as_str(), hash))
+    }
+
+    /// Returns the number of files with a hash.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no hashes.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn insert(&mut self, path: Cow<str>, hash: Sha256String) -> Result<(), ChecksumsError> {
+        match self.0.iter().find(|(existing, _)| *existing == path) {
+            Some((_, existing)) if *existing == hash => Ok(()),
+            Some(_) => Err(ChecksumsError::ConflictingHashes(path.into())),
+            None => {
+                self.0.push((path.into(), hash));
+                Ok(())
+            }
+        }
+    }
+}
+
+impl FromStr for Checksums {
+    type Err = ChecksumsError;
+
+    /// Parses a checksum file in GNU or BSD format, such as `SHA256SUMS`.
+    ///
+    /// Blank lines and lines starting with `#` are ignored. BSD style lines for other algorithms,
+    /// such as `SHA512 (app.exe) = ...`, are skipped so that multi-algorithm files can be read.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut checksums = Self::default();
+
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || is_other_bsd_algorithm(line) {
+                continue;
+            }
+
+            match parse_line(line) {
+                Some(Ok((path, hash))) => checksums.insert(path, hash)?,
+                Some(Err(source)) => {
+                    return Err(ChecksumsError::InvalidHash {
+                        line: index + 1,
+                        source,
+                    });
+                }
+                None => return Err(ChecksumsError::InvalidLine { line: index + 1 }),
+            }
+        }
+
+        Ok(checksums)
+    }
+}
+
+/// Parses a GNU or BSD style checksum line into its file path and hash.
+///
+/// Returns `None` if the line is in neither format, or `Some(Err)` if it is but its hash is
+/// invalid.
+fn parse_line(line: &str) -> Option<Result<(Cow<'_, str>, Sha256String), Sha256StringError>> {
+    // Paths containing a backslash or newline are escaped and the line is prefixed with `\`
+    let (line, is_escaped) = line
+        .strip_prefix('\\')
+        .map_or((line, false), |line| (line, true));
+
+    let (path, hash) = match parse_bsd(line).or_else(|| parse_gnu(line))? {
+        Ok(parsed) => parsed,
+        Err(err) => return Some(Err(err)),
+    };
+    let path = if is_escaped {
+        Cow::Owned(unescape(path)?)
+    } else {
+        Cow::Borrowed(path)
+    };
+
+    Some(Ok((path, hash)))
+}
+
+/// Unescapes a path escaped by `sha256sum`, returning `None` if it has an invalid escape.
+fn unescape(path: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(path.len());
+    let mut chars = path.chars();
+
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            unescaped.push(char);
+            continue;
+        }
+
+        unescaped.push(match chars.next()? {
+            '\\' => '\\',
+            'n' => '\n',
+            'r' => '\r',
+            _ => return None,
+        });
+    }
+
+    Some(unescaped)
+}
+
+/// Parses a line in the form `<hash>  <file>` or `<hash> *<file>`.
+fn parse_gnu(line: &str) -> Option<Result<(&str, Sha256String), Sha256StringError>> {
+    let (hash, file_name) = line.split_once(' ')?;
+    let file_name = file_name
+        .strip_prefix([' ', '*'])
+        .unwrap_or(file_name)
+        .trim();
+
+    if file_name.is_empty() || !hash.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    Some(hash.parse().map(|hash| (file_name, hash)))
+}
+
+/// Parses a line in the form `SHA256 (<file>) = <hash>`.
+fn parse_bsd(line: &str) -> Option<Result<(&str, Sha256String), Sha256StringError>> {
+    let (tag, rest) = line.split_once('(')?;
+    if !BSD_TAGS
+        .iter()
+        .any(|bsd_tag| bsd_tag.eq_ignore_ascii_case(tag.trim_end()))
+    {
+        return None;
+    }
+
+    let (file_name, hash) = rest.rsplit_once(')')?;
+    let hash = hash.trim_start().strip_prefix('=')?.trim();
+
+    Some(hash.parse().map(|hash| (file_name, hash)))
+}
+
+fn is_other_bsd_algorithm(line: &str) -> bool {
+    let line = line.strip_prefix('\\').unwrap_or(line);
+    line.split_once('(').is_some_and(|(tag, _)| {
+        tag.trim_end()
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-')
+    }) && parse_bsd(line).is_none()
+}
+
+/// Returns the final component of a path, which may use either separator.
+fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+fn strip_suffix_ignore_case<'a>(value: &'a str, suffix: &str) -> Option<&'a str> {
+    let index = value.len().checked_sub(suffix.len())?;
+    (value.is_char_boundary(index) && value[index..].eq_ignore_ascii_case(suffix))
+        .then(|| &value[..index])
+        .filter(|stripped| !stripped.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::ToString, vec::Vec};
+
+    use indoc::indoc;
+    use rstest::rstest;
+
+    use super::{Checksums, ChecksumsError};
+    use crate::{Sha256String, Sha256StringError, installer::Installer};
+
+    const ABC: &str = "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD";
+
+    const EMPTY: &str = "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855";
+
+    fn names(checksums: &Checksums) -> Vec<(&str, alloc::string::String)> {
+        checksums
+            .iter()
+            .map(|(file_name, hash)| (file_name, hash.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn gnu() {
+        let checksums = indoc! {"
+            # Generated by sha256sum
+            ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  app setup.exe
+            e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855 *dist/app.msi
+        "}
+        .parse::<Checksums>()
+        .unwrap();
+
+        assert_eq!(
+            names(&checksums),
+            [
+                ("app setup.exe", ABC.to_string()),
+                ("dist/app.msi", EMPTY.to_string())
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(
+        r"\ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  dist\\app.exe",
+        r"dist\app.exe"
+    )]
+    #[case(
+        r"\ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  line\nbreak.exe",
+        "line\nbreak.exe"
+    )]
+    #[case(
+        r"\SHA256 (dist\\app.exe) = ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        r"dist\app.exe"
+    )]
+    fn escaped(#[case] line: &str, #[case] path: &str) {
+        let checksums = line.parse::<Checksums>().unwrap();
+
+        assert_eq!(names(&checksums), [(path, ABC.to_string())]);
+        assert!(checksums.get(path).is_some());
+    }
+
+    #[test]
+    fn same_name_in_different_directories() {
+        let checksums = indoc! {"
+            ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  x64/setup.exe
+            e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855  arm64/setup.exe
+            ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  x64/app.msi
+            ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  x86/app.msi
+        "}
+        .parse::<Checksums>()
+        .unwrap();
+
+        assert_eq!(checksums.len(), 4);
+        assert_eq!(checksums.get("setup.exe"), None);
+        assert_eq!(
+            checksums.get("arm64/setup.exe").map(ToString::to_string),
+            Some(EMPTY.to_string())
+        );
+        assert_eq!(
+            checksums.get("app.msi").map(ToString::to_string),
+            Some(ABC.to_string())
+        );
+        assert_eq!(
+            checksums.get_for_url(&"https://example.com/v1/setup.exe".parse().unwrap()),
+            None
+        );
+    }
+
+    #[rstest]
+    #[case("SHA256 (app.exe) = ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")]
+    #[case("SHA256(app.exe)= ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")]
+    #[case("SHA2-256(app.exe)= ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")]
+    fn bsd(#[case] line: &str) {
+        let checksums = line.parse::<Checksums>().unwrap();
+
+        assert_eq!(names(&checksums), [("app.exe", ABC.to_string())]);
+    }
+
+    #[test]
+    fn bsd_skips_other_algorithms() {
+        let checksums = indoc! {"
+            MD5 (app.exe) = 900150983cd24fb0d6963f7d28e17f72
+            SHA256 (app.exe) = ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad
+        "}
+        .parse::<Checksums>()
+        .unwrap();
+
+        assert_eq!(checksums.len(), 1);
+    }
+
+    #[rstest]
+    #[case("not a checksum", ChecksumsError::InvalidLine { line: 1 })]
+    #[case(
+        "\n900150983cd24fb0d6963f7d28e17f72  app.exe",
+        ChecksumsError::InvalidHash { line: 2, source: Sha256StringError::InvalidLength(32) }
+    )]
+    #[case(
+        indoc! {"
+            ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  app.exe
+            e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855  app.exe
+        "},
+        ChecksumsError::ConflictingHashes("app.exe".into())
+    )]
+    fn invalid(#[case] checksums: &str, #[case] expected: ChecksumsError) {
+        assert_eq!(checksums.parse::<Checksums>(), Err(expected));
+    }
+
+    #[test]
+    fn extract_from_release_notes() {
+        let notes = indoc! {"
+            ## What's Changed
+
+            * Fixed a crash on startup
+
+            ### SHA256
+
+            ```
+            ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  app-x64.exe
+            ```
+            - `SHA256 (app-arm64.exe) = e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855`
+        "};
+
+        assert_eq!(
+            names(&Checksums::extract(notes).unwrap()),
+            [
+                ("app-x64.exe", ABC.to_string()),
+                ("app-arm64.exe", EMPTY.to_string())
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("app.exe.sha256", ABC, "app.exe")]
+    #[case("https://example.com/v1/App.MSI.SHA256SUM", ABC, "App.MSI")]
+    #[case("app.exe.sha256.txt", ABC, "app.exe")]
+    #[case("checksum.sha256", &format!("{ABC}  renamed.exe"), "renamed.exe")]
+    fn sidecar(#[case] sidecar_name: &str, #[case] contents: &str, #[case] file_name: &str) {
+        let checksums = Checksums::from_sidecar(sidecar_name, contents).unwrap();
+
+        assert_eq!(names(&checksums), [(file_name, ABC.to_string())]);
+    }
+
+    #[test]
+    fn sidecar_errors() {
+        assert_eq!(
+            Checksums::from_sidecar("app.exe.sha256", "\n\n"),
+            Err(ChecksumsError::EmptySidecar)
+        );
+        assert!(matches!(
+            Checksums::from_sidecar("app.exe.sha256", "ABC"),
+            Err(ChecksumsError::InvalidHash { .. })
+        ));
+    }
+
+    #[test]
+    fn apply_to_installer() {
+        let checksums = indoc! {"
+            ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  My App.EXE
+        "}
+        .parse::<Checksums>()
+        .unwrap();
+
+        let mut installer = Installer {
+            url: "https://example.com/releases/My%20App.exe".parse().unwrap(),
+            ..Installer::default()
+        };
+        assert!(checksums.apply(&mut installer));
+        assert_eq!(installer.sha_256, ABC.parse::<Sha256String>().unwrap());
+
+        let mut installer = Installer {
+            url: "https://example.com/releases/other.exe".parse().unwrap(),
+            ..Installer::default()
+        };
+        assert!(!checksums.apply(&mut installer));
+        assert_eq!(installer.sha_256, Sha256String::default());
+    }
+}
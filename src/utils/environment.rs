@@ -0,0 +1,433 @@
+use alloc::{collections::BTreeMap, string::String};
+
+use compact_str::CompactString;
+use thiserror::Error;
+
+use super::RelativeDir;
+use crate::installer::{Architecture, Scope};
+
+/// The directories that paths are tokenized into, in order of preference when several expand to
+/// the same directory.
+const TOKENS: [&str; 12] = [
+    RelativeDir::PROGRAM_FILES_32,
+    RelativeDir::PROGRAM_FILES_64,
+    RelativeDir::COMMON_FILES_32,
+    RelativeDir::COMMON_FILES_64,
+    RelativeDir::LOCAL_APP_DATA,
+    RelativeDir::APP_DATA,
+    RelativeDir::TEMP_FOLDER,
+    RelativeDir::USER_PROFILE,
+    RelativeDir::PROGRAM_DATA,
+    RelativeDir::WINDOWS_DIR,
+    RelativeDir::SYSTEM_ROOT,
+    RelativeDir::SYSTEM_DRIVE,
+];
+
+/// Variables whose value is specific to the profile of a user.
+const USER_VARIABLES: [&str; 5] = ["APPDATA", "LOCALAPPDATA", "TEMP", "TMP", "USERPROFILE"];
+
+const SYSTEM32: &str = "System32";
+const SYSWOW64: &str = "SysWOW64";
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum ExpansionError {
+    #[error("Environment variable `{0}` is not defined")]
+    UndefinedVariable(CompactString),
+    #[error("Environment variable at byte {0} is not terminated by `%`")]
+    UnterminatedVariable(usize),
+    #[error("`%{0}%` is specific to a user profile and cannot be used in machine scope")]
+    UserVariableInMachineScope(CompactString),
+}
+
+/// The environment variables of a process with an architecture and scope, used to expand
+/// `%Variable%` tokens in paths and to turn absolute paths back into tokens.
+///
+/// A 32-bit process on 64-bit Windows is subject to [WOW64 redirection], so `%ProgramFiles%`
+/// expands to the value of `%ProgramFiles(x86)%` and `%WinDir%\System32` is redirected to
+/// `%WinDir%\SysWOW64`. A process on 64-bit Windows is recognised by `%ProgramFiles(x86)%`
+/// being defined.
+///
+/// In machine scope, variables that are specific to the profile of a user, such as
+/// `%LocalAppData%`, are not used.
+///
+/// [WOW64 redirection]: https://learn.microsoft.com/windows/win32/winprog64/file-system-redirector
+///
+/// # Examples
+///
+/// ```
+/// use winget_types::{
+///     installer::{Architecture, Scope},
+///     utils::Environment,
+/// };
+///
+/// # fn main() -> Result<(), winget_types::utils::ExpansionError> {
+/// let environment = Environment::new(
+///     [
+///         ("ProgramFiles", r"C:\Program Files"),
+///         ("ProgramFiles(x86)", r"C:\Program Files (x86)"),
+///         ("SystemDrive", "C:"),
+///     ],
+///     Architecture::X86,
+///     Scope::Machine,
+/// );
+///
+/// assert_eq!(
+///     environment.expand(r"%ProgramFiles%\Contoso")?,
+///     r"C:\Program Files (x86)\Contoso"
+/// );
+/// assert_eq!(
+///     environment.tokenize(r"C:\Program Files (x86)\Contoso"),
+///     r"%ProgramFiles(x86)%\Contoso"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Environment {
+    variables: BTreeMap<CompactString, String>,
+    architecture: Architecture,
+    scope: Scope,
+}
+
+impl Environment {
+    /// Creates a new `Environment` from environment variables, whose names are
+    /// case-insensitive.
+    pub fn new<I, K, V>(variables: I, architecture: Architecture, scope: Scope) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        Self {
+            variables: variables
+                .into_iter()
+                .map(|(name, value)| (name.as_ref().to_ascii_uppercase().into(), value.into()))
+                .collect(),
+            architecture,
+            scope,
+        }
+    }
+
+    /// Returns the architecture of the process.
+    #[must_use]
+    #[inline]
+    pub const fn architecture(&self) -> Architecture {
+        self.architecture
+    }
+
+    /// Returns the scope of the process.
+    #[must_use]
+    #[inline]
+    pub const fn scope(&self) -> Scope {
+        self.scope
+    }
+
+    /// Returns the value of an environment variable as seen by the process, after redirection.
+    #[must_use]
+    pub fn variable(&self, name: &str) -> Option<&str> {
+        let name = name.to_ascii_uppercase();
+
+        let redirected = match name.as_str() {
+            "PROGRAMFILES" if self.is_wow64() => self.raw("PROGRAMFILES(X86)"),
+            "COMMONPROGRAMFILES" if self.is_wow64() => self.raw("COMMONPROGRAMFILES(X86)"),
+            "PROGRAMFILES" if !self.architecture.is_32_bit() => self.raw("PROGRAMW6432"),
+            "COMMONPROGRAMFILES" if !self.architecture.is_32_bit() => {
+                self.raw("COMMONPROGRAMW6432")
+            }
+            _ => None,
+        };
+
+        redirected.or_else(|| self.raw(&name))
+    }
+
+    /// Expands every `%Variable%` token in a path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a variable is not defined or not terminated, or if a variable that is
+    /// specific to a user profile is used in machine scope.
+    pub fn expand(&self, path: &str) -> Result<String, ExpansionError> {
+        let mut expanded = String::with_capacity(path.len());
+        let mut rest = path;
+
+        while let Some(start) = rest.find('%') {
+            expanded.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let end = after.find('%').ok_or(ExpansionError::UnterminatedVariable(
+                path.len() - rest.len() + start,
+            ))?;
+
+            let name = &after[..end];
+            if name.is_empty() {
+                expanded.push_str("%%");
+            } else {
+                if self.scope.is_machine() && Self::is_user_variable(name) {
+                    return Err(ExpansionError::UserVariableInMachineScope(name.into()));
+                }
+
+                expanded.push_str(
+                    self.variable(name)
+                        .ok_or_else(|| ExpansionError::UndefinedVariable(name.into()))?,
+                );
+            }
+
+            rest = &after[end + 1..];
+        }
+        expanded.push_str(rest);
+
+        if let Some(index) = self.system32_index(&expanded) {
+            expanded.replace_range(index..index + SYSTEM32.len(), SYSWOW64);
+        }
+
+        Ok(expanded)
+    }
+
+    /// Replaces the most specific directory that a path starts with by its `%Variable%` token.
+    ///
+    /// The token is chosen so that [expanding] the result in this environment returns the
+    /// original path, apart from the redirection of `System32`. Paths that are not within a known
+    /// directory are returned unchanged.
+    ///
+    /// [expanding]: Self::expand
+    #[must_use]
+    pub fn tokenize(&self, path: &str) -> String {
+        let mut best: Option<(&str, &str)> = None;
+
+        for token in TOKENS {
+            let name = token.trim_matches('%');
+            if self.scope.is_machine() && Self::is_user_variable(name) {
+                continue;
+            }
+
+            let Some(value) = self.variable(name).filter(|value| !value.is_empty()) else {
+                continue;
+            };
+
+            if let Some(rest) = strip_directory(path, value) {
+                if best.is_none_or(|(_, best_rest)| rest.len() < best_rest.len()) {
+                    best = Some((token, rest));
+                }
+            }
+        }
+
+        best.map_or_else(
+            || path.into(),
+            |(token, rest)| {
+                let mut tokenized = String::with_capacity(token.len() + rest.len());
+                tokenized.push_str(token);
+                tokenized.push_str(rest);
+                tokenized
+            },
+        )
+    }
+
+    /// Expands a path and then replaces the most specific directory it starts with by its token.
+    ///
+    /// This turns both absolute paths and paths with less specific tokens, such as
+    /// `%UserProfile%\AppData\Local\Programs`, into the same form.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path cannot be [expanded].
+    ///
+    /// [expanded]: Self::expand
+    pub fn normalize(&self, path: &str) -> Result<String, ExpansionError> {
+        self.expand(path).map(|expanded| self.tokenize(&expanded))
+    }
+
+    /// Returns the index of the `System32` directory within a path that a 32-bit process on
+    /// 64-bit Windows is redirected from.
+    fn system32_index(&self, path: &str) -> Option<usize> {
+        if !self.is_wow64() {
+            return None;
+        }
+
+        let windows = self
+            .variable("WINDIR")
+            .or_else(|| self.variable("SYSTEMROOT"))?;
+        let rest = strip_directory(path, windows)?.get(1..)?;
+        let after = strip_directory(rest, SYSTEM32)?;
+
+        Some(path.len() - after.len() - SYSTEM32.len())
+    }
+
+    fn raw(&self, name: &str) -> Option<&str> {
+        self.variables.get(name).map(String::as_str)
+    }
+
+    fn is_wow64(&self) -> bool {
+        self.architecture.is_32_bit() && self.variables.contains_key("PROGRAMFILES(X86)")
+    }
+
+    fn is_user_variable(name: &str) -> bool {
+        USER_VARIABLES
+            .iter()
+            .any(|variable| variable.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Strips a directory from the start of a path, returning the rest of the path starting with
+/// its separator.
+///
+/// The comparison is case-insensitive and treats `/` and `\` as the same separator.
+fn strip_directory<'path>(path: &'path str, directory: &str) -> Option<&'path str> {
+    let directory = directory.trim_end_matches(['\\', '/']);
+    let head = path.get(..directory.len())?;
+
+    let matches = head
+        .bytes()
+        .zip(directory.bytes())
+        .all(|(a, b)| a.eq_ignore_ascii_case(&b) || (is_separator(a) && is_separator(b)));
+    let rest = &path[directory.len()..];
+
+    (matches && rest.bytes().next().is_none_or(is_separator)).then_some(rest)
+}
+
+const fn is_separator(byte: u8) -> bool {
+    matches!(byte, b'\\' | b'/')
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{Environment, ExpansionError};
+    use crate::installer::{Architecture, Scope};
+
+    fn environment(architecture: Architecture, scope: Scope) -> Environment {
+        Environment::new(
+            [
+                ("ProgramFiles", r"C:\Program Files"),
+                ("ProgramFiles(x86)", r"C:\Program Files (x86)"),
+                ("ProgramW6432", r"C:\Program Files"),
+                ("CommonProgramFiles", r"C:\Program Files\Common Files"),
+                (
+                    "CommonProgramFiles(x86)",
+                    r"C:\Program Files (x86)\Common Files",
+                ),
+                ("CommonProgramW6432", r"C:\Program Files\Common Files"),
+                ("LocalAppData", r"C:\Users\Contoso\AppData\Local"),
+                ("AppData", r"C:\Users\Contoso\AppData\Roaming"),
+                ("Temp", r"C:\Users\Contoso\AppData\Local\Temp"),
+                ("UserProfile", r"C:\Users\Contoso"),
+                ("ProgramData", r"C:\ProgramData"),
+                ("windir", r"C:\Windows"),
+                ("SystemRoot", r"C:\Windows"),
+                ("SystemDrive", "C:"),
+            ],
+            architecture,
+            scope,
+        )
+    }
+
+    #[rstest]
+    #[case(Architecture::X64, r"%ProgramFiles%\App", r"C:\Program Files\App")]
+    #[case(
+        Architecture::X86,
+        r"%ProgramFiles%\App",
+        r"C:\Program Files (x86)\App"
+    )]
+    #[case(Architecture::X86, r"%programw6432%\App", r"C:\Program Files\App")]
+    #[case(
+        Architecture::Arm,
+        r"%CommonProgramFiles%",
+        r"C:\Program Files (x86)\Common Files"
+    )]
+    #[case(
+        Architecture::X86,
+        r"%WinDir%\System32\app.dll",
+        r"C:\Windows\SysWOW64\app.dll"
+    )]
+    #[case(Architecture::X86, r"%WinDir%\System32", r"C:\Windows\SysWOW64")]
+    #[case(Architecture::X86, r"%WinDir%\System32x", r"C:\Windows\System32x")]
+    #[case(Architecture::X64, r"%WinDir%\System32", r"C:\Windows\System32")]
+    #[case(Architecture::X64, "100%% done", "100%% done")]
+    fn expand(#[case] architecture: Architecture, #[case] path: &str, #[case] expected: &str) {
+        assert_eq!(
+            environment(architecture, Scope::Machine)
+                .expand(path)
+                .as_deref(),
+            Ok(expected)
+        );
+    }
+
+    #[rstest]
+    #[case("%Missing%", ExpansionError::UndefinedVariable("Missing".into()))]
+    #[case(r"C:\%Temp", ExpansionError::UnterminatedVariable(3))]
+    #[case(
+        r"%LocalAppData%\Programs",
+        ExpansionError::UserVariableInMachineScope("LocalAppData".into())
+    )]
+    fn expand_error(#[case] path: &str, #[case] expected: ExpansionError) {
+        assert_eq!(
+            environment(Architecture::X64, Scope::Machine).expand(path),
+            Err(expected)
+        );
+    }
+
+    #[rstest]
+    #[case(
+        Architecture::X64,
+        Scope::User,
+        r"C:\Program Files\App",
+        r"%ProgramFiles%\App"
+    )]
+    #[case(
+        Architecture::X86,
+        Scope::User,
+        r"c:/program files (x86)/App",
+        r"%ProgramFiles(x86)%/App"
+    )]
+    #[case(
+        Architecture::X64,
+        Scope::User,
+        r"C:\Program Files\Common Files\App",
+        r"%CommonProgramFiles%\App"
+    )]
+    #[case(
+        Architecture::X64,
+        Scope::User,
+        r"C:\Users\Contoso\AppData\Local\Temp\App",
+        r"%Temp%\App"
+    )]
+    #[case(
+        Architecture::X64,
+        Scope::User,
+        r"C:\Users\Contoso\AppData\Local\Programs\App",
+        r"%LocalAppData%\Programs\App"
+    )]
+    #[case(
+        Architecture::X64,
+        Scope::Machine,
+        r"C:\Users\Contoso\AppData\Local\Programs\App",
+        r"%SystemDrive%\Users\Contoso\AppData\Local\Programs\App"
+    )]
+    #[case(Architecture::X64, Scope::User, r"C:\Windows", "%WinDir%")]
+    #[case(Architecture::X64, Scope::User, r"D:\App", r"D:\App")]
+    #[case(
+        Architecture::X86,
+        Scope::User,
+        r"C:\Program Files\App",
+        r"%SystemDrive%\Program Files\App"
+    )]
+    fn tokenize(
+        #[case] architecture: Architecture,
+        #[case] scope: Scope,
+        #[case] path: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(environment(architecture, scope).tokenize(path), expected);
+    }
+
+    #[test]
+    fn normalize() {
+        let environment = environment(Architecture::X64, Scope::User);
+
+        assert_eq!(
+            environment
+                .normalize(r"%UserProfile%\AppData\Local\Programs\App")
+                .as_deref(),
+            Ok(r"%LocalAppData%\Programs\App")
+        );
+    }
+}
@@ -1,3 +1,9 @@
+#[cfg(any(feature = "fetch", feature = "pe", feature = "registry"))]
+pub(crate) mod bytes;
+mod checksums;
+mod environment;
 mod relative_dirs;
 
+pub use checksums::{Checksums, ChecksumsError};
+pub use environment::{Environment, ExpansionError};
 pub use relative_dirs::RelativeDir;
@@ -7,6 +7,7 @@ impl RelativeDir {
     pub const COMMON_FILES_32: &'static str = "%CommonProgramFiles(x86)%";
     pub const LOCAL_APP_DATA: &'static str = "%LocalAppData%";
     pub const APP_DATA: &'static str = "%AppData%";
+    pub const USER_PROFILE: &'static str = "%UserProfile%";
     pub const PROGRAM_DATA: &'static str = "%ProgramData%";
     pub const WINDOWS_DIR: &'static str = "%WinDir%";
     pub const SYSTEM_ROOT: &'static str = "%SystemRoot%";
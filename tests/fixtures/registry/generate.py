@@ -0,0 +1,206 @@
+"""Generates the minimal registry hive fixtures used by the `registry` analysis tests.
+
+Run from this directory with `python3 generate.py`.
+"""
+
+import struct
+
+REG_SZ = 1
+REG_DWORD = 4
+
+
+class Bin:
+    def __init__(self):
+        self.data = bytearray()
+
+    def alloc(self, payload):
+        size = (len(payload) + 4 + 7) & ~7
+        offset = len(self.data) + 32
+        self.data += struct.pack("<i", -size) + payload + bytes(size - 4 - len(payload))
+        return offset
+
+    def patch(self, offset, position, value):
+        start = offset - 32 + 4 + position
+        self.data[start : start + 4] = struct.pack("<I", value)
+
+
+def value(bin, name, data):
+    if isinstance(data, int):
+        kind, raw = REG_DWORD, struct.pack("<I", data)
+        size, data_offset = 4 | 1 << 31, struct.unpack("<I", raw)[0]
+    else:
+        kind, raw = REG_SZ, (data + "\0").encode("utf-16-le")
+        size, data_offset = len(raw), bin.alloc(raw)
+    name = name.encode("latin-1")
+    return bin.alloc(
+        b"vk" + struct.pack("<HIIIHH", len(name), size, data_offset, kind, 1, 0) + name
+    )
+
+
+def key(bin, name, parent, subkeys=(), values=()):
+    name = name.encode("latin-1")
+    offset = bin.alloc(
+        b"nk"
+        + struct.pack("<H", 0x20 | (0x4 if parent is None else 0))
+        + bytes(8)
+        + struct.pack("<II", 0, 0xFFFFFFFF if parent is None else parent)
+        + struct.pack("<II", len(subkeys), 0)
+        + struct.pack("<II", 0xFFFFFFFF, 0xFFFFFFFF)
+        + struct.pack("<II", len(values), 0xFFFFFFFF)
+        + struct.pack("<II", 0xFFFFFFFF, 0xFFFFFFFF)
+        + bytes(20)
+        + struct.pack("<HH", len(name), 0)
+        + name
+    )
+    return offset
+
+
+def build(tree):
+    bin = Bin()
+
+    def write(name, parent, children, values):
+        offset = key(bin, name, parent, children, values)
+        child_offsets = [
+            (child[0], write(*child[:1], offset, *child[1:]))
+            for child in sorted(children, key=lambda child: child[0].upper())
+        ]
+        if child_offsets:
+            items = b"".join(
+                struct.pack("<I4s", child, name.encode("latin-1")[:4].ljust(4, b"\0"))
+                for name, child in child_offsets
+            )
+            bin.patch(offset, 28, bin.alloc(b"lf" + struct.pack("<H", len(child_offsets)) + items))
+        if values:
+            value_offsets = [value(bin, *item) for item in values]
+            bin.patch(
+                offset, 40, bin.alloc(b"".join(struct.pack("<I", item) for item in value_offsets))
+            )
+        return offset
+
+    return hive(bin, write(*tree[:1], None, *tree[1:]))
+
+
+def hive(bin, root):
+    data = bytes(bin.data)
+    size = (len(data) + 32 + 4095) & ~4095
+    data += bytes(size - 32 - len(data))
+    hbin = b"hbin" + struct.pack("<III", 0, size, 0) + bytes(16)
+
+    base = bytearray(4096)
+    base[0:4] = b"regf"
+    struct.pack_into("<IIQIIIIIII", base, 4, 1, 1, 0, 1, 5, 0, 1, root, size, 1)
+    checksum = 0
+    for (word,) in struct.iter_unpack("<I", base[:508]):
+        checksum ^= word
+    struct.pack_into("<I", base, 508, checksum)
+
+    return bytes(base) + hbin + data
+
+
+def recursive():
+    """A hive whose root key's subkey list is an index root (`ri`) that lists itself."""
+    bin = Bin()
+    root = key(bin, "ROOT", None, subkeys=[None])
+    index_root = bin.alloc(b"ri" + struct.pack("<HI", 1, 0))
+    bin.patch(index_root, 4, index_root)
+    bin.patch(root, 28, index_root)
+    return hive(bin, root)
+
+
+def uninstall(*entries):
+    return ("Uninstall", list(entries), [])
+
+
+def path(names, leaf):
+    *parents, last = names
+    node = (last, [leaf], [])
+    for name in reversed(parents):
+        node = (name, [node], [])
+    return node
+
+
+MACHINE = (
+    "ROOT",
+    [
+        path(
+            ["Microsoft", "Windows", "CurrentVersion"],
+            uninstall(
+                (
+                    "{23170F69-40C1-2702-2409-000001000000}",
+                    [],
+                    [
+                        ("DisplayName", "7-Zip 24.09 (x64 edition)"),
+                        ("Publisher", "Igor Pavlov"),
+                        ("DisplayVersion", "24.09.00.0"),
+                        ("InstallLocation", "C:\\Program Files\\7-Zip\\"),
+                        ("WindowsInstaller", 1),
+                    ],
+                ),
+                (
+                    "7-Zip",
+                    [],
+                    [
+                        ("DisplayName", "7-Zip 24.09 (x64)"),
+                        ("Publisher", "Igor Pavlov"),
+                        ("DisplayVersion", "24.09"),
+                    ],
+                ),
+                (
+                    "Connection Manager",
+                    [],
+                    [("DisplayName", "Connection Manager"), ("SystemComponent", 1)],
+                ),
+                ("AddressBook", [], []),
+            ),
+        ),
+        path(
+            ["WOW6432Node", "Microsoft", "Windows", "CurrentVersion"],
+            uninstall(
+                (
+                    "Notepad++",
+                    [],
+                    [
+                        ("DisplayName", "Notepad++ (32-bit x86)"),
+                        ("Publisher", "Notepad++ Team"),
+                        ("DisplayVersion", "8.7.1"),
+                    ],
+                ),
+            ),
+        ),
+    ],
+    [],
+)
+
+USER = (
+    "ROOT",
+    [
+        path(
+            ["Software", "Microsoft", "Windows", "CurrentVersion"],
+            uninstall(
+                (
+                    "{771FD6B0-FA20-440A-A002-3B3BAC16DC50}_is1",
+                    [],
+                    [
+                        ("DisplayName", "Microsoft Visual Studio Code (User)"),
+                        ("Publisher", "Microsoft Corporation"),
+                        ("DisplayVersion", "1.95.3"),
+                        (
+                            "InstallLocation",
+                            "C:\\Users\\User\\AppData\\Local\\Programs\\Microsoft VS Code\\",
+                        ),
+                    ],
+                ),
+            ),
+        ),
+    ],
+    [],
+)
+
+with open("SOFTWARE", "wb") as file:
+    file.write(build(MACHINE))
+
+with open("NTUSER.DAT", "wb") as file:
+    file.write(build(USER))
+
+with open("RECURSIVE", "wb") as file:
+    file.write(recursive())